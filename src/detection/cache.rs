@@ -0,0 +1,425 @@
+use super::{FileStamp, FileSystem};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+const CACHE_FORMAT: u32 = 12;
+
+/// Per-file detection results persisted between runs.
+///
+/// Every entry remembers each path its detector looked at (the file itself,
+/// but also e.g. a sibling it probed for) with a fingerprint of what it saw,
+/// and is reused only while all of those fingerprints still match. A file
+/// whose size and mtime are unchanged is trusted without being re-read;
+/// otherwise its content hash decides.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetectionCache {
+    version: String,
+    entries: BTreeMap<String, CacheEntry>,
+    #[serde(skip)]
+    used: BTreeSet<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    inputs: BTreeMap<PathBuf, Fingerprint>,
+    result: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Fingerprint {
+    Missing,
+    Dir {
+        hash: String,
+    },
+    File {
+        stamp: Option<FileStamp>,
+        hash: String,
+    },
+}
+
+impl Default for DetectionCache {
+    fn default() -> Self {
+        Self {
+            version: cache_version(),
+            entries: BTreeMap::new(),
+            used: BTreeSet::new(),
+        }
+    }
+}
+
+impl DetectionCache {
+    /// Loads a cache file, starting empty if it is missing, unreadable or
+    /// was written by another version of autonix.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|cache| cache.version == cache_version())
+            .unwrap_or_default()
+    }
+
+    /// Writes the entries used since loading; results for files that were
+    /// not visited this run are dropped.
+    pub fn save(&mut self, path: &Path) -> io::Result<()> {
+        let used = std::mem::take(&mut self.used);
+        self.entries.retain(|key, _| used.contains(key));
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+
+    /// `$XDG_CACHE_HOME/autonix/<hash of project root>.json`, falling back
+    /// to `~/.cache`.
+    pub fn default_path(root: &Path) -> Option<PathBuf> {
+        let cache_home = env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let key = hash_bytes(root.to_string_lossy().as_bytes());
+
+        Some(
+            cache_home
+                .join("autonix")
+                .join(format!("{}.json", &key[..16])),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn get_or_compute<T>(
+        &mut self,
+        kind: &str,
+        path: &Path,
+        fs: &dyn FileSystem,
+        compute: impl FnOnce(&dyn FileSystem) -> T,
+    ) -> T
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let key = format!("{kind}:{}", path.display());
+        self.used.insert(key.clone());
+
+        if let Some(entry) = self.entries.get_mut(&key)
+            && entry.is_fresh(fs)
+            && let Ok(result) = serde_json::from_value(entry.result.clone())
+        {
+            return result;
+        }
+
+        let recorder = RecordingFileSystem::new(fs);
+        let result = compute(&recorder);
+        let inputs = recorder
+            .touched
+            .into_inner()
+            .into_iter()
+            .map(|input| {
+                let fingerprint = Fingerprint::of(fs, &input);
+                (input, fingerprint)
+            })
+            .collect();
+
+        match serde_json::to_value(&result) {
+            Ok(value) => {
+                self.entries.insert(
+                    key,
+                    CacheEntry {
+                        inputs,
+                        result: value,
+                    },
+                );
+            }
+            Err(_) => {
+                self.entries.remove(&key);
+            }
+        }
+
+        result
+    }
+}
+
+impl CacheEntry {
+    fn is_fresh(&mut self, fs: &dyn FileSystem) -> bool {
+        self.inputs
+            .iter_mut()
+            .all(|(path, fingerprint)| match fingerprint {
+                Fingerprint::File { stamp, hash } => {
+                    let current = fs.stamp(path);
+                    if stamp.is_some() && current == *stamp {
+                        return true;
+                    }
+                    if fs.read(path).is_ok_and(|bytes| hash_bytes(&bytes) == *hash) {
+                        *stamp = current;
+                        return true;
+                    }
+                    false
+                }
+                Fingerprint::Dir { hash } => dir_hash(fs, path).is_some_and(|h| h == *hash),
+                Fingerprint::Missing => !fs.exists(path),
+            })
+    }
+}
+
+impl Fingerprint {
+    fn of(fs: &dyn FileSystem, path: &Path) -> Self {
+        if fs.is_file(path)
+            && let Ok(bytes) = fs.read(path)
+        {
+            return Fingerprint::File {
+                stamp: fs.stamp(path),
+                hash: hash_bytes(&bytes),
+            };
+        }
+
+        match dir_hash(fs, path) {
+            Some(hash) => Fingerprint::Dir { hash },
+            None => Fingerprint::Missing,
+        }
+    }
+}
+
+/// Keys the cache on the running build as well as the crate version and
+/// format, so a rebuilt autonix never reuses what an older detector produced
+/// even when nobody remembered to bump [`CACHE_FORMAT`].
+fn cache_version() -> String {
+    format!(
+        "{}-{CACHE_FORMAT}-{}",
+        env!("CARGO_PKG_VERSION"),
+        build_stamp()
+    )
+}
+
+/// Hash of the executable's path, size and mtime. Every Nix build lands at a
+/// new store path and every cargo build rewrites the binary, so this changes
+/// whenever the code does.
+fn build_stamp() -> String {
+    let stamp = env::current_exe().ok().and_then(|exe| {
+        let meta = fs::metadata(&exe).ok()?;
+        let mtime = meta
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?;
+        Some(format!(
+            "{}:{}:{}",
+            exe.display(),
+            meta.len(),
+            mtime.as_nanos()
+        ))
+    });
+    hash_bytes(stamp.unwrap_or_default().as_bytes())[..16].to_string()
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn dir_hash(fs: &dyn FileSystem, path: &Path) -> Option<String> {
+    if !fs.is_dir(path) {
+        return None;
+    }
+    let mut names: Vec<String> = fs
+        .read_dir(path)
+        .ok()?
+        .iter()
+        .filter_map(|entry| entry.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+
+    Some(hash_bytes(names.join("\n").as_bytes()))
+}
+
+/// Passes calls through while noting every path that was looked at.
+struct RecordingFileSystem<'a> {
+    inner: &'a dyn FileSystem,
+    touched: RefCell<BTreeSet<PathBuf>>,
+}
+
+impl<'a> RecordingFileSystem<'a> {
+    fn new(inner: &'a dyn FileSystem) -> Self {
+        Self {
+            inner,
+            touched: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    fn touch(&self, path: &Path) {
+        self.touched.borrow_mut().insert(path.to_path_buf());
+    }
+}
+
+impl FileSystem for RecordingFileSystem<'_> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.touch(path);
+        self.inner.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.touch(path);
+        self.inner.read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.touch(path);
+        self.inner.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.touch(path);
+        self.inner.is_dir(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.touch(path);
+        self.inner.read_dir(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.touch(path);
+        self.inner.exists(path)
+    }
+
+    fn stamp(&self, path: &Path) -> Option<FileStamp> {
+        self.inner.stamp(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    fn count_reads(cache: &mut DetectionCache, path: &Path, calls: &Cell<u32>) -> String {
+        cache.get_or_compute("content", path, &LocalFileSystem, |fs| {
+            calls.set(calls.get() + 1);
+            fs.read_to_string(path).unwrap_or_default()
+        })
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_results() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".nvmrc");
+        fs::write(&path, "20\n").unwrap();
+
+        let mut cache = DetectionCache::default();
+        let calls = Cell::new(0);
+        assert_eq!(count_reads(&mut cache, &path, &calls), "20\n");
+        assert_eq!(count_reads(&mut cache, &path, &calls), "20\n");
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_cache_recomputes_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".nvmrc");
+        fs::write(&path, "20\n").unwrap();
+
+        let mut cache = DetectionCache::default();
+        let calls = Cell::new(0);
+        count_reads(&mut cache, &path, &calls);
+
+        fs::write(&path, "22.1\n").unwrap();
+        assert_eq!(count_reads(&mut cache, &path, &calls), "22.1\n");
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_cache_tracks_probed_siblings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        let sibling = dir.path().join("yarn.lock");
+        fs::write(&path, "{}").unwrap();
+
+        let mut cache = DetectionCache::default();
+        let probe = |cache: &mut DetectionCache| {
+            cache.get_or_compute("probe", &path, &LocalFileSystem, |fs| fs.exists(&sibling))
+        };
+
+        assert!(!probe(&mut cache));
+        fs::write(&sibling, "").unwrap();
+        assert!(probe(&mut cache));
+        assert!(probe(&mut cache));
+    }
+
+    #[test]
+    fn test_cache_round_trips_through_disk() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".nvmrc");
+        let cache_path = dir.path().join("cache/cache.json");
+        fs::write(&path, "20\n").unwrap();
+
+        let mut cache = DetectionCache::default();
+        let calls = Cell::new(0);
+        count_reads(&mut cache, &path, &calls);
+        cache.save(&cache_path).unwrap();
+
+        let mut cache = DetectionCache::load(&cache_path);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(count_reads(&mut cache, &path, &calls), "20\n");
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_cache_save_drops_unused_entries() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".nvmrc");
+        let cache_path = dir.path().join("cache.json");
+        fs::write(&path, "20\n").unwrap();
+
+        let mut cache = DetectionCache::default();
+        count_reads(&mut cache, &path, &Cell::new(0));
+        cache.save(&cache_path).unwrap();
+
+        let mut cache = DetectionCache::load(&cache_path);
+        cache.save(&cache_path).unwrap();
+        assert!(DetectionCache::load(&cache_path).is_empty());
+    }
+
+    #[test]
+    fn test_cache_load_ignores_other_versions() {
+        let dir = TempDir::new().unwrap();
+        let cache_path = dir.path().join("cache.json");
+        fs::write(&cache_path, r#"{"version":"0.0.0-0","entries":{}}"#).unwrap();
+
+        assert!(DetectionCache::load(&cache_path).is_empty());
+        assert_eq!(DetectionCache::load(&cache_path).version, cache_version());
+    }
+
+    #[test]
+    fn test_cache_load_ignores_other_builds() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".nvmrc");
+        let cache_path = dir.path().join("cache.json");
+        fs::write(&path, "20\n").unwrap();
+
+        let mut cache = DetectionCache::default();
+        count_reads(&mut cache, &path, &Cell::new(0));
+        cache.version = format!("{}-{CACHE_FORMAT}-0000", env!("CARGO_PKG_VERSION"));
+        cache.save(&cache_path).unwrap();
+
+        assert!(DetectionCache::load(&cache_path).is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+use super::FileSystem;
+use super::task_runner::workspace_glob_matches;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// The `[workspace]` table of a `Cargo.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CargoWorkspace {
+    pub members: Vec<String>,
+    pub exclude: Vec<String>,
+    pub default_members: Vec<String>,
+}
+
+/// The parts of a `Cargo.toml` that decide which cargo commands apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CargoManifest {
+    pub path: PathBuf,
+    /// `package.name`; absent for a virtual workspace manifest.
+    pub package: Option<String>,
+    pub workspace: Option<CargoWorkspace>,
+    /// Declared features other than `default`.
+    pub features: Vec<String>,
+    pub has_lib: bool,
+    pub bins: Vec<String>,
+    pub examples: Vec<String>,
+    pub benches: Vec<String>,
+    /// `[package.metadata.docs.rs]` feature flags, when the crate configures
+    /// its docs.rs build.
+    pub docs_rs_features: Option<DocsRsFeatures>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum DocsRsFeatures {
+    All,
+    Listed(Vec<String>),
+}
+
+impl CargoManifest {
+    pub fn from_path(fs: &dyn FileSystem, path: &Path) -> Option<Self> {
+        let content = fs.read_to_string(path).ok()?;
+        let parsed: toml::Value = toml::from_str(&content).ok()?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let strings = |value: Option<&toml::Value>| -> Vec<String> {
+            value
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        };
+        let target_names = |key: &str| -> Vec<String> {
+            parsed
+                .get(key)
+                .and_then(|t| t.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|t| t.get("name")?.as_str().map(str::to_string))
+                .collect()
+        };
+
+        let package = parsed.get("package");
+        let workspace = parsed.get("workspace").map(|ws| CargoWorkspace {
+            members: strings(ws.get("members")),
+            exclude: strings(ws.get("exclude")),
+            default_members: strings(ws.get("default-members")),
+        });
+        let features = parsed
+            .get("features")
+            .and_then(|f| f.as_table())
+            .map(|f| f.keys().filter(|k| *k != "default").cloned().collect())
+            .unwrap_or_default();
+
+        let docs_rs = package
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("docs")?.get("rs"));
+        let docs_rs_features = docs_rs.map(|config| {
+            if config.get("all-features").and_then(|a| a.as_bool()) == Some(true) {
+                DocsRsFeatures::All
+            } else {
+                DocsRsFeatures::Listed(strings(config.get("features")))
+            }
+        });
+
+        Some(Self {
+            path: path.to_path_buf(),
+            package: package
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .map(str::to_string),
+            workspace,
+            features,
+            has_lib: package.is_some()
+                && (parsed.get("lib").is_some() || fs.is_file(&dir.join("src/lib.rs"))),
+            bins: target_names("bin"),
+            examples: target_names("example"),
+            benches: target_names("bench"),
+            docs_rs_features,
+        })
+    }
+
+    /// Member manifests of this workspace, with `members` globs resolved and
+    /// `exclude` applied. The root package, if any, is not included.
+    pub fn workspace_members(&self, fs: &dyn FileSystem) -> Vec<CargoManifest> {
+        let Some(workspace) = &self.workspace else {
+            return vec![];
+        };
+        let root = self.path.parent().unwrap_or(Path::new(""));
+
+        let mut members: Vec<CargoManifest> = Vec::new();
+        for pattern in &workspace.members {
+            for dir in expand_member_glob(fs, root, pattern) {
+                let Ok(relative) = dir.strip_prefix(root) else {
+                    continue;
+                };
+                if relative.as_os_str().is_empty()
+                    || workspace
+                        .exclude
+                        .iter()
+                        .any(|ex| workspace_glob_matches(ex, relative))
+                {
+                    continue;
+                }
+                if let Some(manifest) = Self::from_path(fs, &dir.join("Cargo.toml"))
+                    && manifest.package.is_some()
+                    && !members.iter().any(|m| m.path == manifest.path)
+                {
+                    members.push(manifest);
+                }
+            }
+        }
+        members
+    }
+}
+
+/// Directories matched by a `members` entry, expanding wildcards one path
+/// segment at a time.
+pub(crate) fn expand_member_glob(fs: &dyn FileSystem, root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for segment in pattern.trim_start_matches("./").split('/') {
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        if !segment.contains(['*', '?']) {
+            dirs = dirs.into_iter().map(|d| d.join(segment)).collect();
+            continue;
+        }
+        let mut matched: Vec<PathBuf> = dirs
+            .iter()
+            .flat_map(|d| fs.read_dir(d).unwrap_or_default())
+            .filter(|p| fs.is_dir(p))
+            .filter(|p| {
+                p.file_name()
+                    .is_some_and(|name| workspace_glob_matches(segment, Path::new(name)))
+            })
+            .collect();
+        matched.sort();
+        dirs = matched;
+    }
+    dirs.retain(|d| fs.is_dir(d));
+    dirs
+}
+
+/// Whether `manifest` is a member of a workspace declared in an ancestor
+/// directory, which then runs its commands instead.
+pub fn in_cargo_workspace(manifest: &Path, fs: &dyn FileSystem) -> bool {
+    let Some(crate_dir) = manifest.parent() else {
+        return false;
+    };
+
+    for dir in crate_dir.ancestors().skip(1) {
+        let root_manifest = dir.join("Cargo.toml");
+        if !fs.is_file(&root_manifest) {
+            continue;
+        }
+        let Some(root) = CargoManifest::from_path(fs, &root_manifest) else {
+            continue;
+        };
+        if root.workspace.is_some() {
+            return root
+                .workspace_members(fs)
+                .iter()
+                .any(|member| member.path == manifest);
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_package_manifest() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            "Cargo.toml",
+            r#"
+[package]
+name = "demo"
+
+[features]
+default = ["std"]
+std = []
+serde = []
+
+[[bin]]
+name = "demo-cli"
+
+[[example]]
+name = "basic"
+
+[[bench]]
+name = "throughput"
+harness = false
+
+[package.metadata.docs.rs]
+all-features = true
+"#,
+        );
+        write(&dir, "src/lib.rs", "");
+
+        let manifest = CargoManifest::from_path(&LocalFileSystem, &path).unwrap();
+        assert_eq!(manifest.package.as_deref(), Some("demo"));
+        assert_eq!(manifest.features, ["serde", "std"]);
+        assert!(manifest.has_lib);
+        assert_eq!(manifest.bins, ["demo-cli"]);
+        assert_eq!(manifest.examples, ["basic"]);
+        assert_eq!(manifest.benches, ["throughput"]);
+        assert_eq!(manifest.docs_rs_features, Some(DocsRsFeatures::All));
+    }
+
+    #[test]
+    fn test_workspace_members_and_exclude() {
+        let dir = TempDir::new().unwrap();
+        let root = write(
+            &dir,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"xtask\"]\nexclude = [\"crates/scratch\"]\n",
+        );
+        let core = write(
+            &dir,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\n",
+        );
+        write(&dir, "crates/cli/Cargo.toml", "[package]\nname = \"cli\"\n");
+        let scratch = write(
+            &dir,
+            "crates/scratch/Cargo.toml",
+            "[package]\nname = \"scratch\"\n",
+        );
+        write(&dir, "xtask/Cargo.toml", "[package]\nname = \"xtask\"\n");
+
+        let manifest = CargoManifest::from_path(&LocalFileSystem, &root).unwrap();
+        assert_eq!(manifest.package, None);
+        let names: Vec<String> = manifest
+            .workspace_members(&LocalFileSystem)
+            .into_iter()
+            .filter_map(|m| m.package)
+            .collect();
+        assert_eq!(names, ["cli", "core", "xtask"]);
+
+        assert!(in_cargo_workspace(&core, &LocalFileSystem));
+        assert!(!in_cargo_workspace(&scratch, &LocalFileSystem));
+        assert!(!in_cargo_workspace(&root, &LocalFileSystem));
+    }
+}
@@ -0,0 +1,440 @@
+use super::{
+    FileSystem, LanguageDetectionSignal, ManagedTool, VersionInfo, VersionSource,
+    tool_version_signals, version::parse_version_or_expression, versions_from_signal,
+};
+use serde_yaml::Value as YamlValue;
+use std::path::{Path, PathBuf};
+
+/// `actions/setup-*` style actions: the tool, the input naming its version,
+/// and the input naming a file that holds it.
+const SETUP_ACTIONS: &[(&str, ManagedTool, &str, &str)] = &[
+    (
+        "actions/setup-node",
+        ManagedTool::Node,
+        "node-version",
+        "node-version-file",
+    ),
+    (
+        "actions/setup-go",
+        ManagedTool::Go,
+        "go-version",
+        "go-version-file",
+    ),
+    (
+        "actions/setup-python",
+        ManagedTool::Python,
+        "python-version",
+        "python-version-file",
+    ),
+    (
+        "oven-sh/setup-bun",
+        ManagedTool::Bun,
+        "bun-version",
+        "bun-version-file",
+    ),
+];
+
+/// Versions pinned by a GitHub Actions workflow or a Dockerfile at `path`.
+///
+/// These only say what CI or an image happens to use, so they are
+/// [fallbacks](VersionSource::is_fallback) for the project's own files.
+pub fn ci_versions(path: &Path, fs: &dyn FileSystem) -> Vec<VersionInfo> {
+    if is_workflow(path) {
+        workflow_versions(path, fs)
+    } else if is_dockerfile(path) {
+        dockerfile_versions(path, fs)
+    } else {
+        vec![]
+    }
+}
+
+fn is_workflow(path: &Path) -> bool {
+    let in_workflows = path
+        .parent()
+        .filter(|dir| dir.file_name().is_some_and(|name| name == "workflows"))
+        .and_then(Path::parent)
+        .is_some_and(|dir| dir.file_name().is_some_and(|name| name == ".github"));
+
+    in_workflows
+        && path
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml")
+}
+
+fn is_dockerfile(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|f| f.to_str()) else {
+        return false;
+    };
+
+    matches!(name, "Dockerfile" | "Containerfile")
+        || name.starts_with("Dockerfile.")
+        || name.to_ascii_lowercase().ends_with(".dockerfile")
+}
+
+fn workflow_versions(path: &Path, fs: &dyn FileSystem) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+    let Ok(workflow) = serde_yaml::from_str::<YamlValue>(&content) else {
+        return vec![];
+    };
+    let Some(jobs) = workflow.get("jobs").and_then(|j| j.as_mapping()) else {
+        return vec![];
+    };
+    // `.github/workflows/x.yml`: `*-version-file` inputs are relative to the
+    // checkout, i.e. the directory holding `.github`.
+    let repo_root = path.ancestors().nth(3).unwrap_or(Path::new("."));
+
+    let mut versions = Vec::new();
+    for job in jobs.values() {
+        let matrix = job.get("strategy").and_then(|s| s.get("matrix"));
+        let steps = job.get("steps").and_then(|s| s.as_sequence());
+
+        for step in steps.into_iter().flatten() {
+            let Some(uses) = step.get("uses").and_then(|u| u.as_str()) else {
+                continue;
+            };
+            let (action, reference) = uses.split_once('@').unwrap_or((uses, ""));
+            let inputs = step.get("with");
+            let input = |key: &str| inputs.and_then(|w| w.get(key)).and_then(yaml_scalar);
+
+            let mut push = |tool: ManagedTool, raw: String| {
+                versions.push(VersionInfo {
+                    parsed: parse_version_or_expression(&raw),
+                    raw,
+                    source: VersionSource::GithubActions(tool),
+                    path: path.to_path_buf(),
+                });
+            };
+
+            if let Some(&(_, tool, version_key, file_key)) =
+                SETUP_ACTIONS.iter().find(|(name, ..)| *name == action)
+            {
+                if let Some(value) = input(version_key) {
+                    for raw in expand_matrix(&value, matrix) {
+                        // `python-version` may list several versions, one per line.
+                        raw.split_whitespace()
+                            .for_each(|version| push(tool, version.to_string()));
+                    }
+                } else if let Some(file) = input(file_key) {
+                    for file in expand_matrix(&file, matrix) {
+                        versions_in_file(fs, &repo_root.join(file), tool)
+                            .into_iter()
+                            .for_each(|version| push(tool, version));
+                    }
+                }
+                continue;
+            }
+
+            let toolchain = match action {
+                // `dtolnay/rust-toolchain@1.78`, or `@master` with a `toolchain` input.
+                "dtolnay/rust-toolchain" => input("toolchain")
+                    .or_else(|| (reference != "master").then(|| reference.to_string())),
+                "actions-rs/toolchain" => input("toolchain"),
+                _ => None,
+            };
+            for raw in toolchain.iter().flat_map(|t| expand_matrix(t, matrix)) {
+                push(ManagedTool::Rust, raw);
+            }
+        }
+    }
+
+    versions
+}
+
+/// Numbers are kept as YAML prints them, so an unquoted `3.10` reads as `3.1`.
+fn yaml_scalar(value: &YamlValue) -> Option<String> {
+    match value {
+        YamlValue::String(s) => Some(s.trim().to_string()),
+        YamlValue::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+    .filter(|s| !s.is_empty())
+}
+
+/// Substitutes a `${{ matrix.<key> }}` reference with every value the
+/// matrix, including its `include` entries, gives `key`. Values with any
+/// other expression are dropped.
+fn expand_matrix(value: &str, matrix: Option<&YamlValue>) -> Vec<String> {
+    let Some((before, rest)) = value.split_once("${{") else {
+        return vec![value.to_string()];
+    };
+    let Some((expression, after)) = rest.split_once("}}") else {
+        return vec![];
+    };
+    let Some(key) = expression.trim().strip_prefix("matrix.") else {
+        return vec![];
+    };
+    let Some(matrix) = matrix else {
+        return vec![];
+    };
+
+    let listed: Vec<String> = matrix
+        .get(key)
+        .map(|values| match values {
+            YamlValue::Sequence(values) => values.iter().filter_map(yaml_scalar).collect(),
+            value => yaml_scalar(value).into_iter().collect(),
+        })
+        .unwrap_or_default();
+    let included = matrix
+        .get("include")
+        .and_then(|i| i.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get(key).and_then(yaml_scalar));
+
+    let mut values: Vec<String> = Vec::new();
+    for value in listed.into_iter().chain(included) {
+        let expanded = format!("{before}{value}{after}");
+        if !values.contains(&expanded) {
+            values.push(expanded);
+        }
+    }
+    values
+}
+
+/// The versions of `tool` in a file a `*-version-file` input points at,
+/// read the same way as when the file is found in the project. A file under
+/// another name, like `.node-version-ci`, gives its first version-like line.
+fn versions_in_file(fs: &dyn FileSystem, path: &Path, tool: ManagedTool) -> Vec<String> {
+    let mut signals = tool_version_signals(path, fs);
+    if let Ok(signal) = LanguageDetectionSignal::try_from(path.to_path_buf()) {
+        signals.push(signal);
+    }
+
+    let versions: Vec<String> = signals
+        .iter()
+        .filter_map(|signal| versions_from_signal(signal, fs))
+        .flatten()
+        .filter(|v| v.source.tool() == tool.name())
+        .map(|v| v.raw)
+        .collect();
+    if !versions.is_empty() || !signals.is_empty() {
+        return versions;
+    }
+
+    fs.read_to_string(path)
+        .ok()
+        .and_then(|content| first_version_line(&content))
+        .into_iter()
+        .collect()
+}
+
+/// The first line that starts with a version, such as `20.11.1` or `v20`,
+/// past blank lines and `#` comments.
+fn first_version_line(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .find(|line| {
+            line.trim_start_matches('v')
+                .starts_with(|c: char| c.is_ascii_digit())
+        })
+        .map(str::to_string)
+}
+
+fn dockerfile_versions(path: &Path, fs: &dyn FileSystem) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            if !words.next()?.eq_ignore_ascii_case("FROM") {
+                return None;
+            }
+            let image = words.find(|w| !w.starts_with("--"))?;
+            let (tool, version) = image_version(image)?;
+
+            Some(VersionInfo {
+                parsed: parse_version_or_expression(&version),
+                raw: version,
+                source: VersionSource::Dockerfile(tool),
+                path: PathBuf::from(path),
+            })
+        })
+        .collect()
+}
+
+/// `golang:1.22-alpine` -> (Go, `1.22`); tags without a leading version
+/// (`latest`, `lts`, `${VERSION}`) are skipped.
+fn image_version(image: &str) -> Option<(ManagedTool, String)> {
+    let image = image.split('@').next()?;
+    let (name, tag) = image.rsplit_once(':')?;
+    let tool = ManagedTool::from_name(name.rsplit('/').next()?)?;
+
+    let version = tag.split('-').next()?;
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| (tool, version.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn raw_versions(versions: &[VersionInfo], tool: ManagedTool) -> Vec<&str> {
+        versions
+            .iter()
+            .filter(|v| v.source == VersionSource::GithubActions(tool))
+            .map(|v| v.raw.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_workflow_setup_actions_and_matrix() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            ".github/workflows/ci.yml",
+            r#"
+jobs:
+  test:
+    strategy:
+      matrix:
+        node: [18, 20]
+        include:
+          - node: 22
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: ${{ matrix.node }}
+      - uses: actions/setup-go@v5
+        with:
+          go-version: "1.22.x"
+      - uses: dtolnay/rust-toolchain@1.78
+  lint:
+    steps:
+      - uses: actions/setup-python@v5
+        with:
+          python-version: |
+            3.11
+            3.12
+      - uses: dtolnay/rust-toolchain@master
+        with:
+          toolchain: nightly
+"#,
+        );
+
+        let versions = ci_versions(&path, &LocalFileSystem);
+        assert_eq!(
+            raw_versions(&versions, ManagedTool::Node),
+            ["18", "20", "22"]
+        );
+        assert_eq!(raw_versions(&versions, ManagedTool::Go), ["1.22.x"]);
+        assert_eq!(
+            raw_versions(&versions, ManagedTool::Python),
+            ["3.11", "3.12"]
+        );
+        assert_eq!(
+            raw_versions(&versions, ManagedTool::Rust),
+            ["1.78", "nightly"]
+        );
+    }
+
+    #[test]
+    fn test_workflow_version_file_indirection() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, ".nvmrc", "20.11.1\n");
+        write(&dir, "go.mod", "module example.com\n\ngo 1.23\n");
+        let path = write(
+            &dir,
+            ".github/workflows/ci.yaml",
+            r#"
+jobs:
+  build:
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version-file: .nvmrc
+      - uses: actions/setup-go@v5
+        with:
+          go-version-file: go.mod
+"#,
+        );
+
+        let versions = ci_versions(&path, &LocalFileSystem);
+        assert_eq!(raw_versions(&versions, ManagedTool::Node), ["20.11.1"]);
+        assert_eq!(raw_versions(&versions, ManagedTool::Go), ["1.23"]);
+        assert!(versions.iter().all(|v| v.path == path));
+    }
+
+    #[test]
+    fn test_version_files_under_other_names() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "pyproject.toml",
+            "[project]\nname = \"demo\"\nrequires-python = \">=3.11\"\n",
+        );
+        write(&dir, ".node-version-ci", "# pinned for CI\n\nv20.11.1\n");
+        let path = write(
+            &dir,
+            ".github/workflows/ci.yml",
+            r#"
+jobs:
+  test:
+    steps:
+      - uses: actions/setup-python@v5
+        with:
+          python-version-file: pyproject.toml
+      - uses: actions/setup-node@v4
+        with:
+          node-version-file: .node-version-ci
+"#,
+        );
+
+        let versions = ci_versions(&path, &LocalFileSystem);
+        assert_eq!(raw_versions(&versions, ManagedTool::Python), [">=3.11"]);
+        assert_eq!(raw_versions(&versions, ManagedTool::Node), ["v20.11.1"]);
+    }
+
+    #[test]
+    fn test_dockerfile_base_images() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            "Dockerfile",
+            "FROM golang:1.22-alpine AS build\nRUN go build\nFROM --platform=linux/amd64 docker.io/library/python:3.12-slim\nFROM node:lts\nFROM gcr.io/distroless/static\n",
+        );
+
+        let versions = ci_versions(&path, &LocalFileSystem);
+        let found: Vec<(VersionSource, &str)> = versions
+            .iter()
+            .map(|v| (v.source, v.raw.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (VersionSource::Dockerfile(ManagedTool::Go), "1.22"),
+                (VersionSource::Dockerfile(ManagedTool::Python), "3.12"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_yaml_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            "deploy/ci.yml",
+            "jobs:\n  a:\n    steps:\n      - uses: actions/setup-node@v4\n        with:\n          node-version: 20\n",
+        );
+
+        assert!(ci_versions(&path, &LocalFileSystem).is_empty());
+    }
+}
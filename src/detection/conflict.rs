@@ -0,0 +1,409 @@
+use super::{
+    Language, PackageManagerDetection, PackageManagerSource, VersionDetection, VersionInfo,
+    VersionRange, VersionSource, version::parse_version_or_expression,
+};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Two requirements on the same tool that no single version satisfies.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionDisagreement {
+    pub first: VersionInfo,
+    pub second: VersionInfo,
+}
+
+/// Version sources of one tool that cannot all be honoured, and the value
+/// generation falls back to: the highest requested version.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionConflict {
+    pub language: Language,
+    pub tool: &'static str,
+    pub disagreements: Vec<VersionDisagreement>,
+    pub chosen: VersionInfo,
+}
+
+impl VersionConflict {
+    /// Compares every pair of parseable requirements per tool that
+    /// [share a scope](share_scope), ignoring
+    /// [overridden](super::VersionSource::overridden_by) and
+    /// [fallback](super::VersionSource::is_fallback) sources, since a CI
+    /// matrix lists several versions on purpose; sources of different tools
+    /// (e.g. Node and Bun) never conflict.
+    pub fn from_version_detection(detection: &VersionDetection) -> Vec<Self> {
+        let mut tools: Vec<&'static str> = detection
+            .effective_versions()
+            .filter(|v| !v.source.is_fallback())
+            .map(|v| v.source.tool())
+            .collect();
+        tools.sort_unstable();
+        tools.dedup();
+
+        tools
+            .into_iter()
+            .filter_map(|tool| {
+                let versions: Vec<&VersionInfo> = detection
+                    .effective_versions()
+                    .filter(|v| !v.source.is_fallback() && v.source.tool() == tool)
+                    .collect();
+                Self::from_versions(detection.language.clone(), tool, &versions)
+            })
+            .collect()
+    }
+
+    /// Compares the `packageManager` and `volta` pins of each package
+    /// manager, e.g. `yarn@4.1.0` against a Volta `yarn` of `1.22.19`.
+    /// Generation installs the root-most pin, preferring `packageManager`,
+    /// so that pin is the one reported as chosen.
+    pub fn from_package_managers(detection: &PackageManagerDetection) -> Vec<Self> {
+        let mut pins: Vec<VersionInfo> = detection
+            .package_managers
+            .iter()
+            .filter_map(|info| {
+                let source = match info.source {
+                    PackageManagerSource::PackageJson => {
+                        VersionSource::PackageJsonPackageManager(info.package_manager)
+                    }
+                    PackageManagerSource::Volta => {
+                        VersionSource::VoltaPackageManager(info.package_manager)
+                    }
+                    _ => return None,
+                };
+                let version = info.version.as_deref()?;
+                // corepack pins may carry a hash: `pnpm@9.0.0+sha512.abc`.
+                let raw = version.split('+').next().unwrap_or(version);
+                Some(VersionInfo {
+                    raw: raw.to_string(),
+                    parsed: parse_version_or_expression(raw),
+                    source,
+                    path: info.path.clone(),
+                })
+            })
+            .collect();
+        pins.sort_by_key(|v| {
+            (
+                v.path.components().count(),
+                matches!(v.source, VersionSource::VoltaPackageManager(_)),
+            )
+        });
+
+        let mut tools: Vec<&'static str> = pins.iter().map(|v| v.source.tool()).collect();
+        tools.sort_unstable();
+        tools.dedup();
+
+        tools
+            .into_iter()
+            .filter_map(|tool| {
+                let versions: Vec<&VersionInfo> =
+                    pins.iter().filter(|v| v.source.tool() == tool).collect();
+                let chosen = versions[0].clone();
+                Self::from_versions(detection.language.clone(), tool, &versions)
+                    .map(|conflict| Self { chosen, ..conflict })
+            })
+            .collect()
+    }
+
+    fn from_versions(
+        language: Language,
+        tool: &'static str,
+        versions: &[&VersionInfo],
+    ) -> Option<Self> {
+        let ranged: Vec<_> = versions
+            .iter()
+            .filter_map(|&v| Some((v, v.range()?)))
+            .collect();
+        // A matrix, like `.python-versions`, lists its versions side by side
+        // on purpose, so its entries count as one set.
+        let same_matrix = |a: &VersionInfo, b: &VersionInfo| {
+            a.source.is_matrix() && a.source == b.source && a.path == b.path
+        };
+        let set_range = |v: &VersionInfo, range: &VersionRange| {
+            ranged
+                .iter()
+                .filter(|(other, _)| same_matrix(v, other))
+                .fold(range.clone(), |set, (_, other)| set.union(other))
+        };
+
+        let disagreements: Vec<VersionDisagreement> = ranged
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (first, first_range))| {
+                ranged[i + 1..]
+                    .iter()
+                    .filter(|(second, _)| share_scope(first, second))
+                    .filter(|(second, _)| !same_matrix(first, second))
+                    .filter(|(second, second_range)| {
+                        set_range(first, first_range)
+                            .intersect(&set_range(second, second_range))
+                            .is_empty()
+                    })
+                    .map(|(second, _)| VersionDisagreement {
+                        first: (*first).clone(),
+                        second: (*second).clone(),
+                    })
+            })
+            .collect();
+
+        if disagreements.is_empty() {
+            return None;
+        }
+
+        // A single-version pin outranks any entry of a matrix.
+        let chosen = versions
+            .iter()
+            .filter(|v| v.parsed.is_some())
+            .max_by_key(|v| {
+                v.parsed.as_ref().map(|p| {
+                    (
+                        !v.source.is_matrix(),
+                        p.major.unwrap_or(0),
+                        p.minor.unwrap_or(0),
+                        p.patch.unwrap_or(0),
+                    )
+                })
+            })?;
+
+        Some(Self {
+            language,
+            tool,
+            disagreements,
+            chosen: (*chosen).clone(),
+        })
+    }
+
+    /// One-line summary, e.g.
+    /// `node: 18 (NvmrcFile) vs >=20 (PackageJsonEnginesNode) -> using 22 (from NodeVersionFile)`.
+    pub fn summary(&self) -> String {
+        let mut out = format!("{}: ", self.tool);
+        for (i, disagreement) in self.disagreements.iter().enumerate() {
+            if i > 0 {
+                out.push_str("; ");
+            }
+            let _ = write!(
+                out,
+                "{} ({:?}) vs {} ({:?})",
+                disagreement.first.raw,
+                disagreement.first.source,
+                disagreement.second.raw,
+                disagreement.second.source
+            );
+        }
+        let _ = write!(
+            out,
+            " -> using {} (from {:?})",
+            self.chosen.raw, self.chosen.source
+        );
+        out
+    }
+}
+
+/// Whether two sources govern a common part of the project. A source
+/// applies to the directory it sits in and everything below, so a root
+/// `.tool-versions` meets every module while sibling modules never meet.
+fn share_scope(a: &VersionInfo, b: &VersionInfo) -> bool {
+    let a = a.path.parent().unwrap_or(Path::new(""));
+    let b = b.path.parent().unwrap_or(Path::new(""));
+    a.starts_with(b) || b.starts_with(a)
+}
+
+/// Conflicts across every language in `versions`, followed by those among
+/// the package-manager pins in `package_managers`.
+pub fn version_conflicts(
+    versions: &[VersionDetection],
+    package_managers: &[PackageManagerDetection],
+) -> Vec<VersionConflict> {
+    versions
+        .iter()
+        .flat_map(VersionConflict::from_version_detection)
+        .chain(
+            package_managers
+                .iter()
+                .flat_map(VersionConflict::from_package_managers),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{VersionSource, version::parse_version_or_expression};
+    use std::path::PathBuf;
+
+    fn info(raw: &str, source: VersionSource, path: &str) -> VersionInfo {
+        VersionInfo {
+            raw: raw.to_string(),
+            parsed: parse_version_or_expression(raw),
+            source,
+            path: PathBuf::from(path),
+        }
+    }
+
+    fn node_detection(versions: Vec<VersionInfo>) -> VersionDetection {
+        VersionDetection {
+            language: Language::JavaScript,
+            versions,
+            go_workspaces: vec![],
+        }
+    }
+
+    #[test]
+    fn test_reports_disagreeing_node_sources() {
+        let detection = node_detection(vec![
+            info("18", VersionSource::NvmrcFile, ".nvmrc"),
+            info(
+                ">=20",
+                VersionSource::PackageJsonEnginesNode,
+                "package.json",
+            ),
+            info("22", VersionSource::NodeVersionFile, ".node-version"),
+        ]);
+
+        let conflicts = VersionConflict::from_version_detection(&detection);
+        assert_eq!(conflicts.len(), 1);
+
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.tool, "node");
+        assert_eq!(conflict.disagreements.len(), 2);
+        assert!(conflict.disagreements.iter().all(|d| d.first.raw == "18"));
+        assert_eq!(conflict.chosen.raw, "22");
+        assert_eq!(
+            conflict.summary(),
+            "node: 18 (NvmrcFile) vs >=20 (PackageJsonEnginesNode); 18 (NvmrcFile) vs 22 (NodeVersionFile) -> using 22 (from NodeVersionFile)"
+        );
+    }
+
+    #[test]
+    fn test_compatible_sources_do_not_conflict() {
+        let detection = node_detection(vec![
+            info(
+                ">=18",
+                VersionSource::PackageJsonEnginesNode,
+                "package.json",
+            ),
+            info("20.11.1", VersionSource::NvmrcFile, ".nvmrc"),
+        ]);
+
+        assert!(VersionConflict::from_version_detection(&detection).is_empty());
+    }
+
+    #[test]
+    fn test_matrix_entries_are_one_set() {
+        let detection = |matrix: [&str; 2]| VersionDetection {
+            language: Language::Python,
+            versions: vec![
+                info(
+                    matrix[0],
+                    VersionSource::PythonVersionsFile,
+                    ".python-versions",
+                ),
+                info(
+                    matrix[1],
+                    VersionSource::PythonVersionsFile,
+                    ".python-versions",
+                ),
+                info("3.11.4", VersionSource::RuntimeTxt, "runtime.txt"),
+            ],
+            go_workspaces: vec![],
+        };
+
+        assert!(VersionConflict::from_version_detection(&detection(["3.11", "3.12"])).is_empty());
+
+        let conflicts = VersionConflict::from_version_detection(&detection(["3.12", "3.13"]));
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.disagreements.len(), 2);
+        assert!(
+            conflict
+                .disagreements
+                .iter()
+                .all(|d| d.second.raw == "3.11.4")
+        );
+        assert_eq!(conflict.chosen.raw, "3.11.4");
+    }
+
+    #[test]
+    fn test_only_sources_sharing_a_directory_conflict() {
+        let siblings = node_detection(vec![
+            info("18", VersionSource::NvmrcFile, "apps/a/.nvmrc"),
+            info("22", VersionSource::NvmrcFile, "apps/b/.nvmrc"),
+        ]);
+        assert!(VersionConflict::from_version_detection(&siblings).is_empty());
+
+        let nested = node_detection(vec![
+            info("18", VersionSource::NvmrcFile, ".nvmrc"),
+            info(
+                ">=20",
+                VersionSource::PackageJsonEnginesNode,
+                "apps/b/package.json",
+            ),
+        ]);
+        assert_eq!(VersionConflict::from_version_detection(&nested).len(), 1);
+    }
+
+    #[test]
+    fn test_different_tools_do_not_conflict() {
+        let detection = node_detection(vec![
+            info("18", VersionSource::NvmrcFile, ".nvmrc"),
+            info("1.1.0", VersionSource::BunVersionFile, ".bun-version"),
+            info(
+                "^5.3.0",
+                VersionSource::PackageJsonTypescript,
+                "package.json",
+            ),
+        ]);
+
+        assert!(VersionConflict::from_version_detection(&detection).is_empty());
+    }
+
+    #[test]
+    fn test_package_manager_pins_disagree() {
+        use crate::detection::{PackageManager, PackageManagerInfo};
+
+        let pin = |package_manager, source, path: &str, version: &str| PackageManagerInfo {
+            package_manager,
+            source,
+            path: PathBuf::from(path),
+            version: Some(version.to_string()),
+        };
+        let detection = PackageManagerDetection {
+            language: Language::JavaScript,
+            package_managers: vec![
+                pin(
+                    PackageManager::Yarn,
+                    PackageManagerSource::Volta,
+                    "package.json",
+                    "4.1.0",
+                ),
+                pin(
+                    PackageManager::Yarn,
+                    PackageManagerSource::PackageJson,
+                    "package.json",
+                    "1.22.19",
+                ),
+                pin(
+                    PackageManager::Pnpm,
+                    PackageManagerSource::PackageJson,
+                    "package.json",
+                    "9.0.0+sha512.abc",
+                ),
+                pin(
+                    PackageManager::Pnpm,
+                    PackageManagerSource::Volta,
+                    "package.json",
+                    "9.0.0",
+                ),
+            ],
+        };
+
+        let conflicts = VersionConflict::from_package_managers(&detection);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].tool, "yarn");
+        assert_eq!(conflicts[0].disagreements.len(), 1);
+        // `packageManager` wins, as in generation, even though Volta's is newer.
+        assert_eq!(conflicts[0].chosen.raw, "1.22.19");
+        assert_eq!(
+            conflicts[0].chosen.source,
+            VersionSource::PackageJsonPackageManager(PackageManager::Yarn)
+        );
+    }
+}
@@ -0,0 +1,462 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    process::Command,
+    time::UNIX_EPOCH,
+};
+
+/// Read-only view of a project tree used by detection.
+///
+/// Paths passed to these methods are the same paths the detection engine
+/// produces while walking, so virtual backends treat them relative to the
+/// root of their tree (`.` and `./go.mod` both resolve inside the tree).
+pub trait FileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    fn is_file(&self, path: &Path) -> bool;
+    fn is_dir(&self, path: &Path) -> bool;
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.is_file(path) || self.is_dir(path)
+    }
+
+    /// Size and modification time of a file, if the backend tracks them.
+    fn stamp(&self, _path: &Path) -> Option<FileStamp> {
+        None
+    }
+}
+
+/// Cheap identity of a file's contents, compared before hashing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified_secs: u64,
+    pub modified_nanos: u32,
+}
+
+/// The working tree on local disk.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalFileSystem;
+
+impl FileSystem for LocalFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        Ok(path
+            .read_dir()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect())
+    }
+
+    fn stamp(&self, path: &Path) -> Option<FileStamp> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(FileStamp {
+            size: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+        })
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+struct VirtualTree<T> {
+    files: BTreeMap<PathBuf, T>,
+    dirs: BTreeSet<PathBuf>,
+}
+
+impl<T> VirtualTree<T> {
+    fn new() -> Self {
+        Self {
+            files: BTreeMap::new(),
+            dirs: BTreeSet::from([PathBuf::new()]),
+        }
+    }
+
+    fn insert_dir(&mut self, path: &Path) {
+        let mut current = normalize(path);
+        loop {
+            if !self.dirs.insert(current.clone()) || !current.pop() {
+                break;
+            }
+        }
+    }
+
+    fn insert_file(&mut self, path: &Path, value: T) {
+        let key = normalize(path);
+        if let Some(parent) = key.parent() {
+            self.insert_dir(parent);
+        }
+        self.files.insert(key, value);
+    }
+
+    fn get(&self, path: &Path) -> Option<&T> {
+        self.files.get(&normalize(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.dirs.contains(&normalize(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let key = normalize(path);
+        if !self.dirs.contains(&key) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no such directory: {}", path.display()),
+            ));
+        }
+
+        let is_child = |candidate: &PathBuf| {
+            candidate.parent() == Some(key.as_path()) && candidate.as_path() != key.as_path()
+        };
+
+        Ok(self
+            .dirs
+            .iter()
+            .filter(|d| is_child(d))
+            .chain(self.files.keys().filter(|f| is_child(f)))
+            .filter_map(|child| child.file_name())
+            .map(|name| path.join(name))
+            .collect())
+    }
+
+    /// Drops a single leading directory shared by every entry, as found in
+    /// GitHub tarballs (`owner-repo-sha/...`) or `git archive --prefix`.
+    fn strip_common_root(self) -> Self {
+        let mut roots = self
+            .files
+            .keys()
+            .chain(self.dirs.iter())
+            .filter_map(|p| p.components().next());
+        let Some(first) = roots.next() else {
+            return self;
+        };
+        if roots.any(|c| c != first) || self.files.keys().any(|f| f.components().count() < 2) {
+            return self;
+        }
+
+        let prefix = PathBuf::from(first.as_os_str());
+        let mut stripped = VirtualTree::new();
+        for dir in &self.dirs {
+            if let Ok(rest) = dir.strip_prefix(&prefix) {
+                stripped.insert_dir(rest);
+            }
+        }
+        for (file, value) in self.files {
+            if let Ok(rest) = file.strip_prefix(&prefix) {
+                stripped.insert_file(rest, value);
+            }
+        }
+        stripped
+    }
+}
+
+/// A project tree loaded from a `.tar` or `.tar.gz` archive.
+///
+/// A single top-level directory shared by all entries is stripped, so both
+/// `git archive` output and GitHub source tarballs resolve to the repo root.
+#[derive(Debug)]
+pub struct TarFileSystem {
+    tree: VirtualTree<Vec<u8>>,
+}
+
+impl TarFileSystem {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let reader: Box<dyn Read + '_> = if bytes.starts_with(&[0x1f, 0x8b]) {
+            Box::new(flate2::read::GzDecoder::new(bytes))
+        } else {
+            Box::new(bytes)
+        };
+
+        let mut archive = tar::Archive::new(reader);
+        let mut tree = VirtualTree::new();
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.into_owned();
+            match entry.header().entry_type() {
+                tar::EntryType::Directory => tree.insert_dir(&path),
+                tar::EntryType::Regular | tar::EntryType::Continuous => {
+                    let mut content = Vec::new();
+                    entry.read_to_end(&mut content)?;
+                    tree.insert_file(&path, content);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            tree: tree.strip_common_root(),
+        })
+    }
+}
+
+impl FileSystem for TarFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.tree.get(path).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no such file in archive: {}", path.display()),
+            )
+        })
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.tree.get(path).is_some()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.tree.is_dir(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.tree.read_dir(path)
+    }
+}
+
+/// A project tree read from a revision of a git repository (bare or not)
+/// through `git ls-tree` and `git cat-file`, without a checkout.
+#[derive(Debug)]
+pub struct GitTreeFileSystem {
+    repo: PathBuf,
+    tree: VirtualTree<String>,
+}
+
+impl GitTreeFileSystem {
+    pub fn new(repo: &Path, rev: &str) -> io::Result<Self> {
+        let listing = run_git(repo, &["ls-tree", "-r", "-z", "--full-tree", rev])?;
+        let mut tree = VirtualTree::new();
+
+        for record in listing.split(|b| *b == 0).filter(|r| !r.is_empty()) {
+            let record = String::from_utf8_lossy(record);
+            let Some((meta, path)) = record.split_once('\t') else {
+                continue;
+            };
+            let mut fields = meta.split_whitespace();
+            let (Some(mode), Some(kind), Some(oid)) = (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            match kind {
+                "blob" if mode != "120000" => tree.insert_file(Path::new(path), oid.to_string()),
+                "commit" => tree.insert_dir(Path::new(path)),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            repo: repo.to_path_buf(),
+            tree,
+        })
+    }
+}
+
+fn run_git(repo: &Path, args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+impl FileSystem for GitTreeFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let oid = self.tree.get(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no such file in tree: {}", path.display()),
+            )
+        })?;
+        run_git(&self.repo, &["cat-file", "blob", oid])
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.tree.get(path).is_some()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.tree.is_dir(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.tree.read_dir(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn build_tar(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_normalize_strips_current_dir() {
+        assert_eq!(
+            normalize(Path::new("./src/main.rs")),
+            PathBuf::from("src/main.rs")
+        );
+        assert_eq!(normalize(Path::new(".")), PathBuf::new());
+        assert_eq!(normalize(Path::new("a/../b")), PathBuf::from("b"));
+    }
+
+    #[test]
+    fn test_local_file_system_reads_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("go.mod"), "module x\n").unwrap();
+
+        let local = LocalFileSystem;
+        assert!(local.is_dir(dir.path()));
+        assert!(local.is_file(&dir.path().join("go.mod")));
+        assert_eq!(
+            local.read_dir(dir.path()).unwrap(),
+            vec![dir.path().join("go.mod")]
+        );
+    }
+
+    #[test]
+    fn test_tar_file_system_lists_and_reads() {
+        let bytes = build_tar(&[
+            ("go.mod", "module x\n"),
+            ("cmd/app/main.go", "package main"),
+        ]);
+        let tar_fs = TarFileSystem::from_bytes(&bytes).unwrap();
+
+        assert!(tar_fs.is_dir(Path::new(".")));
+        assert!(tar_fs.is_dir(Path::new("./cmd/app")));
+        assert!(tar_fs.is_file(Path::new("./go.mod")));
+        assert!(!tar_fs.is_file(Path::new("./cmd")));
+        assert_eq!(
+            tar_fs.read_to_string(Path::new("./go.mod")).unwrap(),
+            "module x\n"
+        );
+
+        let mut root = tar_fs.read_dir(Path::new(".")).unwrap();
+        root.sort();
+        assert_eq!(
+            root,
+            vec![PathBuf::from("./cmd"), PathBuf::from("./go.mod")]
+        );
+    }
+
+    #[test]
+    fn test_tar_file_system_gzip_and_common_root() {
+        let bytes = gzip(&build_tar(&[
+            ("repo-abc123/package.json", "{}"),
+            ("repo-abc123/src/index.js", ""),
+        ]));
+        let tar_fs = TarFileSystem::from_bytes(&bytes).unwrap();
+
+        assert!(tar_fs.is_file(Path::new("./package.json")));
+        assert!(tar_fs.is_file(Path::new("src/index.js")));
+        assert!(!tar_fs.is_dir(Path::new("repo-abc123")));
+    }
+
+    #[test]
+    fn test_tar_file_system_missing_file() {
+        let tar_fs = TarFileSystem::from_bytes(&build_tar(&[("a.txt", "a")])).unwrap();
+        assert!(tar_fs.read_to_string(Path::new("b.txt")).is_err());
+        assert!(tar_fs.read_dir(Path::new("nope")).is_err());
+    }
+
+    #[test]
+    fn test_git_tree_file_system_reads_revision() {
+        let dir = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .output();
+            matches!(status, Ok(o) if o.status.success())
+        };
+        if !git(&["init", "-q"]) {
+            return;
+        }
+        fs::create_dir_all(dir.path().join("api")).unwrap();
+        fs::write(dir.path().join("api/go.mod"), "module api\n\ngo 1.22\n").unwrap();
+        assert!(git(&["add", "-A"]));
+        assert!(git(&["commit", "-q", "-m", "init"]));
+        fs::remove_file(dir.path().join("api/go.mod")).unwrap();
+
+        let git_fs = GitTreeFileSystem::new(dir.path(), "HEAD").unwrap();
+        assert!(git_fs.is_dir(Path::new("./api")));
+        assert!(git_fs.is_file(Path::new("./api/go.mod")));
+        assert_eq!(
+            git_fs.read_to_string(Path::new("./api/go.mod")).unwrap(),
+            "module api\n\ngo 1.22\n"
+        );
+        assert!(GitTreeFileSystem::new(dir.path(), "no-such-rev").is_err());
+    }
+}
@@ -0,0 +1,462 @@
+use super::FileSystem;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A recipe from a justfile or one of its imports and modules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JustRecipe {
+    /// The name as passed to `just`, with module recipes written `mod::name`.
+    pub name: String,
+    pub parameters: Vec<JustParameter>,
+    /// Recipes run before this one; `&&` subsequents are not included.
+    pub dependencies: Vec<String>,
+    /// The `#` comment above the recipe, or its `[doc(...)]` attribute.
+    pub description: Option<String>,
+    pub groups: Vec<String>,
+    /// Marked `[private]` or named with a leading underscore.
+    pub private: bool,
+    pub body: Vec<String>,
+}
+
+impl JustRecipe {
+    /// Parameters that must be passed on the command line.
+    pub fn required_parameters(&self) -> impl Iterator<Item = &str> {
+        self.parameters
+            .iter()
+            .filter(|p| p.required)
+            .map(|p| p.name.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JustParameter {
+    pub name: String,
+    /// No default value, and not a `*` variadic that may be empty.
+    pub required: bool,
+}
+
+/// The recipes of a justfile, including those of files it imports and of the
+/// modules it declares.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Justfile {
+    pub recipes: Vec<JustRecipe>,
+    /// Imported files and module sources that were found and read.
+    pub sources: Vec<PathBuf>,
+}
+
+impl Justfile {
+    /// Parses `path`, following `import` statements relative to the file that
+    /// contains them and `mod` statements to the module's source file.
+    pub fn from_path(fs: &dyn FileSystem, path: &Path) -> Option<Self> {
+        let content = fs.read_to_string(path).ok()?;
+        let mut parser = Parser {
+            fs: Some(fs),
+            visited: HashSet::from([path.to_path_buf()]),
+            ..Parser::default()
+        };
+        parser.scan(&content, path.parent(), "");
+        Some(Justfile {
+            recipes: parser.recipes,
+            sources: parser.sources,
+        })
+    }
+
+    /// Parses a single justfile; imports and modules are ignored.
+    pub fn parse(content: &str) -> Self {
+        let mut parser = Parser::default();
+        parser.scan(content, None, "");
+        Justfile {
+            recipes: parser.recipes,
+            sources: parser.sources,
+        }
+    }
+
+    /// Recipes `just --list` shows.
+    pub fn public_recipes(&self) -> impl Iterator<Item = &JustRecipe> {
+        self.recipes.iter().filter(|r| !r.private)
+    }
+}
+
+#[derive(Default)]
+struct Attributes {
+    private: bool,
+    groups: Vec<String>,
+    doc: Option<String>,
+}
+
+#[derive(Default)]
+struct Parser<'a> {
+    fs: Option<&'a dyn FileSystem>,
+    visited: HashSet<PathBuf>,
+    recipes: Vec<JustRecipe>,
+    sources: Vec<PathBuf>,
+}
+
+impl Parser<'_> {
+    /// `prefix` is the module path of the file, e.g. `db::` inside `mod db`.
+    fn scan(&mut self, content: &str, dir: Option<&Path>, prefix: &str) {
+        let mut comment: Option<String> = None;
+        let mut attributes = Attributes::default();
+        // Index into `recipes` of the recipe whose body is being read.
+        let mut current: Option<usize> = None;
+        // Open brackets of a multi-line assignment or setting.
+        let mut depth = 0i32;
+
+        for line in content.lines() {
+            if depth > 0 {
+                depth += bracket_depth(line);
+                continue;
+            }
+            if line.starts_with([' ', '\t']) {
+                if let Some(index) = current
+                    && !line.trim().is_empty()
+                {
+                    self.recipes[index].body.push(line.trim().to_string());
+                }
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                comment = None;
+                continue;
+            }
+            current = None;
+            if let Some(text) = trimmed.strip_prefix('#') {
+                if !text.starts_with('!') {
+                    comment = Some(text.trim().to_string());
+                }
+                continue;
+            }
+            if let Some(attrs) = trimmed.strip_prefix('[') {
+                parse_attributes(attrs.trim_end_matches(']'), &mut attributes);
+                continue;
+            }
+
+            let words: Vec<&str> = trimmed.split_whitespace().collect();
+            match words.as_slice() {
+                ["import" | "import?", path, ..] => {
+                    self.import(unquote(path), dir, prefix);
+                }
+                ["mod" | "mod?", name, rest @ ..] => {
+                    let path = rest.first().map(|p| unquote(p));
+                    self.module(name, path, dir, prefix, attributes.private);
+                }
+                ["set" | "alias" | "export" | "unexport", ..] => {
+                    depth = bracket_depth(trimmed);
+                }
+                _ if is_assignment(trimmed) => {
+                    depth = bracket_depth(trimmed);
+                }
+                _ => {
+                    if let Some(recipe) = parse_recipe(
+                        trimmed,
+                        prefix,
+                        std::mem::take(&mut attributes),
+                        comment.take(),
+                    ) && !self.recipes.iter().any(|r| r.name == recipe.name)
+                    {
+                        self.recipes.push(recipe);
+                        current = Some(self.recipes.len() - 1);
+                    }
+                }
+            }
+            comment = None;
+            attributes = Attributes::default();
+        }
+    }
+
+    fn import(&mut self, path: &str, dir: Option<&Path>, prefix: &str) {
+        let (Some(fs), Some(dir)) = (self.fs, dir) else {
+            return;
+        };
+        let path = dir.join(path);
+        if !self.visited.insert(path.clone()) {
+            return;
+        }
+        let Ok(content) = fs.read_to_string(&path) else {
+            return;
+        };
+        self.sources.push(path.clone());
+        self.scan(&content, path.parent(), prefix);
+    }
+
+    fn module(
+        &mut self,
+        name: &str,
+        path: Option<&str>,
+        dir: Option<&Path>,
+        prefix: &str,
+        private: bool,
+    ) {
+        let (Some(fs), Some(dir)) = (self.fs, dir) else {
+            return;
+        };
+        let base = match path {
+            Some(path) => dir.join(path),
+            None => dir.join(name),
+        };
+        let mut candidates = match path {
+            Some(_) if !fs.is_dir(&base) => vec![base.clone()],
+            Some(_) => vec![],
+            None => vec![dir.join(format!("{name}.just"))],
+        };
+        candidates.extend(
+            ["mod.just", "justfile", "Justfile", ".justfile"]
+                .iter()
+                .map(|file| base.join(file)),
+        );
+        let Some(source) = candidates.into_iter().find(|p| fs.is_file(p)) else {
+            return;
+        };
+        if !self.visited.insert(source.clone()) {
+            return;
+        }
+        let Ok(content) = fs.read_to_string(&source) else {
+            return;
+        };
+
+        self.sources.push(source.clone());
+        let first = self.recipes.len();
+        self.scan(&content, source.parent(), &format!("{prefix}{name}::"));
+        if private {
+            for recipe in &mut self.recipes[first..] {
+                recipe.private = true;
+            }
+        }
+    }
+}
+
+/// Applies `private, group('test'), doc("...")`-style attributes.
+fn parse_attributes(list: &str, attributes: &mut Attributes) {
+    for attr in split_top_level(list, ',') {
+        let attr = attr.trim();
+        let (name, value) = match attr.split_once(['(', ':']) {
+            Some((name, value)) => (
+                name.trim(),
+                Some(unquote(value.trim().trim_end_matches(')').trim())),
+            ),
+            None => (attr, None),
+        };
+        match (name, value) {
+            ("private", _) => attributes.private = true,
+            ("group", Some(group)) => attributes.groups.push(group.to_string()),
+            ("doc", Some(doc)) => attributes.doc = Some(doc.to_string()),
+            _ => {}
+        }
+    }
+}
+
+fn parse_recipe(
+    line: &str,
+    prefix: &str,
+    attributes: Attributes,
+    comment: Option<String>,
+) -> Option<JustRecipe> {
+    let line = line.strip_prefix('@').unwrap_or(line);
+    let colon = top_level_colon(line)?;
+    let mut header = split_top_level(&line[..colon], ' ')
+        .into_iter()
+        .filter(|w| !w.is_empty());
+    let name = header.next()?;
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return None;
+    }
+
+    let parameters = header
+        .map(|param| {
+            let sigils = param.len() - param.trim_start_matches(['+', '*', '$']).len();
+            let (param_name, default) = match param[sigils..].split_once('=') {
+                Some((param_name, _)) => (param_name, true),
+                None => (&param[sigils..], false),
+            };
+            JustParameter {
+                name: param_name.to_string(),
+                required: !default && !param[..sigils].contains('*'),
+            }
+        })
+        .collect();
+
+    let dependencies = line[colon + 1..]
+        .split("&&")
+        .next()
+        .unwrap_or("")
+        .split('#')
+        .next()
+        .unwrap_or("");
+    let dependencies = split_top_level(dependencies, ' ')
+        .into_iter()
+        .filter_map(|dep| {
+            let dep = dep.trim().trim_start_matches('(');
+            let dep = dep.split_whitespace().next()?.trim_end_matches(')');
+            Some(format!("{prefix}{dep}"))
+        })
+        .collect();
+
+    Some(JustRecipe {
+        name: format!("{prefix}{name}"),
+        parameters,
+        dependencies,
+        description: attributes.doc.or(comment),
+        groups: attributes.groups,
+        private: attributes.private || name.starts_with('_'),
+        body: vec![],
+    })
+}
+
+fn is_assignment(line: &str) -> bool {
+    line.split_once(":=").is_some_and(|(name, _)| {
+        let name = name.trim();
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+/// The first `:` outside quotes and parentheses that is not part of `:=`.
+fn top_level_colon(line: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut depth = 0;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, ':') if depth == 0 && !line[i + 1..].starts_with('=') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits on `separator` outside quotes and parentheses.
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut quote: Option<char> = None;
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, c) if c == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Net brackets opened on `line`, ignoring those inside strings.
+fn bracket_depth(line: &str) -> i32 {
+    let mut quote: Option<char> = None;
+    let mut depth = 0;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[' | '(' | '{') => depth += 1,
+            (None, ']' | ')' | '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(['\'', '"'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn names(justfile: &Justfile) -> Vec<&str> {
+        justfile.public_recipes().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_skips_settings_aliases_and_assignments() {
+        let justfile = Justfile::parse(
+            "set shell := [\"bash\",\n  \"-c\"]\nalias t := test\nexport RUST_LOG := \"debug\"\nversion := `git describe`\n\ntest:\n    cargo test\n",
+        );
+        assert_eq!(names(&justfile), ["test"]);
+        assert_eq!(justfile.recipes[0].body, ["cargo test"]);
+    }
+
+    #[test]
+    fn test_parameters_dependencies_and_docs() {
+        let justfile = Justfile::parse(
+            "# Deploy to an environment\ndeploy env target='x86:64' *flags: build (check env) && notify\n    ./deploy {{env}}\n\n[group('ci'), doc(\"Run checks\")]\n@check +names:\n    echo {{names}}\n",
+        );
+
+        let deploy = &justfile.recipes[0];
+        assert_eq!(
+            deploy.description.as_deref(),
+            Some("Deploy to an environment")
+        );
+        assert_eq!(deploy.required_parameters().collect::<Vec<_>>(), ["env"]);
+        assert_eq!(deploy.dependencies, ["build", "check"]);
+
+        let check = &justfile.recipes[1];
+        assert_eq!(check.name, "check");
+        assert_eq!(check.groups, ["ci"]);
+        assert_eq!(check.description.as_deref(), Some("Run checks"));
+        assert_eq!(check.required_parameters().collect::<Vec<_>>(), ["names"]);
+    }
+
+    #[test]
+    fn test_private_recipes() {
+        let justfile = Justfile::parse(
+            "_helper:\n    echo hi\n\n[private]\nsecret:\n    echo hi\n\n[linux]\nbuild:\n    make\n",
+        );
+        assert_eq!(names(&justfile), ["build"]);
+        assert_eq!(justfile.recipes.len(), 3);
+    }
+
+    #[test]
+    fn test_imports_and_modules() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("just")).unwrap();
+        fs::create_dir_all(dir.path().join("db")).unwrap();
+        fs::write(
+            dir.path().join("justfile"),
+            "import 'just/lint.just'\nimport? 'missing.just'\nmod db\nmod? docs\n\ntest: lint\n    cargo test\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("just/lint.just"),
+            "lint:\n    cargo clippy\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("db/mod.just"),
+            "migrate: setup\n    sqlx migrate run\n\nsetup:\n    sqlx database create\n",
+        )
+        .unwrap();
+
+        let justfile = Justfile::from_path(&LocalFileSystem, &dir.path().join("justfile")).unwrap();
+        assert_eq!(
+            names(&justfile),
+            ["lint", "db::migrate", "db::setup", "test"]
+        );
+        assert_eq!(justfile.recipes[1].dependencies, ["db::setup"]);
+    }
+}
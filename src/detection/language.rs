@@ -1,3 +1,4 @@
+use super::{CustomName, ManagedTool};
 use serde::Serialize;
 use std::path::PathBuf;
 
@@ -7,6 +8,9 @@ pub enum Language {
     Rust,
     Python,
     JavaScript,
+    /// A language without built-in support, contributed by a detector
+    /// registered at runtime or pinned in a version manager file.
+    Custom(CustomName),
 }
 
 impl Language {
@@ -16,6 +20,7 @@ impl Language {
             Language::Python => "python",
             Language::JavaScript => "nodejs",
             Language::Rust => "rust",
+            Language::Custom(name) => name,
         }
     }
 }
@@ -46,6 +51,17 @@ pub enum LanguageDetectionSource {
     SetupCfg,
     EnvironmentYml,
     PythonVersionFile,
+    PythonVersionsFile,
+    RuntimeTxt,
+    ToxIni,
+    NoxfilePy,
+    UvLock,
+    PdmLock,
+    PixiToml,
+    PixiLock,
+    HatchToml,
+    /// A pip-tools input such as `requirements.in` or `requirements-dev.in`.
+    RequirementsIn,
     PyFile,
 
     //NodeJS
@@ -70,6 +86,16 @@ pub enum LanguageDetectionSource {
     TsFile,
     JsxFile,
     TsxFile,
+
+    // Version managers, one signal per pinned tool
+    ToolVersions(ManagedTool),
+    MiseToml(ManagedTool),
+
+    // Registered at runtime
+    Custom {
+        language: CustomName,
+        name: CustomName,
+    },
 }
 
 impl From<&LanguageDetectionSource> for Language {
@@ -99,6 +125,16 @@ impl From<&LanguageDetectionSource> for Language {
             | LanguageDetectionSource::SetupCfg
             | LanguageDetectionSource::EnvironmentYml
             | LanguageDetectionSource::PythonVersionFile
+            | LanguageDetectionSource::PythonVersionsFile
+            | LanguageDetectionSource::RuntimeTxt
+            | LanguageDetectionSource::ToxIni
+            | LanguageDetectionSource::NoxfilePy
+            | LanguageDetectionSource::UvLock
+            | LanguageDetectionSource::PdmLock
+            | LanguageDetectionSource::PixiToml
+            | LanguageDetectionSource::PixiLock
+            | LanguageDetectionSource::HatchToml
+            | LanguageDetectionSource::RequirementsIn
             | LanguageDetectionSource::PyFile => Language::Python,
 
             // JavaScript/Node
@@ -123,6 +159,11 @@ impl From<&LanguageDetectionSource> for Language {
             | LanguageDetectionSource::TsFile
             | LanguageDetectionSource::JsxFile
             | LanguageDetectionSource::TsxFile => Language::JavaScript,
+
+            LanguageDetectionSource::ToolVersions(tool)
+            | LanguageDetectionSource::MiseToml(tool) => tool.language(),
+
+            LanguageDetectionSource::Custom { language, .. } => Language::Custom(language),
         }
     }
 }
@@ -194,8 +235,20 @@ impl TryFrom<PathBuf> for LanguageDetectionSignal {
             "poetry.lock" => Ok(LanguageDetectionSource::PoetryLock),
             "setup.py" => Ok(LanguageDetectionSource::SetupPy),
             "setup.cfg" => Ok(LanguageDetectionSource::SetupCfg),
-            "environment.yml" => Ok(LanguageDetectionSource::EnvironmentYml),
+            "environment.yml" | "environment.yaml" => Ok(LanguageDetectionSource::EnvironmentYml),
             ".python-version" => Ok(LanguageDetectionSource::PythonVersionFile),
+            ".python-versions" => Ok(LanguageDetectionSource::PythonVersionsFile),
+            "runtime.txt" => Ok(LanguageDetectionSource::RuntimeTxt),
+            "tox.ini" => Ok(LanguageDetectionSource::ToxIni),
+            "noxfile.py" => Ok(LanguageDetectionSource::NoxfilePy),
+            "uv.lock" => Ok(LanguageDetectionSource::UvLock),
+            "pdm.lock" => Ok(LanguageDetectionSource::PdmLock),
+            "pixi.toml" => Ok(LanguageDetectionSource::PixiToml),
+            "pixi.lock" => Ok(LanguageDetectionSource::PixiLock),
+            "hatch.toml" => Ok(LanguageDetectionSource::HatchToml),
+            name if name.starts_with("requirements") && name.ends_with(".in") => {
+                Ok(LanguageDetectionSource::RequirementsIn)
+            }
 
             // JavaScript/Node
             "package.json" => Ok(LanguageDetectionSource::PackageJson),
@@ -328,6 +381,22 @@ mod tests {
             Language::from(&LanguageDetectionSource::PythonVersionFile),
             Language::Python
         );
+        assert_eq!(
+            Language::from(&LanguageDetectionSource::PythonVersionsFile),
+            Language::Python
+        );
+        assert_eq!(
+            Language::from(&LanguageDetectionSource::RuntimeTxt),
+            Language::Python
+        );
+        assert_eq!(
+            Language::from(&LanguageDetectionSource::ToxIni),
+            Language::Python
+        );
+        assert_eq!(
+            Language::from(&LanguageDetectionSource::NoxfilePy),
+            Language::Python
+        );
         assert_eq!(
             Language::from(&LanguageDetectionSource::PyFile),
             Language::Python
@@ -831,6 +900,51 @@ mod tests {
                 ..
             }
         ));
+
+        let path = PathBuf::from(".python-versions");
+        let signal = LanguageDetectionSignal::try_from(path).unwrap();
+        assert!(matches!(
+            signal,
+            LanguageDetectionSignal::Strong {
+                source: LanguageDetectionSource::PythonVersionsFile,
+                ..
+            }
+        ));
+
+        let path = PathBuf::from("runtime.txt");
+        let signal = LanguageDetectionSignal::try_from(path).unwrap();
+        assert!(matches!(
+            signal,
+            LanguageDetectionSignal::Strong {
+                source: LanguageDetectionSource::RuntimeTxt,
+                ..
+            }
+        ));
+
+        let path = PathBuf::from("tox.ini");
+        let signal = LanguageDetectionSignal::try_from(path).unwrap();
+        assert!(matches!(
+            signal,
+            LanguageDetectionSignal::Strong {
+                source: LanguageDetectionSource::ToxIni,
+                ..
+            }
+        ));
+
+        for (name, expected) in [
+            ("uv.lock", "UvLock"),
+            ("pdm.lock", "PdmLock"),
+            ("pixi.toml", "PixiToml"),
+            ("pixi.lock", "PixiLock"),
+            ("hatch.toml", "HatchToml"),
+            ("requirements-dev.in", "RequirementsIn"),
+        ] {
+            let signal = LanguageDetectionSignal::try_from(PathBuf::from(name)).unwrap();
+            let LanguageDetectionSignal::Strong { source, .. } = signal else {
+                panic!("{name} should be a strong signal");
+            };
+            assert_eq!(format!("{source:?}"), expected);
+        }
     }
 
     #[test]
@@ -0,0 +1,343 @@
+use super::FileSystem;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A rule from a Makefile that can be invoked as `make <name>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MakeTarget {
+    pub name: String,
+    pub prerequisites: Vec<String>,
+    /// The `## help text` trailing the rule line.
+    pub description: Option<String>,
+    /// Recipe lines, without the leading tab.
+    pub recipe: Vec<String>,
+    pub phony: bool,
+}
+
+/// The rules of a Makefile and of the files it includes, in the order make
+/// reads them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Makefile {
+    pub targets: Vec<MakeTarget>,
+    /// Included files that were found and read.
+    pub includes: Vec<PathBuf>,
+}
+
+impl Makefile {
+    /// Parses `path`, following `include`, `-include` and `sinclude`
+    /// directives. Like make, included paths resolve against the directory
+    /// make runs in, which is that of `path`.
+    pub fn from_path(fs: &dyn FileSystem, path: &Path) -> Option<Self> {
+        let content = fs.read_to_string(path).ok()?;
+        let mut parser = Parser {
+            fs: Some(fs),
+            visited: HashSet::from([path.to_path_buf()]),
+            ..Parser::default()
+        };
+        parser.scan(&content, path.parent());
+        Some(parser.finish())
+    }
+
+    /// Parses a single Makefile; include directives are ignored.
+    pub fn parse(content: &str) -> Self {
+        let mut parser = Parser::default();
+        parser.scan(content, None);
+        parser.finish()
+    }
+
+    /// Targets meant to be invoked by hand: targets that look like file
+    /// paths only count when declared `.PHONY`.
+    pub fn tasks(&self) -> impl Iterator<Item = &MakeTarget> {
+        self.targets
+            .iter()
+            .filter(|t| t.phony || !t.name.contains(['/', '.']))
+    }
+}
+
+#[derive(Default)]
+struct Parser<'a> {
+    fs: Option<&'a dyn FileSystem>,
+    visited: HashSet<PathBuf>,
+    targets: Vec<MakeTarget>,
+    includes: Vec<PathBuf>,
+    phony: HashSet<String>,
+}
+
+impl Parser<'_> {
+    fn scan(&mut self, content: &str, dir: Option<&Path>) {
+        // Indices into `targets` of the rule whose recipe is being read.
+        let mut current: Vec<usize> = vec![];
+        let mut in_define = false;
+
+        for line in logical_lines(content) {
+            if in_define {
+                in_define = line.trim() != "endef";
+                continue;
+            }
+            if let Some(recipe) = line.strip_prefix('\t') {
+                for &index in &current {
+                    self.targets[index].recipe.push(recipe.trim().to_string());
+                }
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            current.clear();
+
+            let directive = trimmed.split_whitespace().next().unwrap_or("");
+            match directive {
+                "define" => {
+                    in_define = true;
+                    continue;
+                }
+                "include" | "-include" | "sinclude" => {
+                    let rest = trimmed[directive.len()..].to_string();
+                    self.include(&rest, dir);
+                    continue;
+                }
+                "ifeq" | "ifneq" | "ifdef" | "ifndef" | "else" | "endif" | "export"
+                | "unexport" | "override" | "vpath" | "undefine" => continue,
+                _ => {}
+            }
+
+            if let Some(rule) = parse_rule(trimmed) {
+                current = self.add_rule(rule);
+            }
+        }
+    }
+
+    fn include(&mut self, files: &str, dir: Option<&Path>) {
+        let (Some(fs), Some(dir)) = (self.fs, dir) else {
+            return;
+        };
+        let files = files.split('#').next().unwrap_or("");
+        for file in files.split_whitespace() {
+            // Paths built from variables can't be resolved without make.
+            if file.contains(['$', '*', '?']) {
+                continue;
+            }
+            let path = dir.join(file);
+            if !self.visited.insert(path.clone()) {
+                continue;
+            }
+            let Ok(content) = fs.read_to_string(&path) else {
+                continue;
+            };
+            self.includes.push(path);
+            self.scan(&content, Some(dir));
+        }
+    }
+
+    fn add_rule(&mut self, rule: Rule) -> Vec<usize> {
+        if rule.targets == [".PHONY"] {
+            self.phony.extend(rule.prerequisites);
+            return vec![];
+        }
+
+        let mut indices = vec![];
+        for name in rule.targets {
+            // Special targets, suffix rules, pattern rules and computed names.
+            if name.starts_with('.') || name.contains(['%', '$']) {
+                continue;
+            }
+            let index = match self.targets.iter().position(|t| t.name == name) {
+                Some(index) => index,
+                None => {
+                    self.targets.push(MakeTarget {
+                        name,
+                        prerequisites: vec![],
+                        description: None,
+                        recipe: vec![],
+                        phony: false,
+                    });
+                    self.targets.len() - 1
+                }
+            };
+            let target = &mut self.targets[index];
+            for prerequisite in &rule.prerequisites {
+                if !target.prerequisites.contains(prerequisite) {
+                    target.prerequisites.push(prerequisite.clone());
+                }
+            }
+            if target.description.is_none() {
+                target.description = rule.description.clone();
+            }
+            if let Some(recipe) = &rule.inline_recipe {
+                target.recipe.push(recipe.clone());
+            }
+            indices.push(index);
+        }
+        indices
+    }
+
+    fn finish(mut self) -> Makefile {
+        for target in &mut self.targets {
+            target.phony = self.phony.contains(&target.name);
+        }
+        Makefile {
+            targets: self.targets,
+            includes: self.includes,
+        }
+    }
+}
+
+struct Rule {
+    targets: Vec<String>,
+    prerequisites: Vec<String>,
+    description: Option<String>,
+    inline_recipe: Option<String>,
+}
+
+/// Splits `targets: prerequisites ; recipe ## help`, or returns `None` for
+/// variable assignments, target-specific variables and other non-rules.
+fn parse_rule(line: &str) -> Option<Rule> {
+    let (line, help) = match line.split_once("##") {
+        Some((rule, help)) => (rule, Some(help.trim())),
+        None => (line, None),
+    };
+    let colon = line.find(':')?;
+    if line[..colon].contains('=') {
+        return None;
+    }
+
+    let mut rest = line[colon + 1..].trim_start_matches(':');
+    if rest.starts_with('=') {
+        return None;
+    }
+    let mut inline_recipe = None;
+    if let Some((prerequisites, recipe)) = rest.split_once(';') {
+        rest = prerequisites;
+        inline_recipe = Some(recipe.trim().to_string()).filter(|r| !r.is_empty());
+    }
+    let rest = rest.split('#').next().unwrap_or("");
+    if rest.contains('=') {
+        return None;
+    }
+
+    let targets: Vec<String> = line[..colon]
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    if targets.is_empty() {
+        return None;
+    }
+    // Order-only prerequisites after `|` don't run on every invocation.
+    let prerequisites = rest
+        .split('|')
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .filter(|p| !p.contains('%'))
+        .map(str::to_string)
+        .collect();
+
+    Some(Rule {
+        targets,
+        prerequisites,
+        description: help.filter(|h| !h.is_empty()).map(str::to_string),
+        inline_recipe,
+    })
+}
+
+/// Lines with backslash continuations joined.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut pending: Option<String> = None;
+    for line in content.lines() {
+        let (text, continues) = match line.strip_suffix('\\') {
+            Some(text) => (text, true),
+            None => (line, false),
+        };
+        let joined = match pending.take() {
+            Some(mut previous) => {
+                previous.push(' ');
+                previous.push_str(text.trim_start());
+                previous
+            }
+            None => text.to_string(),
+        };
+        if continues {
+            pending = Some(joined);
+        } else {
+            lines.push(joined);
+        }
+    }
+    lines.extend(pending);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn names(makefile: &Makefile) -> Vec<&str> {
+        makefile.tasks().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_skips_variables_and_pattern_rules() {
+        let makefile = Makefile::parse(
+            "CC := gcc\nFLAGS ::= -O2\nPREFIX ?= /usr\nURL = http://example.com\n\n%.o: %.c\n\t$(CC) -c $<\n\ntest: CFLAGS += -g\ntest: app.o\n\t./run-tests\n",
+        );
+        assert_eq!(names(&makefile), ["test"]);
+        assert_eq!(makefile.targets[0].prerequisites, ["app.o"]);
+        assert_eq!(makefile.targets[0].recipe, ["./run-tests"]);
+    }
+
+    #[test]
+    fn test_help_comments_prerequisites_and_phony() {
+        let makefile = Makefile::parse(
+            ".PHONY: docs/serve ci\n\nci: lint test | out ## Run everything CI runs\n\ndocs/serve: ## Serve the docs\n\tmkdocs serve\n\nbuild/app.bin: main.c\n\tcc -o $@ $<\n\nlint: ; ruff check .\n",
+        );
+        assert_eq!(names(&makefile), ["ci", "docs/serve", "lint"]);
+
+        let ci = &makefile.targets[0];
+        assert!(ci.phony);
+        assert_eq!(ci.prerequisites, ["lint", "test"]);
+        assert_eq!(ci.description.as_deref(), Some("Run everything CI runs"));
+
+        let lint = makefile.targets.iter().find(|t| t.name == "lint").unwrap();
+        assert!(!lint.phony);
+        assert_eq!(lint.recipe, ["ruff check ."]);
+    }
+
+    #[test]
+    fn test_continuations_and_define_blocks() {
+        let makefile = Makefile::parse(
+            "define HELP\nusage: make\nfake: target\nendef\n\nall: one \\\n\ttwo\n\techo done\n",
+        );
+        assert_eq!(names(&makefile), ["all"]);
+        assert_eq!(makefile.targets[0].prerequisites, ["one", "two"]);
+    }
+
+    #[test]
+    fn test_includes_relative_to_file() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("mk")).unwrap();
+        fs::write(
+            dir.path().join("Makefile"),
+            "include mk/test.mk\n-include missing.mk\n\nbuild:\n\tcargo build\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("mk/test.mk"),
+            "include mk/lint.mk\n\ntest: build ## Run tests\n\tcargo test\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("mk/lint.mk"), "lint:\n\tcargo clippy\n").unwrap();
+
+        let makefile = Makefile::from_path(&LocalFileSystem, &dir.path().join("Makefile")).unwrap();
+        assert_eq!(names(&makefile), ["lint", "test", "build"]);
+        assert_eq!(
+            makefile.includes,
+            [dir.path().join("mk/test.mk"), dir.path().join("mk/lint.mk")]
+        );
+    }
+}
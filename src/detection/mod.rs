@@ -5,14 +5,44 @@ use std::{
     path::PathBuf,
 };
 
+pub mod cache;
+pub mod cargo_manifest;
+pub mod ci_versions;
+pub mod conflict;
+pub mod filesystem;
+pub mod justfile;
 pub mod language;
+pub mod makefile;
+pub mod nx;
 pub mod package_manager;
+pub mod range;
+pub mod registry;
+pub mod rust_toolchain;
+pub mod task_graph;
 pub mod task_runner;
+pub mod taskfile;
+pub mod tool_versions;
+pub mod turbo;
 pub mod version;
 
+pub use cache::*;
+pub use cargo_manifest::*;
+pub use ci_versions::*;
+pub use conflict::*;
+pub use filesystem::*;
+pub use justfile::*;
 pub use language::*;
+pub use makefile::*;
+pub use nx::*;
 pub use package_manager::*;
+pub use range::*;
+pub use registry::*;
+pub use rust_toolchain::*;
+pub use task_graph::*;
 pub use task_runner::*;
+pub use taskfile::*;
+pub use tool_versions::*;
+pub use turbo::*;
 pub use version::*;
 
 #[derive(Debug, Serialize)]
@@ -21,6 +51,8 @@ pub struct ProjectMetadata {
     pub versions: Vec<VersionDetection>,
     pub package_managers: Vec<PackageManagerDetection>,
     pub task_runners: Vec<TaskRunnerDetection>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub version_conflicts: Vec<VersionConflict>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -31,19 +63,62 @@ pub enum DetectionScope {
 }
 
 #[derive(Default)]
-pub struct DetectionEngine;
+pub struct DetectionEngine {
+    registry: DetectorRegistry,
+}
 
 impl DetectionEngine {
+    pub fn with_registry(registry: DetectorRegistry) -> Self {
+        Self { registry }
+    }
+
+    pub fn registry_mut(&mut self) -> &mut DetectorRegistry {
+        &mut self.registry
+    }
+
     pub fn detect(&self, path: &Path) -> ProjectMetadata {
         self.detect_with_scope(path, DetectionScope::All)
     }
 
     pub fn detect_with_scope(&self, path: &Path, scope: DetectionScope) -> ProjectMetadata {
-        let paths: Vec<PathBuf> = DirectoryIterator::new(path.to_path_buf(), scope).collect();
+        self.detect_with_fs(&LocalFileSystem, path, scope)
+    }
+
+    pub fn detect_with_fs(
+        &self,
+        fs: &dyn FileSystem,
+        path: &Path,
+        scope: DetectionScope,
+    ) -> ProjectMetadata {
+        self.detect_inner(fs, path, scope, None)
+    }
+
+    /// Like [`detect_with_fs`](Self::detect_with_fs), reusing per-file results
+    /// from `cache` for files that have not changed. The cache is bypassed
+    /// when custom detectors are registered.
+    pub fn detect_with_cache(
+        &self,
+        fs: &dyn FileSystem,
+        path: &Path,
+        scope: DetectionScope,
+        cache: &mut DetectionCache,
+    ) -> ProjectMetadata {
+        let cache = self.registry.is_builtin_only().then_some(cache);
+        self.detect_inner(fs, path, scope, cache)
+    }
+
+    fn detect_inner(
+        &self,
+        fs: &dyn FileSystem,
+        path: &Path,
+        scope: DetectionScope,
+        mut cache: Option<&mut DetectionCache>,
+    ) -> ProjectMetadata {
+        let paths: Vec<PathBuf> = DirectoryIterator::new(fs, path.to_path_buf(), scope).collect();
 
         let languages: Vec<LanguageDetection> = paths
             .iter()
-            .filter_map(|path| LanguageDetectionSignal::try_from(path.clone()).ok())
+            .flat_map(|path| self.registry.detect_languages(path, fs))
             .fold(
                 HashMap::<Language, Vec<LanguageDetectionSignal>>::new(),
                 |mut acc, signal| {
@@ -58,29 +133,119 @@ impl DetectionEngine {
 
         let task_runners: Vec<TaskRunnerDetection> = paths
             .iter()
-            .filter_map(|path| TaskRunnerFile::try_from(path.clone()).ok())
-            .map(TaskRunnerDetection::from)
+            .flat_map(|path| {
+                cached(&mut cache, "task_runners", Some(path), fs, |fs| {
+                    self.registry.detect_task_runners(path, fs)
+                })
+            })
+            .collect();
+
+        // Re-read every run rather than through the per-file cache: a
+        // workflow's `*-version-file` input points at another file, which a
+        // cache entry keyed on the workflow would not notice changing. The
+        // pins still feed into `versions` below like any cached result.
+        let ci_pins: Vec<VersionInfo> = paths
+            .iter()
+            .flat_map(|path| ci_versions(path, fs))
             .collect();
 
-        let versions = languages
+        let versions: Vec<VersionDetection> = languages
             .iter()
-            .filter_map(|lang| VersionDetection::try_from(lang).ok())
+            .filter_map(|lang| {
+                let versions: Vec<VersionInfo> = lang
+                    .sources
+                    .iter()
+                    .flat_map(|signal| {
+                        // Version manager files yield one signal per tool, so the
+                        // source is part of the key.
+                        let kind = match signal {
+                            LanguageDetectionSignal::Strong { source, .. } => {
+                                format!("versions:{source:?}")
+                            }
+                            LanguageDetectionSignal::Weak(_) => "versions".to_string(),
+                        };
+                        cached(&mut cache, &kind, signal_path(signal), fs, |fs| {
+                            self.registry.detect_versions(signal, fs)
+                        })
+                    })
+                    .chain(
+                        ci_pins
+                            .iter()
+                            .filter(|v| match v.source {
+                                VersionSource::GithubActions(tool)
+                                | VersionSource::Dockerfile(tool) => {
+                                    tool.language() == lang.language
+                                }
+                                _ => false,
+                            })
+                            .cloned(),
+                    )
+                    .collect();
+
+                (!versions.is_empty())
+                    .then(|| VersionDetection::new(lang.language.clone(), versions, fs))
+            })
             .collect();
 
-        let package_managers = languages
+        let package_managers: Vec<PackageManagerDetection> = languages
             .iter()
-            .filter_map(|lang| PackageManagerDetection::try_from(lang).ok())
+            .filter_map(|lang| {
+                let package_managers: Vec<PackageManagerInfo> = lang
+                    .sources
+                    .iter()
+                    .flat_map(|signal| {
+                        cached(
+                            &mut cache,
+                            "package_managers",
+                            signal_path(signal),
+                            fs,
+                            |fs| package_managers_from_signal(signal, fs).unwrap_or_default(),
+                        )
+                    })
+                    .collect();
+
+                (!package_managers.is_empty()).then(|| PackageManagerDetection {
+                    language: lang.language.clone(),
+                    package_managers,
+                })
+            })
             .collect();
 
+        let version_conflicts = version_conflicts(&versions, &package_managers);
+
         ProjectMetadata {
             languages,
             versions,
             package_managers,
             task_runners,
+            version_conflicts,
         }
     }
 }
 
+fn signal_path(signal: &LanguageDetectionSignal) -> Option<&Path> {
+    match signal {
+        LanguageDetectionSignal::Strong { path, .. } => Some(path),
+        LanguageDetectionSignal::Weak(_) => None,
+    }
+}
+
+fn cached<T>(
+    cache: &mut Option<&mut DetectionCache>,
+    kind: &str,
+    path: Option<&Path>,
+    fs: &dyn FileSystem,
+    compute: impl FnOnce(&dyn FileSystem) -> T,
+) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    match (cache, path) {
+        (Some(cache), Some(path)) => cache.get_or_compute(kind, path, fs, compute),
+        _ => compute(fs),
+    }
+}
+
 const IGNORED_DIR_BASENAMES: &[&str] = &[
     // VCS
     ".git",
@@ -121,15 +286,17 @@ const IGNORED_DIR_BASENAMES: &[&str] = &[
     ".terraform",
 ];
 
-struct DirectoryIterator {
+pub(crate) struct DirectoryIterator<'a> {
+    fs: &'a dyn FileSystem,
     queue: VecDeque<PathBuf>,
     root: PathBuf,
     scope: DetectionScope,
 }
 
-impl DirectoryIterator {
-    fn new(root: PathBuf, scope: DetectionScope) -> Self {
+impl<'a> DirectoryIterator<'a> {
+    pub(crate) fn new(fs: &'a dyn FileSystem, root: PathBuf, scope: DetectionScope) -> Self {
         Self {
+            fs,
             queue: VecDeque::from([root.clone()]),
             root,
             scope,
@@ -144,30 +311,21 @@ impl DirectoryIterator {
     }
 }
 
-impl Iterator for DirectoryIterator {
+impl Iterator for DirectoryIterator<'_> {
     type Item = PathBuf;
     fn next(&mut self) -> Option<Self::Item> {
-        self.queue.pop_front().inspect(|p| match self.scope {
-            DetectionScope::All => {
-                if p.is_dir()
-                    && !Self::should_ignore_dir(p)
-                    && let Ok(entries) = p.read_dir()
-                {
-                    entries
-                        .filter_map(|entry| entry.ok())
-                        .for_each(|entry| self.queue.push_back(entry.path()));
-                }
-            }
-            DetectionScope::Root => {
-                if p == &self.root
-                    && p.is_dir()
-                    && !Self::should_ignore_dir(p)
-                    && let Ok(entries) = p.read_dir()
-                {
-                    entries
-                        .filter_map(|entry| entry.ok())
-                        .for_each(|entry| self.queue.push_back(entry.path()));
-                }
+        self.queue.pop_front().inspect(|p| {
+            let descend = match self.scope {
+                DetectionScope::All => true,
+                DetectionScope::Root => p == &self.root,
+            };
+
+            if descend
+                && self.fs.is_dir(p)
+                && !Self::should_ignore_dir(p)
+                && let Ok(entries) = self.fs.read_dir(p)
+            {
+                self.queue.extend(entries);
             }
         })
     }
@@ -193,7 +351,11 @@ mod tests {
     #[test]
     fn test_directory_iterator_empty() {
         let dir = TempDir::new().unwrap();
-        let iterator = DirectoryIterator::new(dir.path().to_path_buf(), DetectionScope::All);
+        let iterator = DirectoryIterator::new(
+            &LocalFileSystem,
+            dir.path().to_path_buf(),
+            DetectionScope::All,
+        );
         let paths: Vec<PathBuf> = iterator.collect();
         assert_eq!(paths.len(), 1);
         assert_eq!(paths[0], dir.path());
@@ -205,7 +367,11 @@ mod tests {
         create_temp_file(&dir, "file1.txt", "content");
         create_temp_file(&dir, "file2.rs", "fn main() {}");
 
-        let iterator = DirectoryIterator::new(dir.path().to_path_buf(), DetectionScope::All);
+        let iterator = DirectoryIterator::new(
+            &LocalFileSystem,
+            dir.path().to_path_buf(),
+            DetectionScope::All,
+        );
         let paths: Vec<PathBuf> = iterator.collect();
 
         assert!(paths.len() >= 3);
@@ -219,7 +385,11 @@ mod tests {
         create_temp_file(&dir, "file1.txt", "content");
         create_temp_file(&dir, "subdir/file2.txt", "content");
 
-        let iterator = DirectoryIterator::new(dir.path().to_path_buf(), DetectionScope::All);
+        let iterator = DirectoryIterator::new(
+            &LocalFileSystem,
+            dir.path().to_path_buf(),
+            DetectionScope::All,
+        );
         let paths: Vec<PathBuf> = iterator.collect();
 
         assert!(paths.iter().any(|p| p.ends_with("subdir")));
@@ -230,7 +400,8 @@ mod tests {
     #[test]
     fn test_directory_iterator_nonexistent_path() {
         let nonexistent = PathBuf::from("/nonexistent/path/that/does/not/exist");
-        let iterator = DirectoryIterator::new(nonexistent.clone(), DetectionScope::All);
+        let iterator =
+            DirectoryIterator::new(&LocalFileSystem, nonexistent.clone(), DetectionScope::All);
         let paths: Vec<PathBuf> = iterator.collect();
 
         assert_eq!(paths.len(), 1);
@@ -239,7 +410,7 @@ mod tests {
 
     #[test]
     fn test_detection_engine_default() {
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let dir = TempDir::new().unwrap();
         let metadata = engine.detect(dir.path());
 
@@ -264,7 +435,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "Cargo.lock", "");
         create_temp_file(&dir, "src/main.rs", "fn main() {}");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 1);
@@ -284,7 +455,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "go.sum", "");
         create_temp_file(&dir, "main.go", "package main\n\nfunc main() {}");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 1);
@@ -300,7 +471,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, ".python-version", "3.11.0");
         create_temp_file(&dir, "main.py", "print('hello')");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 1);
@@ -326,7 +497,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "pnpm-lock.yaml", "");
         create_temp_file(&dir, "index.js", "console.log('hello')");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 1);
@@ -346,7 +517,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "src/main.rs", "fn main() {}");
         create_temp_file(&dir, "index.js", "console.log('test')");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 2);
@@ -366,7 +537,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "frontend/package.json", "{}");
         create_temp_file(&dir, "frontend/index.ts", "console.log('test')");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 2);
@@ -380,7 +551,7 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "backend/go.mod", "module example.com\n\ngo 1.21\n");
         create_temp_file(&dir, "backend/main.go", "package main");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect_with_scope(dir.path(), DetectionScope::Root);
 
         assert!(metadata.languages.is_empty());
@@ -397,13 +568,347 @@ rust-version = "1.70.0"
         create_temp_file(&dir, "Cargo.toml", "[package]\nname = \"test\"\n");
         create_temp_file(&dir, "frontend/package.json", "{}");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect_with_scope(dir.path(), DetectionScope::Root);
 
         assert_eq!(metadata.languages.len(), 1);
         assert!(matches!(metadata.languages[0].language, Language::Rust));
     }
 
+    #[test]
+    fn test_detection_engine_detect_from_tarball() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in [
+            ("repo/go.mod", "module example.com\n\ngo 1.22\n"),
+            ("repo/Makefile", "test:\n\tgo test ./...\n"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        let tar_fs = TarFileSystem::from_bytes(&builder.into_inner().unwrap()).unwrap();
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect_with_fs(&tar_fs, Path::new("."), DetectionScope::All);
+
+        assert_eq!(metadata.languages.len(), 1);
+        assert!(matches!(metadata.languages[0].language, Language::Go));
+        assert_eq!(metadata.versions[0].versions[0].raw, "1.22");
+        assert_eq!(
+            metadata.versions[0].versions[0].path,
+            PathBuf::from("./go.mod")
+        );
+        assert!(
+            metadata
+                .task_runners
+                .iter()
+                .any(|tr| matches!(tr.task_runner, TaskRunner::Make))
+        );
+    }
+
+    #[test]
+    fn test_detection_engine_with_custom_detectors() {
+        struct ZigDetector;
+
+        impl LanguageDetector for ZigDetector {
+            fn detect(&self, path: &Path) -> Option<LanguageDetectionSignal> {
+                (path.file_name()? == "build.zig").then(|| LanguageDetectionSignal::Strong {
+                    path: path.to_path_buf(),
+                    source: LanguageDetectionSource::Custom {
+                        language: "zig",
+                        name: "BuildZig",
+                    },
+                })
+            }
+        }
+
+        impl VersionDetector for ZigDetector {
+            fn detect(
+                &self,
+                signal: &LanguageDetectionSignal,
+                fs: &dyn FileSystem,
+            ) -> Vec<VersionInfo> {
+                let LanguageDetectionSignal::Strong {
+                    path,
+                    source:
+                        LanguageDetectionSource::Custom {
+                            language: "zig", ..
+                        },
+                } = signal
+                else {
+                    return vec![];
+                };
+                let version_path = path.with_file_name(".zig-version");
+                fs.read_to_string(&version_path)
+                    .map(|raw| VersionInfo {
+                        raw: raw.trim().to_string(),
+                        parsed: None,
+                        source: VersionSource::Custom("ZigVersionFile"),
+                        path: version_path,
+                    })
+                    .into_iter()
+                    .collect()
+            }
+        }
+
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "build.zig", "");
+        create_temp_file(&dir, ".zig-version", "0.13.0\n");
+        create_temp_file(&dir, "go.mod", "module test\n\ngo 1.21\n");
+
+        let mut engine = DetectionEngine::default();
+        engine
+            .registry_mut()
+            .register_language_detector(ZigDetector)
+            .register_version_detector(ZigDetector);
+        let metadata = engine.detect(dir.path());
+
+        assert_eq!(metadata.languages.len(), 2);
+        let zig = metadata
+            .versions
+            .iter()
+            .find(|v| v.language == Language::Custom("zig"))
+            .unwrap();
+        assert_eq!(zig.versions[0].raw, "0.13.0");
+        assert!(
+            metadata
+                .versions
+                .iter()
+                .any(|v| v.language == Language::Go && v.versions[0].raw == "1.21")
+        );
+
+        let engine = DetectionEngine::with_registry(DetectorRegistry::empty());
+        assert!(engine.detect(dir.path()).languages.is_empty());
+    }
+
+    #[test]
+    fn test_detection_engine_cached_results_match_uncached() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"packageManager": "pnpm@9.0.0", "engines": {"node": ">=20"}, "scripts": {"test": "vitest"}}"#,
+        );
+        create_temp_file(&dir, ".nvmrc", "20.11.0\n");
+        let cache_path = dir.path().join("cache.json");
+
+        let engine = DetectionEngine::default();
+        let uncached = serde_json::to_string(&engine.detect(dir.path())).unwrap();
+
+        for _ in 0..2 {
+            let mut cache = DetectionCache::load(&cache_path);
+            let metadata = engine.detect_with_cache(
+                &LocalFileSystem,
+                dir.path(),
+                DetectionScope::All,
+                &mut cache,
+            );
+            assert_eq!(serde_json::to_string(&metadata).unwrap(), uncached);
+            cache.save(&cache_path).unwrap();
+        }
+        assert!(!DetectionCache::load(&cache_path).is_empty());
+
+        create_temp_file(&dir, ".nvmrc", "22\n");
+        let mut cache = DetectionCache::load(&cache_path);
+        let metadata = engine.detect_with_cache(
+            &LocalFileSystem,
+            dir.path(),
+            DetectionScope::All,
+            &mut cache,
+        );
+        assert!(metadata.versions[0].versions.iter().any(|v| v.raw == "22"));
+    }
+
+    #[test]
+    fn test_detection_reports_version_conflicts() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "go.mod", "module test\n\ngo 1.23\n");
+        create_temp_file(&dir, ".go-version", "1.22.3\n");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        assert_eq!(metadata.version_conflicts.len(), 1);
+        assert_eq!(metadata.version_conflicts[0].language, Language::Go);
+        assert_eq!(metadata.version_conflicts[0].chosen.raw, "1.23");
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(json.contains("version_conflicts"));
+    }
+
+    #[test]
+    fn test_detection_reports_package_manager_pin_conflicts() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"packageManager": "yarn@4.1.0+sha512.abc", "volta": {"yarn": "1.22.19"}}"#,
+        );
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        assert_eq!(metadata.version_conflicts.len(), 1);
+        let conflict = &metadata.version_conflicts[0];
+        assert_eq!(conflict.tool, "yarn");
+        assert_eq!(conflict.chosen.raw, "4.1.0");
+        assert_eq!(
+            conflict.summary(),
+            "yarn: 4.1.0 (PackageJsonPackageManager(Yarn)) vs 1.22.19 (VoltaPackageManager(Yarn)) -> using 4.1.0 (from PackageJsonPackageManager(Yarn))"
+        );
+    }
+
+    #[test]
+    fn test_tool_versions_file_signals_each_pinned_language() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            ".tool-versions",
+            "golang 1.23.4
+nodejs 22.1.0
+ruby 3.3.0
+",
+        );
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let languages: Vec<&Language> = metadata.languages.iter().map(|l| &l.language).collect();
+        assert!(languages.contains(&&Language::Go));
+        assert!(languages.contains(&&Language::JavaScript));
+        assert!(languages.contains(&&Language::Custom("ruby")));
+
+        let go = metadata
+            .versions
+            .iter()
+            .find(|v| v.language == Language::Go)
+            .unwrap();
+        assert_eq!(go.versions.len(), 1);
+        assert_eq!(go.versions[0].raw, "1.23.4");
+        assert_eq!(
+            go.versions[0].source,
+            VersionSource::ToolVersionsFile(ManagedTool::Go)
+        );
+    }
+
+    #[test]
+    fn test_tool_versions_cached_per_tool() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            ".tool-versions",
+            "golang 1.23.4
+nodejs 22.1.0
+",
+        );
+
+        let engine = DetectionEngine::default();
+        let mut cache = DetectionCache::default();
+        for _ in 0..2 {
+            let metadata = engine.detect_with_cache(
+                &LocalFileSystem,
+                dir.path(),
+                DetectionScope::All,
+                &mut cache,
+            );
+            let node = metadata
+                .versions
+                .iter()
+                .find(|v| v.language == Language::JavaScript)
+                .unwrap();
+            assert_eq!(node.versions[0].raw, "22.1.0");
+        }
+    }
+
+    #[test]
+    fn test_ci_pins_attach_to_detected_languages_as_fallbacks() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "go.mod", "module example.com\n\ngo 1.22\n");
+        create_temp_file(
+            &dir,
+            "Dockerfile",
+            "FROM golang:1.23-alpine\nFROM node:20\n",
+        );
+        create_temp_file(
+            &dir,
+            ".github/workflows/ci.yml",
+            "jobs:\n  test:\n    strategy:\n      matrix:\n        go: ['1.21', '1.23']\n    steps:\n      - uses: actions/setup-go@v5\n        with:\n          go-version: ${{ matrix.go }}\n",
+        );
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        assert!(metadata.versions.iter().all(|v| v.language == Language::Go));
+        assert!(metadata.version_conflicts.is_empty());
+
+        let go = &metadata.versions[0];
+        assert_eq!(go.versions.len(), 4);
+        let effective: Vec<VersionSource> = go.effective_versions().map(|v| v.source).collect();
+        assert_eq!(effective, [VersionSource::GoModDirective]);
+    }
+
+    #[test]
+    fn test_toolchain_of_one_go_module_leaves_sibling_modules_alone() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "a/go.mod",
+            "module a\n\ngo 1.24\n\ntoolchain go1.24.3\n",
+        );
+        create_temp_file(&dir, "b/go.mod", "module b\n\ngo 1.25\n");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let go = &metadata.versions[0];
+        let mut effective: Vec<&str> = go.effective_versions().map(|v| v.raw.as_str()).collect();
+        effective.sort_unstable();
+        assert_eq!(effective, ["1.24.3", "1.25"]);
+        // Each module builds with its own toolchain.
+        assert!(metadata.version_conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_compatible_minimum_versions_do_not_conflict() {
+        let conflicts = |files: &[(&str, &str)]| {
+            let dir = TempDir::new().unwrap();
+            for (name, content) in files {
+                create_temp_file(&dir, name, content);
+            }
+            DetectionEngine::default()
+                .detect(dir.path())
+                .version_conflicts
+                .len()
+        };
+
+        assert_eq!(
+            conflicts(&[
+                ("go.mod", "module a\n\ngo 1.22\n"),
+                (".go-version", "1.23.4\n"),
+            ]),
+            0
+        );
+        assert_eq!(
+            conflicts(&[
+                ("a/go.mod", "module a\n\ngo 1.22\n"),
+                ("b/go.mod", "module b\n\ngo 1.23.0\n"),
+            ]),
+            0
+        );
+        assert_eq!(
+            conflicts(&[
+                (
+                    "Cargo.toml",
+                    "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nrust-version = \"1.70\"\n",
+                ),
+                ("rust-toolchain.toml", "[toolchain]\nchannel = \"1.78.0\"\n",),
+            ]),
+            0
+        );
+        // A version file below the module's minimum still conflicts.
+        assert_eq!(
+            conflicts(&[
+                ("go.mod", "module a\n\ngo 1.22\n"),
+                (".go-version", "1.21.5\n"),
+            ]),
+            1
+        );
+    }
+
     #[test]
     fn test_project_metadata_serialization() {
         let metadata = ProjectMetadata {
@@ -411,12 +916,14 @@ rust-version = "1.70.0"
             versions: vec![],
             package_managers: vec![],
             task_runners: vec![],
+            version_conflicts: vec![],
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
         assert!(json.contains("languages"));
         assert!(json.contains("versions"));
         assert!(json.contains("package_managers"));
+        assert!(!json.contains("version_conflicts"));
     }
 
     #[test]
@@ -424,7 +931,7 @@ rust-version = "1.70.0"
         let dir = TempDir::new().unwrap();
         create_temp_file(&dir, "go.mod", "module test\n\ngo 1.21\n");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let json = serde_json::to_string_pretty(&metadata).unwrap();
 
@@ -446,7 +953,7 @@ rust-version = "1.70.0"
             r#"{"scripts": {"test": "jest", "build": "vite build"}}"#,
         );
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.task_runners.len(), 2);
@@ -479,7 +986,7 @@ rust-version = "1.70.0"
         fs::create_dir(dir.path().join("node_modules/foo")).unwrap();
         create_temp_file(&dir, "node_modules/foo/package.json", r#"{"name": "foo"}"#);
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 0);
@@ -502,7 +1009,7 @@ rust-version = "1.70.0"
         fs::create_dir(dir.path().join("dist")).unwrap();
         create_temp_file(&dir, "dist/package.json", "{}");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
 
         assert_eq!(metadata.languages.len(), 0);
@@ -0,0 +1,745 @@
+use super::{
+    DetectionScope, DirectoryIterator, FileSystem, GraphPackage, GraphTask, TaskGraph,
+    js_workspace_packages,
+};
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A target of an Nx project, as `project.json`, a `package.json` or a
+/// plugin defines it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NxTarget {
+    pub name: String,
+    /// Such as `@nx/jest:jest`; `nx:run-commands` for a `command`.
+    pub executor: Option<String>,
+    /// The shell the target runs, for `nx:run-commands` and scripts.
+    pub command: Option<String>,
+    pub depends_on: Vec<NxDependency>,
+    pub outputs: Vec<String>,
+    /// Marked `continuous`, like a dev server.
+    pub continuous: bool,
+}
+
+/// An entry of a target's `dependsOn`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NxDependency {
+    /// The target in the same project.
+    SameProject(String),
+    /// `^target`: the target in each project this one depends on.
+    Dependencies(String),
+    /// The target in the listed projects.
+    Projects(Vec<String>, String),
+}
+
+/// A project of an Nx workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NxProject {
+    pub name: String,
+    pub root: PathBuf,
+    pub targets: Vec<NxTarget>,
+    /// `implicitDependencies` and workspace packages the project depends on.
+    pub dependencies: Vec<String>,
+}
+
+/// A plugin of `nx.json` that infers targets from the config files it
+/// finds in a project, each target named by an option of the plugin.
+struct NxPlugin {
+    name: &'static str,
+    files: &'static [&'static str],
+    /// Option path, default target name and the executor it stands for.
+    targets: &'static [(&'static str, &'static str, &'static str)],
+}
+
+const NX_PLUGINS: &[NxPlugin] = &[
+    NxPlugin {
+        name: "@nx/vite/plugin",
+        files: &[
+            "vite.config.ts",
+            "vite.config.js",
+            "vite.config.mts",
+            "vite.config.mjs",
+            "vitest.config.ts",
+            "vitest.config.js",
+            "vitest.config.mts",
+        ],
+        targets: &[
+            ("buildTargetName", "build", "@nx/vite:build"),
+            ("testTargetName", "test", "@nx/vite:test"),
+            ("serveTargetName", "serve", "@nx/vite:dev-server"),
+            ("previewTargetName", "preview", "@nx/vite:preview-server"),
+        ],
+    },
+    NxPlugin {
+        name: "@nx/jest/plugin",
+        files: &[
+            "jest.config.ts",
+            "jest.config.js",
+            "jest.config.mjs",
+            "jest.config.cjs",
+            "jest.config.cts",
+            "jest.config.json",
+        ],
+        targets: &[("targetName", "test", "@nx/jest:jest")],
+    },
+    NxPlugin {
+        name: "@nx/eslint/plugin",
+        files: &[
+            "eslint.config.js",
+            "eslint.config.mjs",
+            "eslint.config.cjs",
+            "eslint.config.ts",
+            ".eslintrc.json",
+            ".eslintrc.js",
+            ".eslintrc.cjs",
+            ".eslintrc.yml",
+            ".eslintrc.yaml",
+        ],
+        targets: &[("targetName", "lint", "@nx/eslint:lint")],
+    },
+    NxPlugin {
+        name: "@nx/webpack/plugin",
+        files: &[
+            "webpack.config.js",
+            "webpack.config.ts",
+            "webpack.config.mjs",
+            "webpack.config.cjs",
+        ],
+        targets: &[
+            ("buildTargetName", "build", "@nx/webpack:webpack"),
+            ("serveTargetName", "serve", "@nx/webpack:dev-server"),
+        ],
+    },
+    NxPlugin {
+        name: "@nx/next/plugin",
+        files: &["next.config.js", "next.config.mjs", "next.config.ts"],
+        targets: &[
+            ("buildTargetName", "build", "@nx/next:build"),
+            ("devTargetName", "dev", "@nx/next:server"),
+            ("startTargetName", "start", "@nx/next:server"),
+        ],
+    },
+    NxPlugin {
+        name: "@nx/playwright/plugin",
+        files: &["playwright.config.ts", "playwright.config.js"],
+        targets: &[("targetName", "e2e", "@nx/playwright:playwright")],
+    },
+    NxPlugin {
+        name: "@nx/cypress/plugin",
+        files: &[
+            "cypress.config.ts",
+            "cypress.config.js",
+            "cypress.config.mjs",
+            "cypress.config.cjs",
+        ],
+        targets: &[("targetName", "e2e", "@nx/cypress:cypress")],
+    },
+    NxPlugin {
+        name: "@nx/js/typescript",
+        files: &["tsconfig.json"],
+        targets: &[("typecheck.targetName", "typecheck", "@nx/js:typecheck")],
+    },
+];
+
+/// The parts of `nx.json` that shape projects' targets.
+#[derive(Debug, Clone, Default)]
+struct NxJson {
+    /// Keyed by target name or executor.
+    target_defaults: Vec<(String, JsonValue)>,
+    /// Registered plugins with their options.
+    plugins: Vec<(String, JsonValue)>,
+}
+
+impl NxJson {
+    fn parse(content: &str) -> Option<Self> {
+        let json: JsonValue = serde_json::from_str(content).ok()?;
+        let target_defaults = json
+            .get("targetDefaults")
+            .and_then(|t| t.as_object())
+            .into_iter()
+            .flatten()
+            .map(|(key, config)| (key.clone(), config.clone()))
+            .collect();
+        let plugins = json
+            .get("plugins")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|plugin| match plugin {
+                JsonValue::String(name) => Some((name.clone(), JsonValue::Null)),
+                JsonValue::Object(config) => Some((
+                    config.get("plugin")?.as_str()?.to_string(),
+                    config.get("options").cloned().unwrap_or(JsonValue::Null),
+                )),
+                _ => None,
+            })
+            .collect();
+        Some(Self {
+            target_defaults,
+            plugins,
+        })
+    }
+
+    /// The default for a target, matched by executor before name.
+    fn target_default(&self, target: &NxTarget) -> Option<&JsonValue> {
+        let find = |key: &str| {
+            self.target_defaults
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v)
+        };
+        target
+            .executor
+            .as_deref()
+            .and_then(find)
+            .or_else(|| find(&target.name))
+    }
+}
+
+/// Reads a target's configuration; fields it leaves out come from `base`.
+fn parse_target(name: &str, config: &JsonValue, base: Option<NxTarget>) -> NxTarget {
+    let mut target = base.unwrap_or_else(|| NxTarget {
+        name: name.to_string(),
+        ..NxTarget::default()
+    });
+    let options = config.get("options");
+
+    if let Some(executor) = config.get("executor").and_then(|e| e.as_str()) {
+        target.executor = Some(executor.to_string());
+    }
+    let command = config
+        .get("command")
+        .or_else(|| options?.get("command"))
+        .and_then(|c| c.as_str())
+        .map(str::to_string)
+        .or_else(|| {
+            let commands: Vec<&str> = options?
+                .get("commands")?
+                .as_array()?
+                .iter()
+                .filter_map(|c| c.as_str().or_else(|| c.get("command")?.as_str()))
+                .collect();
+            Some(commands.join("\n")).filter(|c| !c.is_empty())
+        });
+    if let Some(command) = command {
+        target
+            .executor
+            .get_or_insert_with(|| "nx:run-commands".to_string());
+        target.command = Some(command);
+    }
+    if let Some(depends_on) = config.get("dependsOn").and_then(|d| d.as_array()) {
+        target.depends_on = depends_on.iter().filter_map(parse_dependency).collect();
+    }
+    if let Some(outputs) = config.get("outputs").and_then(|o| o.as_array()) {
+        target.outputs = outputs
+            .iter()
+            .filter_map(|o| o.as_str().map(str::to_string))
+            .collect();
+    }
+    if let Some(continuous) = config.get("continuous").and_then(|c| c.as_bool()) {
+        target.continuous = continuous;
+    }
+    target
+}
+
+fn parse_dependency(value: &JsonValue) -> Option<NxDependency> {
+    if let Some(entry) = value.as_str() {
+        return Some(match entry.strip_prefix('^') {
+            Some(target) => NxDependency::Dependencies(target.to_string()),
+            None => match entry.split_once(':') {
+                Some((project, target)) => {
+                    NxDependency::Projects(vec![project.to_string()], target.to_string())
+                }
+                None => NxDependency::SameProject(entry.to_string()),
+            },
+        });
+    }
+
+    let target = value.get("target")?.as_str()?.to_string();
+    if value.get("dependencies").and_then(|d| d.as_bool()) == Some(true) {
+        return Some(NxDependency::Dependencies(target));
+    }
+    Some(match value.get("projects") {
+        Some(JsonValue::String(p)) if p == "dependencies" => NxDependency::Dependencies(target),
+        Some(JsonValue::String(p)) if p != "self" => {
+            NxDependency::Projects(vec![p.clone()], target)
+        }
+        Some(JsonValue::Array(projects)) => NxDependency::Projects(
+            projects
+                .iter()
+                .filter_map(|p| p.as_str().map(str::to_string))
+                .collect(),
+            target,
+        ),
+        _ => NxDependency::SameProject(target),
+    })
+}
+
+/// A project's directory as Nx sees it: `project.json`, the `package.json`
+/// of a workspace member, or both.
+struct ProjectSource {
+    root: PathBuf,
+    project_json: Option<JsonValue>,
+    package_json: Option<JsonValue>,
+}
+
+impl ProjectSource {
+    fn name(&self) -> Option<String> {
+        let package_nx = self.package_json.as_ref().and_then(|p| p.get("nx"));
+        [
+            self.project_json.as_ref().and_then(|p| p.get("name")),
+            package_nx.and_then(|nx| nx.get("name")),
+            self.package_json.as_ref().and_then(|p| p.get("name")),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|n| n.as_str().map(str::to_string))
+        .or_else(|| Some(self.root.file_name()?.to_str()?.to_string()))
+    }
+}
+
+/// Finds the projects of the Nx workspace configured by `nx_json`, with
+/// targets from `targetDefaults`, plugins, scripts and project files merged
+/// the way Nx merges them.
+pub fn nx_projects(nx_json: &Path, fs: &dyn FileSystem) -> Vec<NxProject> {
+    let Some(config) = fs
+        .read_to_string(nx_json)
+        .ok()
+        .and_then(|content| NxJson::parse(&content))
+    else {
+        return vec![];
+    };
+    let Some(root) = nx_json.parent() else {
+        return vec![];
+    };
+    let read_json = |path: &Path| -> Option<JsonValue> {
+        serde_json::from_str(&fs.read_to_string(path).ok()?).ok()
+    };
+
+    let mut sources: Vec<ProjectSource> = Vec::new();
+    for package_json in js_workspace_packages(root, fs) {
+        sources.push(ProjectSource {
+            root: package_json.parent().unwrap_or(root).to_path_buf(),
+            project_json: None,
+            package_json: read_json(&package_json),
+        });
+    }
+    let mut project_files: Vec<PathBuf> =
+        DirectoryIterator::new(fs, root.to_path_buf(), DetectionScope::All)
+            .filter(|p| p.file_name().is_some_and(|n| n == "project.json") && fs.is_file(p))
+            .collect();
+    project_files.sort();
+    for project_json in project_files {
+        let dir = project_json.parent().unwrap_or(root).to_path_buf();
+        let parsed = read_json(&project_json);
+        match sources.iter_mut().find(|s| s.root == dir) {
+            Some(source) => source.project_json = parsed,
+            None => sources.push(ProjectSource {
+                package_json: read_json(&dir.join("package.json")),
+                root: dir,
+                project_json: parsed,
+            }),
+        }
+    }
+
+    // Sources without targets give no project, so each project keeps its own.
+    let mut projects: Vec<(NxProject, &ProjectSource)> = sources
+        .iter()
+        .filter_map(|source| Some((project_from_source(source, &config, fs)?, source)))
+        .collect();
+
+    // Workspace packages depended on through package.json count as project
+    // dependencies, by package name.
+    let package_names: Vec<(String, String)> = sources
+        .iter()
+        .filter_map(|s| {
+            let package = s.package_json.as_ref()?.get("name")?.as_str()?.to_string();
+            Some((package, s.name()?))
+        })
+        .collect();
+    for (project, source) in &mut projects {
+        let declared = ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .filter_map(|key| source.package_json.as_ref()?.get(key)?.as_object())
+            .flat_map(|deps| deps.keys());
+        for dependency in declared {
+            if let Some((_, name)) = package_names.iter().find(|(p, _)| p == dependency)
+                && !project.dependencies.contains(name)
+                && *name != project.name
+            {
+                project.dependencies.push(name.clone());
+            }
+        }
+    }
+
+    projects.into_iter().map(|(project, _)| project).collect()
+}
+
+fn project_from_source(
+    source: &ProjectSource,
+    config: &NxJson,
+    fs: &dyn FileSystem,
+) -> Option<NxProject> {
+    let name = source.name()?;
+    let package_nx = source.package_json.as_ref().and_then(|p| p.get("nx"));
+    let mut targets: Vec<NxTarget> = Vec::new();
+
+    // Inferred targets first; explicit configuration refines them.
+    for (plugin_name, options) in &config.plugins {
+        let Some(plugin) = NX_PLUGINS.iter().find(|p| p.name == plugin_name) else {
+            continue;
+        };
+        if !plugin
+            .files
+            .iter()
+            .any(|f| fs.is_file(&source.root.join(f)))
+        {
+            continue;
+        }
+        for (option, default_name, executor) in plugin.targets {
+            let target_name = option
+                .split('.')
+                .try_fold(options, |value, key| value.get(key))
+                .and_then(|v| v.as_str())
+                .unwrap_or(default_name);
+            set_target(
+                &mut targets,
+                NxTarget {
+                    name: target_name.to_string(),
+                    executor: Some(executor.to_string()),
+                    ..NxTarget::default()
+                },
+            );
+        }
+    }
+
+    if let Some(scripts) = source
+        .package_json
+        .as_ref()
+        .and_then(|p| p.get("scripts"))
+        .and_then(|s| s.as_object())
+    {
+        let included: Option<Vec<&str>> = package_nx
+            .and_then(|nx| nx.get("includedScripts"))
+            .and_then(|i| i.as_array())
+            .map(|i| i.iter().filter_map(|s| s.as_str()).collect());
+        for (script, body) in scripts {
+            if included
+                .as_ref()
+                .is_some_and(|i| !i.contains(&script.as_str()))
+            {
+                continue;
+            }
+            set_target(
+                &mut targets,
+                NxTarget {
+                    name: script.clone(),
+                    executor: Some("nx:run-script".to_string()),
+                    command: body.as_str().map(str::to_string),
+                    ..NxTarget::default()
+                },
+            );
+        }
+    }
+
+    let explicit = [
+        package_nx.and_then(|nx| nx.get("targets")),
+        source.project_json.as_ref().and_then(|p| p.get("targets")),
+    ];
+    for targets_config in explicit.into_iter().flatten() {
+        for (target_name, target_config) in targets_config.as_object().into_iter().flatten() {
+            let base = targets.iter().find(|t| &t.name == target_name).cloned();
+            set_target(&mut targets, parse_target(target_name, target_config, base));
+        }
+    }
+
+    if source.project_json.is_none() && package_nx.is_none() && targets.is_empty() {
+        return None;
+    }
+
+    let targets = targets
+        .into_iter()
+        .map(|target| match config.target_default(&target) {
+            // Project configuration wins over the defaults.
+            Some(defaults) => {
+                let mut merged = parse_target(&target.name, defaults, None);
+                merged.executor = target.executor.or(merged.executor);
+                merged.command = target.command.or(merged.command);
+                if !target.depends_on.is_empty() {
+                    merged.depends_on = target.depends_on;
+                }
+                if !target.outputs.is_empty() {
+                    merged.outputs = target.outputs;
+                }
+                merged.continuous |= target.continuous;
+                merged
+            }
+            None => target,
+        })
+        .collect();
+
+    let implicit = [source.project_json.as_ref(), package_nx]
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.get("implicitDependencies")?.as_array())
+        .flatten()
+        .filter_map(|d| d.as_str())
+        .filter(|d| !d.starts_with('!'))
+        .map(str::to_string)
+        .collect();
+
+    Some(NxProject {
+        name,
+        root: source.root.clone(),
+        targets,
+        dependencies: implicit,
+    })
+}
+
+/// Adds `target`, replacing one of the same name.
+fn set_target(targets: &mut Vec<NxTarget>, target: NxTarget) {
+    match targets.iter_mut().find(|t| t.name == target.name) {
+        Some(existing) => *existing = target,
+        None => targets.push(target),
+    }
+}
+
+/// The task graph of the Nx workspace configured by `nx_json`, one task per
+/// project target. `None` when no projects are found.
+pub fn nx_task_graph(nx_json: &Path, fs: &dyn FileSystem) -> Option<TaskGraph> {
+    let projects = nx_projects(nx_json, fs);
+    if projects.is_empty() {
+        return None;
+    }
+    let root = nx_json.parent()?;
+
+    let mut tasks = vec![];
+    for project in &projects {
+        for target in &project.targets {
+            let mut depends_on = vec![];
+            for dependency in &target.depends_on {
+                match dependency {
+                    NxDependency::SameProject(name) => {
+                        depends_on.push(format!("{}:{name}", project.name));
+                    }
+                    NxDependency::Dependencies(name) => {
+                        let mut seen = HashSet::new();
+                        upstream_targets(project, name, &projects, &mut seen, &mut depends_on);
+                    }
+                    NxDependency::Projects(names, name) => {
+                        depends_on.extend(names.iter().map(|p| format!("{p}:{name}")));
+                    }
+                }
+            }
+            tasks.push(GraphTask {
+                id: format!("{}:{}", project.name, target.name),
+                package: project.name.clone(),
+                task: target.name.clone(),
+                depends_on,
+                outputs: target.outputs.clone(),
+                persistent: target.continuous,
+                script: target.command.clone(),
+                executor: target.executor.clone(),
+            });
+        }
+    }
+
+    let packages = projects
+        .iter()
+        .map(|project| GraphPackage {
+            name: project.name.clone(),
+            path: project
+                .root
+                .strip_prefix(root)
+                .unwrap_or(&project.root)
+                .to_path_buf(),
+            dependencies: project.dependencies.clone(),
+        })
+        .collect();
+
+    Some(TaskGraph::new(packages, tasks))
+}
+
+/// `^target`: the target in each project `project` depends on, looking
+/// through projects that don't define it.
+fn upstream_targets(
+    project: &NxProject,
+    target: &str,
+    projects: &[NxProject],
+    seen: &mut HashSet<String>,
+    out: &mut Vec<String>,
+) {
+    for dependency in &project.dependencies {
+        let Some(upstream) = projects.iter().find(|p| &p.name == dependency) else {
+            continue;
+        };
+        if !seen.insert(upstream.name.clone()) {
+            continue;
+        }
+        if upstream.targets.iter().any(|t| t.name == target) {
+            out.push(format!("{}:{target}", upstream.name));
+        } else {
+            upstream_targets(upstream, target, projects, seen, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn target<'a>(project: &'a NxProject, name: &str) -> &'a NxTarget {
+        project.targets.iter().find(|t| t.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_project_json_targets_and_defaults() {
+        let dir = TempDir::new().unwrap();
+        let nx_json = write(
+            &dir,
+            "nx.json",
+            r#"{"targetDefaults": {"build": {"dependsOn": ["^build"], "outputs": ["{projectRoot}/dist"]}, "@nx/jest:jest": {"dependsOn": ["build"]}}}"#,
+        );
+        write(
+            &dir,
+            "libs/ui/project.json",
+            r#"{"name": "ui", "targets": {"build": {"executor": "@nx/js:tsc"}, "test": {"executor": "@nx/jest:jest"}}}"#,
+        );
+        write(
+            &dir,
+            "apps/shop/project.json",
+            r#"{"implicitDependencies": ["ui", "!legacy"], "targets": {"build": {"command": "vite build"}}}"#,
+        );
+        write(
+            &dir,
+            "node_modules/pkg/project.json",
+            r#"{"name": "vendored"}"#,
+        );
+
+        let projects = nx_projects(&nx_json, &LocalFileSystem);
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["shop", "ui"]);
+
+        let shop = &projects[0];
+        assert_eq!(shop.dependencies, ["ui"]);
+        let build = target(shop, "build");
+        assert_eq!(build.executor.as_deref(), Some("nx:run-commands"));
+        assert_eq!(build.command.as_deref(), Some("vite build"));
+        assert_eq!(build.outputs, ["{projectRoot}/dist"]);
+
+        let test = target(&projects[1], "test");
+        assert_eq!(test.depends_on, [NxDependency::SameProject("build".into())]);
+    }
+
+    #[test]
+    fn test_package_json_projects_and_plugins() {
+        let dir = TempDir::new().unwrap();
+        let nx_json = write(
+            &dir,
+            "nx.json",
+            r#"{"plugins": [{"plugin": "@nx/vite/plugin", "options": {"testTargetName": "vitest"}}, "@nx/eslint/plugin", "@nx/js/typescript"]}"#,
+        );
+        write(
+            &dir,
+            "package.json",
+            r#"{"name": "repo", "workspaces": ["packages/*"]}"#,
+        );
+        write(
+            &dir,
+            "packages/web/package.json",
+            r#"{"name": "@acme/web", "dependencies": {"@acme/ui": "*"}, "scripts": {"start": "node server.js", "lint": "eslint ."}, "nx": {"name": "web", "targets": {"lint": {"dependsOn": [{"target": "build", "projects": "dependencies"}]}}}}"#,
+        );
+        write(&dir, "packages/web/vite.config.ts", "export default {}");
+        write(&dir, "packages/web/eslint.config.js", "export default []");
+        write(
+            &dir,
+            "packages/ui/package.json",
+            r#"{"name": "@acme/ui", "scripts": {"build": "tsup"}}"#,
+        );
+
+        let projects = nx_projects(&nx_json, &LocalFileSystem);
+        let web = projects.iter().find(|p| p.name == "web").unwrap();
+        let names: Vec<&str> = web.targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["build", "vitest", "serve", "preview", "lint", "start"]
+        );
+        assert_eq!(web.dependencies, ["@acme/ui"]);
+
+        // The script replaces the eslint plugin's target, and the `nx` block
+        // refines the script.
+        let lint = target(web, "lint");
+        assert_eq!(lint.executor.as_deref(), Some("nx:run-script"));
+        assert_eq!(lint.command.as_deref(), Some("eslint ."));
+        assert_eq!(
+            lint.depends_on,
+            [NxDependency::Dependencies("build".into())]
+        );
+
+        let graph = nx_task_graph(&nx_json, &LocalFileSystem).unwrap();
+        assert_eq!(
+            graph.task("web:lint").unwrap().depends_on,
+            ["@acme/ui:build"]
+        );
+        assert!(graph.task("web:vitest").is_some());
+    }
+
+    #[test]
+    fn test_package_dependencies_skip_members_without_targets() {
+        let dir = TempDir::new().unwrap();
+        let nx_json = write(
+            &dir,
+            "nx.json",
+            r#"{"targetDefaults": {"build": {"dependsOn": ["^build"]}}}"#,
+        );
+        write(
+            &dir,
+            "package.json",
+            r#"{"name": "repo", "workspaces": ["packages/*"]}"#,
+        );
+        write(&dir, "packages/a/package.json", r#"{"name": "a"}"#);
+        write(
+            &dir,
+            "packages/b/package.json",
+            r#"{"name": "b", "dependencies": {"d": "*"}, "scripts": {"build": "tsup"}}"#,
+        );
+        write(
+            &dir,
+            "packages/c/package.json",
+            r#"{"name": "c", "scripts": {"build": "tsup"}}"#,
+        );
+        write(
+            &dir,
+            "packages/d/package.json",
+            r#"{"name": "d", "scripts": {"build": "tsup"}}"#,
+        );
+
+        let projects = nx_projects(&nx_json, &LocalFileSystem);
+        let dependencies: Vec<(&str, &[String])> = projects
+            .iter()
+            .map(|p| (p.name.as_str(), p.dependencies.as_slice()))
+            .collect();
+        assert_eq!(
+            dependencies,
+            [
+                ("b", &["d".to_string()][..]),
+                ("c", &[][..]),
+                ("d", &[][..])
+            ]
+        );
+
+        let graph = nx_task_graph(&nx_json, &LocalFileSystem).unwrap();
+        assert_eq!(graph.task("b:build").unwrap().depends_on, ["d:build"]);
+        assert!(graph.task("c:build").unwrap().depends_on.is_empty());
+    }
+}
@@ -1,9 +1,11 @@
-use super::{Language, LanguageDetection, LanguageDetectionSignal, LanguageDetectionSource};
-use serde::Serialize;
-use std::fs;
+use super::{
+    FileSystem, Language, LanguageDetection, LanguageDetectionSignal, LanguageDetectionSource,
+    LocalFileSystem,
+};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum PackageManager {
     // JavaScript
     Npm,
@@ -17,6 +19,10 @@ pub enum PackageManager {
     Poetry,
     Pdm,
     Pipenv,
+    Hatch,
+    Pixi,
+    PipTools,
+    Conda,
     // Rust
     Cargo,
     // Golang
@@ -51,12 +57,25 @@ impl PackageManager {
             _ => None,
         }
     }
+
+    /// Runs a script of one workspace member from the workspace root.
+    pub fn run_workspace_script(&self, member: &str, script_name: &str) -> Option<String> {
+        match self {
+            PackageManager::Npm => Some(format!("npm -w {member} run {script_name}")),
+            PackageManager::Pnpm => Some(format!("pnpm --filter {member} run {script_name}")),
+            PackageManager::Yarn => Some(format!("yarn workspace {member} run {script_name}")),
+            PackageManager::Bun => Some(format!("bun run --filter {member} {script_name}")),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum PackageManagerSource {
     // JavaScript
     PackageJson,
+    /// The `volta` block of a `package.json`.
+    Volta,
     PackageLockJson,
     YarnLock,
     PnpmLockYaml,
@@ -73,6 +92,13 @@ pub enum PackageManagerSource {
     PoetryLock,
     Pipfile,
     PipfileLock,
+    UvLock,
+    PdmLock,
+    PixiToml,
+    PixiLock,
+    HatchToml,
+    RequirementsIn,
+    EnvironmentYml,
 
     // Rust
     CargoToml,
@@ -83,7 +109,7 @@ pub enum PackageManagerSource {
     GoSum,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageManagerInfo {
     pub package_manager: PackageManager,
     pub source: PackageManagerSource,
@@ -97,21 +123,22 @@ pub struct PackageManagerDetection {
     pub package_managers: Vec<PackageManagerInfo>,
 }
 
-impl TryFrom<&LanguageDetection> for PackageManagerDetection {
-    type Error = ();
-
-    fn try_from(lang_detection: &LanguageDetection) -> Result<Self, Self::Error> {
+impl PackageManagerDetection {
+    pub fn from_language_detection(
+        lang_detection: &LanguageDetection,
+        fs: &dyn FileSystem,
+    ) -> Option<Self> {
         let package_managers: Vec<PackageManagerInfo> = lang_detection
             .sources
             .iter()
-            .filter_map(|signal| Vec::<PackageManagerInfo>::try_from(signal).ok())
+            .filter_map(|signal| package_managers_from_signal(signal, fs))
             .flatten()
             .collect();
 
         if package_managers.is_empty() {
-            Err(())
+            None
         } else {
-            Ok(PackageManagerDetection {
+            Some(PackageManagerDetection {
                 language: lang_detection.language.clone(),
                 package_managers,
             })
@@ -119,8 +146,16 @@ impl TryFrom<&LanguageDetection> for PackageManagerDetection {
     }
 }
 
-fn detect_npm(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+impl TryFrom<&LanguageDetection> for PackageManagerDetection {
+    type Error = ();
+
+    fn try_from(lang_detection: &LanguageDetection) -> Result<Self, Self::Error> {
+        Self::from_language_detection(lang_detection, &LocalFileSystem).ok_or(())
+    }
+}
+
+fn detect_npm(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Npm,
             source: PackageManagerSource::PackageLockJson,
@@ -132,21 +167,88 @@ fn detect_npm(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_yarn(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_yarn(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        let setup = path.parent().map(|dir| YarnSetup::detect(fs, dir));
         vec![PackageManagerInfo {
             package_manager: PackageManager::Yarn,
             source: PackageManagerSource::YarnLock,
             path: path.to_path_buf(),
-            version: None,
+            version: setup.and_then(|setup| setup.version),
         }]
     } else {
         vec![]
     }
 }
 
-fn detect_pnpm(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+/// How a Yarn project is set up: classic (1.x) or Berry (2+), and whether it
+/// runs a release checked in under `.yarn/releases`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YarnSetup {
+    pub berry: bool,
+    pub version: Option<String>,
+    /// The `yarnPath` release, relative to the project directory.
+    pub yarn_path: Option<PathBuf>,
+    /// Plug'n'Play installs, Berry's default `nodeLinker`.
+    pub pnp: bool,
+}
+
+impl YarnSetup {
+    /// Reads `.yarnrc.yml`, `.yarn/releases` and the `yarn.lock` header in `dir`.
+    pub fn detect(fs: &dyn FileSystem, dir: &Path) -> Self {
+        let rc: Option<serde_yaml::Value> = fs
+            .read_to_string(&dir.join(".yarnrc.yml"))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok());
+        let rc_string =
+            |key: &str| -> Option<String> { rc.as_ref()?.get(key)?.as_str().map(str::to_string) };
+
+        let yarn_path = rc_string("yarnPath")
+            .map(PathBuf::from)
+            .or_else(|| {
+                let mut releases = fs.read_dir(&dir.join(".yarn/releases")).ok()?;
+                releases.sort();
+                releases
+                    .into_iter()
+                    .find(|p| yarn_release_version(p).is_some())
+                    .and_then(|p| p.strip_prefix(dir).ok().map(Path::to_path_buf))
+            })
+            .filter(|p| fs.is_file(&dir.join(p)));
+        let version = yarn_path.as_deref().and_then(yarn_release_version);
+
+        let lock_is_berry = fs
+            .read_to_string(&dir.join("yarn.lock"))
+            .is_ok_and(|content| content.lines().take(10).any(|l| l == "__metadata:"));
+        let berry = match version.as_deref().and_then(|v| v.split('.').next()) {
+            Some(major) => major != "1",
+            None => rc.is_some() || lock_is_berry,
+        };
+
+        let node_linker = rc_string("nodeLinker");
+        let pnp = berry
+            && (node_linker.as_deref() == Some("pnp")
+                || (node_linker.is_none() && fs.exists(&dir.join(".pnp.cjs"))));
+
+        Self {
+            berry,
+            version,
+            yarn_path,
+            pnp,
+        }
+    }
+}
+
+/// `4.1.0` from `yarn-4.1.0.cjs`.
+fn yarn_release_version(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let version = name.strip_prefix("yarn-")?.strip_suffix(".cjs")?;
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| version.to_string())
+}
+
+fn detect_pnpm(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Pnpm,
             source: PackageManagerSource::PnpmLockYaml,
@@ -158,8 +260,8 @@ fn detect_pnpm(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_bun_lockb(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_bun_lockb(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Bun,
             source: PackageManagerSource::BunLockb,
@@ -171,8 +273,8 @@ fn detect_bun_lockb(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_bun_lock(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_bun_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Bun,
             source: PackageManagerSource::BunLock,
@@ -184,8 +286,8 @@ fn detect_bun_lock(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_deno_json(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_deno_json(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Deno,
             source: PackageManagerSource::DenoJson,
@@ -197,8 +299,8 @@ fn detect_deno_json(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_deno_jsonc(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_deno_jsonc(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Deno,
             source: PackageManagerSource::DenoJsonc,
@@ -210,8 +312,8 @@ fn detect_deno_jsonc(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_deno_lock(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_deno_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Deno,
             source: PackageManagerSource::DenoLock,
@@ -223,8 +325,8 @@ fn detect_deno_lock(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_lock_json(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_lock_json(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Deno,
             source: PackageManagerSource::LockJson,
@@ -236,8 +338,8 @@ fn detect_lock_json(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_from_package_json(path: &Path) -> Vec<PackageManagerInfo> {
-    let Ok(content) = fs::read_to_string(path) else {
+fn detect_from_package_json(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
         return vec![];
     };
 
@@ -245,6 +347,8 @@ fn detect_from_package_json(path: &Path) -> Vec<PackageManagerInfo> {
         return vec![];
     };
 
+    let mut package_managers = Vec::new();
+
     if let Some(package_manager_str) = parsed.get("packageManager").and_then(|v| v.as_str()) {
         let (name, version) = if let Some(at_pos) = package_manager_str.find('@') {
             let (name_part, version_part) = package_manager_str.split_at(at_pos);
@@ -262,20 +366,37 @@ fn detect_from_package_json(path: &Path) -> Vec<PackageManagerInfo> {
         };
 
         if let Some(pm) = package_manager {
-            return vec![PackageManagerInfo {
+            package_managers.push(PackageManagerInfo {
                 package_manager: pm,
                 source: PackageManagerSource::PackageJson,
                 path: path.to_path_buf(),
                 version,
-            }];
+            });
+        }
+    }
+
+    if let Some(volta) = parsed.get("volta").and_then(|v| v.as_object()) {
+        for (name, pm) in [
+            ("npm", PackageManager::Npm),
+            ("pnpm", PackageManager::Pnpm),
+            ("yarn", PackageManager::Yarn),
+        ] {
+            if let Some(version) = volta.get(name).and_then(|v| v.as_str()) {
+                package_managers.push(PackageManagerInfo {
+                    package_manager: pm,
+                    source: PackageManagerSource::Volta,
+                    path: path.to_path_buf(),
+                    version: Some(version.to_string()),
+                });
+            }
         }
     }
 
-    vec![]
+    package_managers
 }
 
-fn detect_pip(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_pip(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Pip,
             source: PackageManagerSource::RequirementsTxt,
@@ -287,8 +408,8 @@ fn detect_pip(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_poetry_from_lock(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_poetry_from_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Poetry,
             source: PackageManagerSource::PoetryLock,
@@ -300,8 +421,8 @@ fn detect_poetry_from_lock(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_from_pyproject_toml(path: &Path) -> Vec<PackageManagerInfo> {
-    let Ok(content) = fs::read_to_string(path) else {
+fn detect_from_pyproject_toml(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
         return vec![];
     };
 
@@ -336,10 +457,28 @@ fn detect_from_pyproject_toml(path: &Path) -> Vec<PackageManagerInfo> {
             package_manager: PackageManager::Uv,
             source: PackageManagerSource::PyprojectToml,
             path: path.to_path_buf(),
+            version: uv_required_version(fs, path.parent()),
+        });
+    }
+
+    if parsed.get("tool").and_then(|t| t.get("hatch")).is_some() {
+        results.push(PackageManagerInfo {
+            package_manager: PackageManager::Hatch,
+            source: PackageManagerSource::PyprojectToml,
+            path: path.to_path_buf(),
             version: None,
         });
     }
 
+    if let Some(pixi) = parsed.get("tool").and_then(|t| t.get("pixi")) {
+        results.push(PackageManagerInfo {
+            package_manager: PackageManager::Pixi,
+            source: PackageManagerSource::PyprojectToml,
+            path: path.to_path_buf(),
+            version: requires_pixi(pixi),
+        });
+    }
+
     if results.is_empty() {
         results.push(PackageManagerInfo {
             package_manager: PackageManager::Pip,
@@ -352,8 +491,8 @@ fn detect_from_pyproject_toml(path: &Path) -> Vec<PackageManagerInfo> {
     results
 }
 
-fn detect_pipenv(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_pipenv(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Pipenv,
             source: PackageManagerSource::Pipfile,
@@ -365,8 +504,8 @@ fn detect_pipenv(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_pipenv_lock(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_pipenv_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Pipenv,
             source: PackageManagerSource::PipfileLock,
@@ -378,8 +517,133 @@ fn detect_pipenv_lock(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_cargo_toml(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_uv_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        vec![PackageManagerInfo {
+            package_manager: PackageManager::Uv,
+            source: PackageManagerSource::UvLock,
+            path: path.to_path_buf(),
+            version: uv_required_version(fs, path.parent()),
+        }]
+    } else {
+        vec![]
+    }
+}
+
+/// uv's `required-version`, from `uv.toml` or the `[tool.uv]` table.
+fn uv_required_version(fs: &dyn FileSystem, dir: Option<&Path>) -> Option<String> {
+    let dir = dir?;
+    let read = |name: &str| -> Option<toml::Value> {
+        toml::from_str(&fs.read_to_string(&dir.join(name)).ok()?).ok()
+    };
+
+    let uv_toml = read("uv.toml");
+    let pyproject = read("pyproject.toml");
+    uv_toml
+        .as_ref()
+        .or_else(|| pyproject.as_ref()?.get("tool")?.get("uv"))?
+        .get("required-version")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn detect_pdm_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        vec![PackageManagerInfo {
+            package_manager: PackageManager::Pdm,
+            source: PackageManagerSource::PdmLock,
+            path: path.to_path_buf(),
+            version: None,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+fn detect_pixi_toml(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+
+    let version = toml::from_str::<toml::Value>(&content)
+        .ok()
+        .and_then(|parsed| requires_pixi(&parsed));
+    vec![PackageManagerInfo {
+        package_manager: PackageManager::Pixi,
+        source: PackageManagerSource::PixiToml,
+        path: path.to_path_buf(),
+        version,
+    }]
+}
+
+fn detect_pixi_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        let version = path
+            .parent()
+            .and_then(|dir| fs.read_to_string(&dir.join("pixi.toml")).ok())
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|parsed| requires_pixi(&parsed));
+        vec![PackageManagerInfo {
+            package_manager: PackageManager::Pixi,
+            source: PackageManagerSource::PixiLock,
+            path: path.to_path_buf(),
+            version,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+/// `requires-pixi` from the `[workspace]` (or older `[project]`) table of a
+/// pixi manifest.
+fn requires_pixi(manifest: &toml::Value) -> Option<String> {
+    ["workspace", "project"]
+        .iter()
+        .find_map(|table| manifest.get(table)?.get("requires-pixi")?.as_str())
+        .map(str::to_string)
+}
+
+fn detect_hatch_toml(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        vec![PackageManagerInfo {
+            package_manager: PackageManager::Hatch,
+            source: PackageManagerSource::HatchToml,
+            path: path.to_path_buf(),
+            version: None,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+fn detect_pip_tools(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        vec![PackageManagerInfo {
+            package_manager: PackageManager::PipTools,
+            source: PackageManagerSource::RequirementsIn,
+            path: path.to_path_buf(),
+            version: None,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+fn detect_conda(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
+        vec![PackageManagerInfo {
+            package_manager: PackageManager::Conda,
+            source: PackageManagerSource::EnvironmentYml,
+            path: path.to_path_buf(),
+            version: None,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+fn detect_cargo_toml(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Cargo,
             source: PackageManagerSource::CargoToml,
@@ -391,8 +655,8 @@ fn detect_cargo_toml(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_cargo_lock(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_cargo_lock(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Cargo,
             source: PackageManagerSource::CargoLock,
@@ -404,8 +668,8 @@ fn detect_cargo_lock(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_go_mod(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_go_mod(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Go,
             source: PackageManagerSource::GoMod,
@@ -417,8 +681,8 @@ fn detect_go_mod(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
-fn detect_go_sum(path: &Path) -> Vec<PackageManagerInfo> {
-    if path.exists() {
+fn detect_go_sum(fs: &dyn FileSystem, path: &Path) -> Vec<PackageManagerInfo> {
+    if fs.exists(path) {
         vec![PackageManagerInfo {
             package_manager: PackageManager::Go,
             source: PackageManagerSource::GoSum,
@@ -430,57 +694,72 @@ fn detect_go_sum(path: &Path) -> Vec<PackageManagerInfo> {
     }
 }
 
+pub fn package_managers_from_signal(
+    signal: &LanguageDetectionSignal,
+    fs: &dyn FileSystem,
+) -> Option<Vec<PackageManagerInfo>> {
+    match signal {
+        LanguageDetectionSignal::Strong { path, source } => {
+            let package_managers = match source {
+                // JavaScript
+                LanguageDetectionSource::PackageJson => detect_from_package_json(fs, path),
+                LanguageDetectionSource::PackageLockJson => detect_npm(fs, path),
+                LanguageDetectionSource::YarnLock => detect_yarn(fs, path),
+                LanguageDetectionSource::PnpmLockYaml => detect_pnpm(fs, path),
+                LanguageDetectionSource::BunLockb => detect_bun_lockb(fs, path),
+                LanguageDetectionSource::BunLock => detect_bun_lock(fs, path),
+                LanguageDetectionSource::DenoJson => detect_deno_json(fs, path),
+                LanguageDetectionSource::DenoJsonc => detect_deno_jsonc(fs, path),
+                LanguageDetectionSource::DenoLock => detect_deno_lock(fs, path),
+                LanguageDetectionSource::LockJson => detect_lock_json(fs, path),
+
+                // Python
+                LanguageDetectionSource::RequirementsTxt => detect_pip(fs, path),
+                LanguageDetectionSource::PyprojectToml => detect_from_pyproject_toml(fs, path),
+                LanguageDetectionSource::PoetryLock => detect_poetry_from_lock(fs, path),
+                LanguageDetectionSource::Pipfile => detect_pipenv(fs, path),
+                LanguageDetectionSource::PipfileLock => detect_pipenv_lock(fs, path),
+                LanguageDetectionSource::UvLock => detect_uv_lock(fs, path),
+                LanguageDetectionSource::PdmLock => detect_pdm_lock(fs, path),
+                LanguageDetectionSource::PixiToml => detect_pixi_toml(fs, path),
+                LanguageDetectionSource::PixiLock => detect_pixi_lock(fs, path),
+                LanguageDetectionSource::HatchToml => detect_hatch_toml(fs, path),
+                LanguageDetectionSource::RequirementsIn => detect_pip_tools(fs, path),
+                LanguageDetectionSource::EnvironmentYml => detect_conda(fs, path),
+
+                // Rust
+                LanguageDetectionSource::CargoToml => detect_cargo_toml(fs, path),
+                LanguageDetectionSource::CargoLock => detect_cargo_lock(fs, path),
+
+                // Go
+                LanguageDetectionSource::GoMod => detect_go_mod(fs, path),
+                LanguageDetectionSource::GoSum => detect_go_sum(fs, path),
+
+                _ => vec![],
+            };
+
+            if package_managers.is_empty() {
+                None
+            } else {
+                Some(package_managers)
+            }
+        }
+        LanguageDetectionSignal::Weak(_) => None,
+    }
+}
+
 impl TryFrom<&LanguageDetectionSignal> for Vec<PackageManagerInfo> {
     type Error = ();
 
     fn try_from(signal: &LanguageDetectionSignal) -> Result<Self, Self::Error> {
-        match signal {
-            LanguageDetectionSignal::Strong { path, source } => {
-                let package_managers = match source {
-                    // JavaScript
-                    LanguageDetectionSource::PackageJson => detect_from_package_json(path),
-                    LanguageDetectionSource::PackageLockJson => detect_npm(path),
-                    LanguageDetectionSource::YarnLock => detect_yarn(path),
-                    LanguageDetectionSource::PnpmLockYaml => detect_pnpm(path),
-                    LanguageDetectionSource::BunLockb => detect_bun_lockb(path),
-                    LanguageDetectionSource::BunLock => detect_bun_lock(path),
-                    LanguageDetectionSource::DenoJson => detect_deno_json(path),
-                    LanguageDetectionSource::DenoJsonc => detect_deno_jsonc(path),
-                    LanguageDetectionSource::DenoLock => detect_deno_lock(path),
-                    LanguageDetectionSource::LockJson => detect_lock_json(path),
-
-                    // Python
-                    LanguageDetectionSource::RequirementsTxt => detect_pip(path),
-                    LanguageDetectionSource::PyprojectToml => detect_from_pyproject_toml(path),
-                    LanguageDetectionSource::PoetryLock => detect_poetry_from_lock(path),
-                    LanguageDetectionSource::Pipfile => detect_pipenv(path),
-                    LanguageDetectionSource::PipfileLock => detect_pipenv_lock(path),
-
-                    // Rust
-                    LanguageDetectionSource::CargoToml => detect_cargo_toml(path),
-                    LanguageDetectionSource::CargoLock => detect_cargo_lock(path),
-
-                    // Go
-                    LanguageDetectionSource::GoMod => detect_go_mod(path),
-                    LanguageDetectionSource::GoSum => detect_go_sum(path),
-
-                    _ => vec![],
-                };
-
-                if package_managers.is_empty() {
-                    Err(())
-                } else {
-                    Ok(package_managers)
-                }
-            }
-            LanguageDetectionSignal::Weak(_) => Err(()),
-        }
+        package_managers_from_signal(signal, &LocalFileSystem).ok_or(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -520,6 +799,14 @@ mod tests {
                 Some("pnpm run test".to_string())
             );
             assert_eq!(PackageManager::Cargo.run_script("test"), None);
+            assert_eq!(
+                PackageManager::Pnpm.run_workspace_script("@acme/web", "test"),
+                Some("pnpm --filter @acme/web run test".to_string())
+            );
+            assert_eq!(
+                PackageManager::Npm.run_workspace_script("api", "build"),
+                Some("npm -w api run build".to_string())
+            );
         }
     }
 
@@ -531,7 +818,7 @@ mod tests {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "package-lock.json", "{}");
 
-            let pms = detect_npm(&path);
+            let pms = detect_npm(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Npm);
             assert!(matches!(
@@ -545,18 +832,73 @@ mod tests {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "yarn.lock", "");
 
-            let pms = detect_yarn(&path);
+            let pms = detect_yarn(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Yarn);
             assert!(matches!(pms[0].source, PackageManagerSource::YarnLock));
         }
 
+        #[test]
+        fn test_detect_yarn_classic() {
+            let dir = TempDir::new().unwrap();
+            create_temp_file(
+                &dir,
+                "yarn.lock",
+                "# yarn lockfile v1\n\nleft-pad@^1.0.0:\n",
+            );
+
+            let setup = YarnSetup::detect(&LocalFileSystem, dir.path());
+            assert!(!setup.berry);
+            assert!(!setup.pnp);
+            assert_eq!(setup.yarn_path, None);
+        }
+
+        #[test]
+        fn test_detect_yarn_berry_release() {
+            let dir = TempDir::new().unwrap();
+            let path = create_temp_file(
+                &dir,
+                "yarn.lock",
+                "# This file is generated by running \"yarn install\"\n\n__metadata:\n  version: 8\n",
+            );
+            create_temp_file(
+                &dir,
+                ".yarnrc.yml",
+                "yarnPath: .yarn/releases/yarn-4.1.0.cjs\nnodeLinker: pnp\n",
+            );
+            fs::create_dir_all(dir.path().join(".yarn/releases")).unwrap();
+            create_temp_file(&dir, ".yarn/releases/yarn-4.1.0.cjs", "");
+
+            let setup = YarnSetup::detect(&LocalFileSystem, dir.path());
+            assert!(setup.berry);
+            assert!(setup.pnp);
+            assert_eq!(setup.version.as_deref(), Some("4.1.0"));
+            assert_eq!(
+                setup.yarn_path.as_deref(),
+                Some(Path::new(".yarn/releases/yarn-4.1.0.cjs"))
+            );
+
+            let pms = detect_yarn(&LocalFileSystem, &path);
+            assert_eq!(pms[0].version.as_deref(), Some("4.1.0"));
+        }
+
+        #[test]
+        fn test_detect_yarn_berry_from_lockfile() {
+            let dir = TempDir::new().unwrap();
+            create_temp_file(&dir, "yarn.lock", "__metadata:\n  version: 6\n");
+
+            let setup = YarnSetup::detect(&LocalFileSystem, dir.path());
+            assert!(setup.berry);
+            assert_eq!(setup.version, None);
+            assert_eq!(setup.yarn_path, None);
+        }
+
         #[test]
         fn test_detect_pnpm() {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "pnpm-lock.yaml", "");
 
-            let pms = detect_pnpm(&path);
+            let pms = detect_pnpm(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pnpm);
             assert!(matches!(pms[0].source, PackageManagerSource::PnpmLockYaml));
@@ -567,7 +909,7 @@ mod tests {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "bun.lockb", "");
 
-            let pms = detect_bun_lockb(&path);
+            let pms = detect_bun_lockb(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Bun);
             assert!(matches!(pms[0].source, PackageManagerSource::BunLockb));
@@ -578,7 +920,7 @@ mod tests {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "deno.json", "{}");
 
-            let pms = detect_deno_json(&path);
+            let pms = detect_deno_json(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Deno);
             assert!(matches!(pms[0].source, PackageManagerSource::DenoJson));
@@ -589,7 +931,7 @@ mod tests {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "lock.json", "{}");
 
-            let pms = detect_lock_json(&path);
+            let pms = detect_lock_json(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Deno);
             assert!(matches!(pms[0].source, PackageManagerSource::LockJson));
@@ -601,7 +943,7 @@ mod tests {
             let content = r#"{"packageManager": "pnpm@9.0.0"}"#;
             let path = create_temp_file(&dir, "package.json", content);
 
-            let pms = detect_from_package_json(&path);
+            let pms = detect_from_package_json(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pnpm);
             assert!(matches!(pms[0].source, PackageManagerSource::PackageJson));
@@ -614,7 +956,7 @@ mod tests {
             let content = r#"{"packageManager": "pnpm"}"#;
             let path = create_temp_file(&dir, "package.json", content);
 
-            let pms = detect_from_package_json(&path);
+            let pms = detect_from_package_json(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pnpm);
             assert!(matches!(pms[0].source, PackageManagerSource::PackageJson));
@@ -627,7 +969,7 @@ mod tests {
             let content = r#"{"name": "test"}"#;
             let path = create_temp_file(&dir, "package.json", content);
 
-            let pms = detect_from_package_json(&path);
+            let pms = detect_from_package_json(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 0);
         }
 
@@ -637,7 +979,7 @@ mod tests {
             let content = r#"{"packageManager": "unknown@1.0.0"}"#;
             let path = create_temp_file(&dir, "package.json", content);
 
-            let pms = detect_from_package_json(&path);
+            let pms = detect_from_package_json(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 0);
         }
 
@@ -654,12 +996,34 @@ mod tests {
                 let content = format!(r#"{{"packageManager": "{}"}}"#, pm_str);
                 let path = create_temp_file(&dir, &format!("package-{}.json", pm_str), &content);
 
-                let pms = detect_from_package_json(&path);
+                let pms = detect_from_package_json(&LocalFileSystem, &path);
                 assert_eq!(pms.len(), 1);
                 assert_eq!(pms[0].package_manager, expected_pm);
                 assert_eq!(pms[0].version, Some(expected_version.to_string()));
             }
         }
+
+        #[test]
+        fn test_detect_from_package_json_volta() {
+            let dir = TempDir::new().unwrap();
+            let content = r#"{"packageManager": "pnpm@9.1.0", "volta": {"node": "20.11.0", "npm": "10.2.4", "yarn": "1.22.19"}}"#;
+            let path = create_temp_file(&dir, "package.json", content);
+
+            let pms = detect_from_package_json(&LocalFileSystem, &path);
+            let found: Vec<(PackageManager, &str)> = pms
+                .iter()
+                .map(|pm| (pm.package_manager, pm.version.as_deref().unwrap()))
+                .collect();
+            assert_eq!(
+                found,
+                [
+                    (PackageManager::Pnpm, "9.1.0"),
+                    (PackageManager::Npm, "10.2.4"),
+                    (PackageManager::Yarn, "1.22.19"),
+                ]
+            );
+            assert!(matches!(pms[1].source, PackageManagerSource::Volta));
+        }
     }
 
     mod python {
@@ -670,7 +1034,7 @@ mod tests {
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "requirements.txt", "requests==2.28.0");
 
-            let pms = detect_pip(&path);
+            let pms = detect_pip(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pip);
             assert!(matches!(
@@ -689,7 +1053,7 @@ version = "0.1.0"
 "#;
             let path = create_temp_file(&dir, "pyproject.toml", content);
 
-            let pms = detect_from_pyproject_toml(&path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Poetry);
             assert!(matches!(pms[0].source, PackageManagerSource::PyprojectToml));
@@ -703,7 +1067,7 @@ version = "0.1.0"
 "#;
             let path = create_temp_file(&dir, "pyproject.toml", content);
 
-            let pms = detect_from_pyproject_toml(&path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pdm);
         }
@@ -716,7 +1080,7 @@ version = "0.1.0"
 "#;
             let path = create_temp_file(&dir, "pyproject.toml", content);
 
-            let pms = detect_from_pyproject_toml(&path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Uv);
         }
@@ -730,7 +1094,7 @@ name = "test"
 "#;
             let path = create_temp_file(&dir, "pyproject.toml", content);
 
-            let pms = detect_from_pyproject_toml(&path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pip);
         }
@@ -740,7 +1104,7 @@ name = "test"
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "Pipfile", "");
 
-            let pms = detect_pipenv(&path);
+            let pms = detect_pipenv(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pipenv);
             assert!(matches!(pms[0].source, PackageManagerSource::Pipfile));
@@ -751,11 +1115,91 @@ name = "test"
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "poetry.lock", "");
 
-            let pms = detect_poetry_from_lock(&path);
+            let pms = detect_poetry_from_lock(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Poetry);
             assert!(matches!(pms[0].source, PackageManagerSource::PoetryLock));
         }
+
+        #[test]
+        fn test_detect_uv_lock_with_required_version() {
+            let dir = TempDir::new().unwrap();
+            create_temp_file(
+                &dir,
+                "pyproject.toml",
+                "[project]\nname = \"demo\"\n\n[tool.uv]\nrequired-version = \">=0.5.0\"\n",
+            );
+            let path = create_temp_file(&dir, "uv.lock", "version = 1\n");
+
+            let pms = detect_uv_lock(&LocalFileSystem, &path);
+            assert_eq!(pms.len(), 1);
+            assert_eq!(pms[0].package_manager, PackageManager::Uv);
+            assert!(matches!(pms[0].source, PackageManagerSource::UvLock));
+            assert_eq!(pms[0].version.as_deref(), Some(">=0.5.0"));
+        }
+
+        #[test]
+        fn test_detect_pixi_toml_and_lock() {
+            let dir = TempDir::new().unwrap();
+            let path = create_temp_file(
+                &dir,
+                "pixi.toml",
+                "[workspace]\nname = \"demo\"\nrequires-pixi = \">=0.40\"\n",
+            );
+            let lock = create_temp_file(&dir, "pixi.lock", "version: 6\n");
+
+            let pms = detect_pixi_toml(&LocalFileSystem, &path);
+            assert_eq!(pms[0].package_manager, PackageManager::Pixi);
+            assert_eq!(pms[0].version.as_deref(), Some(">=0.40"));
+
+            let pms = detect_pixi_lock(&LocalFileSystem, &lock);
+            assert!(matches!(pms[0].source, PackageManagerSource::PixiLock));
+            assert_eq!(pms[0].version.as_deref(), Some(">=0.40"));
+        }
+
+        #[test]
+        fn test_detect_hatch_and_pixi_from_pyproject() {
+            let dir = TempDir::new().unwrap();
+            let content = r#"
+[tool.hatch.envs.default.scripts]
+test = "pytest"
+
+[tool.pixi.workspace]
+requires-pixi = "0.41.0"
+"#;
+            let path = create_temp_file(&dir, "pyproject.toml", content);
+
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &path);
+            assert_eq!(pms.len(), 2);
+            assert_eq!(pms[0].package_manager, PackageManager::Hatch);
+            assert_eq!(pms[1].package_manager, PackageManager::Pixi);
+            assert_eq!(pms[1].version.as_deref(), Some("0.41.0"));
+        }
+
+        #[test]
+        fn test_detect_pip_tools_and_conda_via_signal() {
+            let dir = TempDir::new().unwrap();
+            let inputs = create_temp_file(&dir, "requirements-dev.in", "pytest\n");
+            let env = create_temp_file(&dir, "environment.yml", "dependencies:\n  - python=3.12\n");
+            let lock = create_temp_file(&dir, "pdm.lock", "[metadata]\n");
+            let hatch = create_temp_file(&dir, "hatch.toml", "[envs.default]\n");
+
+            let managers: Vec<PackageManager> = [inputs, env, lock, hatch]
+                .into_iter()
+                .map(|path| LanguageDetectionSignal::try_from(path).unwrap())
+                .flat_map(|signal| package_managers_from_signal(&signal, &LocalFileSystem).unwrap())
+                .map(|info| info.package_manager)
+                .collect();
+            assert_eq!(
+                managers,
+                [
+                    PackageManager::PipTools,
+                    PackageManager::Conda,
+                    PackageManager::Pdm,
+                    PackageManager::Hatch
+                ]
+            );
+        }
     }
 
     mod rust {
@@ -770,7 +1214,7 @@ name = "test"
 "#;
             let path = create_temp_file(&dir, "Cargo.toml", content);
 
-            let pms = detect_cargo_toml(&path);
+            let pms = detect_cargo_toml(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Cargo);
             assert!(matches!(pms[0].source, PackageManagerSource::CargoToml));
@@ -781,7 +1225,7 @@ name = "test"
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "Cargo.lock", "");
 
-            let pms = detect_cargo_lock(&path);
+            let pms = detect_cargo_lock(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Cargo);
             assert!(matches!(pms[0].source, PackageManagerSource::CargoLock));
@@ -797,7 +1241,7 @@ name = "test"
             let content = "module test\n\ngo 1.21\n";
             let path = create_temp_file(&dir, "go.mod", content);
 
-            let pms = detect_go_mod(&path);
+            let pms = detect_go_mod(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Go);
             assert!(matches!(pms[0].source, PackageManagerSource::GoMod));
@@ -808,7 +1252,7 @@ name = "test"
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "go.sum", "");
 
-            let pms = detect_go_sum(&path);
+            let pms = detect_go_sum(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Go);
             assert!(matches!(pms[0].source, PackageManagerSource::GoSum));
@@ -981,7 +1425,7 @@ dev = ["pytest"]
 "#;
             let pyproject_path = create_temp_file(&dir, "pyproject.toml", content);
 
-            let pms = detect_from_pyproject_toml(&pyproject_path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &pyproject_path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Uv);
         }
@@ -989,11 +1433,11 @@ dev = ["pytest"]
         #[test]
         fn test_nonexistent_files() {
             let path = Path::new("/nonexistent/file.txt");
-            assert!(detect_npm(path).is_empty());
-            assert!(detect_yarn(path).is_empty());
-            assert!(detect_pnpm(path).is_empty());
-            assert!(detect_go_mod(path).is_empty());
-            assert!(detect_cargo_toml(path).is_empty());
+            assert!(detect_npm(&LocalFileSystem, path).is_empty());
+            assert!(detect_yarn(&LocalFileSystem, path).is_empty());
+            assert!(detect_pnpm(&LocalFileSystem, path).is_empty());
+            assert!(detect_go_mod(&LocalFileSystem, path).is_empty());
+            assert!(detect_cargo_toml(&LocalFileSystem, path).is_empty());
         }
 
         #[test]
@@ -1001,7 +1445,7 @@ dev = ["pytest"]
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "deno.jsonc", "{}");
 
-            let pms = detect_deno_jsonc(&path);
+            let pms = detect_deno_jsonc(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Deno);
             assert!(matches!(pms[0].source, PackageManagerSource::DenoJsonc));
@@ -1012,7 +1456,7 @@ dev = ["pytest"]
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "bun.lock", "");
 
-            let pms = detect_bun_lock(&path);
+            let pms = detect_bun_lock(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Bun);
             assert!(matches!(pms[0].source, PackageManagerSource::BunLock));
@@ -1023,7 +1467,7 @@ dev = ["pytest"]
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "deno.lock", "{}");
 
-            let pms = detect_deno_lock(&path);
+            let pms = detect_deno_lock(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Deno);
             assert!(matches!(pms[0].source, PackageManagerSource::DenoLock));
@@ -1034,7 +1478,7 @@ dev = ["pytest"]
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "Pipfile.lock", "{}");
 
-            let pms = detect_pipenv_lock(&path);
+            let pms = detect_pipenv_lock(&LocalFileSystem, &path);
             assert_eq!(pms.len(), 1);
             assert_eq!(pms[0].package_manager, PackageManager::Pipenv);
             assert!(matches!(pms[0].source, PackageManagerSource::PipfileLock));
@@ -1045,14 +1489,14 @@ dev = ["pytest"]
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "package.json", "{ invalid json }");
 
-            let pms = detect_from_package_json(&path);
+            let pms = detect_from_package_json(&LocalFileSystem, &path);
             assert!(pms.is_empty());
         }
 
         #[test]
         fn test_package_json_missing_file() {
             let path = Path::new("/nonexistent/package.json");
-            let pms = detect_from_package_json(path);
+            let pms = detect_from_package_json(&LocalFileSystem, path);
             assert!(pms.is_empty());
         }
 
@@ -1061,14 +1505,14 @@ dev = ["pytest"]
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, "pyproject.toml", "invalid toml {{");
 
-            let pms = detect_from_pyproject_toml(&path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, &path);
             assert!(pms.is_empty());
         }
 
         #[test]
         fn test_pyproject_toml_missing_file() {
             let path = Path::new("/nonexistent/pyproject.toml");
-            let pms = detect_from_pyproject_toml(path);
+            let pms = detect_from_pyproject_toml(&LocalFileSystem, path);
             assert!(pms.is_empty());
         }
     }
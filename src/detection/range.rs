@@ -0,0 +1,491 @@
+use serde::Serialize;
+
+/// A concrete `major.minor.patch` point. Pre-release and build metadata are
+/// ignored when reasoning about ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// The half-open interval `[start, end)`; an `end` of `None` is unbounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VersionInterval {
+    pub start: Version,
+    pub end: Option<Version>,
+}
+
+impl VersionInterval {
+    /// Every `major.x.y` release, as packaged by e.g. `nodejs_20`.
+    pub const fn major_series(major: u32) -> Self {
+        Self {
+            start: Version::new(major, 0, 0),
+            end: Some(Version::new(major + 1, 0, 0)),
+        }
+    }
+
+    /// Every `major.minor.x` release, as packaged by e.g. `python311`.
+    pub const fn minor_series(major: u32, minor: u32) -> Self {
+        Self {
+            start: Version::new(major, minor, 0),
+            end: Some(Version::new(major, minor + 1, 0)),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.end.is_some_and(|end| end <= self.start)
+    }
+
+    fn intersect(&self, other: &Self) -> Self {
+        let end = match (self.end, other.end) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        Self {
+            start: self.start.max(other.start),
+            end,
+        }
+    }
+}
+
+/// The syntax a range is written in. The two disagree on what a partial
+/// version means after `>` and `<=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeFlavor {
+    /// npm semver ranges: `>3.8` is above the whole `3.8.x` series.
+    Npm,
+    /// PEP 440 specifiers: `>3.8` is above `3.8.0` itself, so it takes 3.8.1.
+    Pep440,
+}
+
+/// A set of versions, stored as a union of disjoint intervals.
+///
+/// Parses npm-style ranges (`^`, `~`, `x`-ranges, hyphen ranges, `||`) and
+/// PEP 440 specifiers (`~=`, `==`, `!=`, comma-separated clauses). A partial
+/// version such as `3.11` or `20` stands for its whole series, also after
+/// `=` and `!=`, and after `<=` and `>` in npm ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionRange {
+    intervals: Vec<VersionInterval>,
+}
+
+impl VersionRange {
+    pub fn any() -> Self {
+        Self::from_intervals(vec![VersionInterval {
+            start: Version::new(0, 0, 0),
+            end: None,
+        }])
+    }
+
+    pub fn parse(expr: &str) -> Option<Self> {
+        Self::parse_as(expr, RangeFlavor::Npm)
+    }
+
+    pub fn parse_as(expr: &str, flavor: RangeFlavor) -> Option<Self> {
+        let mut intervals = Vec::new();
+        for alternative in expr.split("||") {
+            intervals.extend(parse_conjunction(alternative.trim(), flavor)?.intervals);
+        }
+        Some(Self::from_intervals(intervals))
+    }
+
+    pub fn intervals(&self) -> &[VersionInterval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().map(move |b| a.intersect(b)))
+            .collect();
+        Self::from_intervals(intervals)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_intervals(
+            self.intervals
+                .iter()
+                .chain(&other.intervals)
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn contains(&self, version: Version) -> bool {
+        self.intervals
+            .iter()
+            .any(|i| i.start <= version && i.end.is_none_or(|end| version < end))
+    }
+
+    pub fn overlaps(&self, interval: VersionInterval) -> bool {
+        self.intervals
+            .iter()
+            .any(|i| !i.intersect(&interval).is_empty())
+    }
+
+    fn from_intervals(mut intervals: Vec<VersionInterval>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort_by_key(|i| i.start);
+
+        let mut merged: Vec<VersionInterval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.end.is_none_or(|end| interval.start <= end) => {
+                    last.end = match (last.end, interval.end) {
+                        (Some(a), Some(b)) => Some(a.max(b)),
+                        _ => None,
+                    };
+                }
+                _ => merged.push(interval),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    fn below(end: Version) -> Self {
+        Self::from_intervals(vec![VersionInterval {
+            start: Version::new(0, 0, 0),
+            end: Some(end),
+        }])
+    }
+
+    fn between(start: Version, end: Option<Version>) -> Self {
+        Self::from_intervals(vec![VersionInterval { start, end }])
+    }
+}
+
+/// A version with up to three numeric components; missing ones are wildcards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PartialVersion {
+    parts: Vec<u32>,
+}
+
+impl PartialVersion {
+    fn parse(raw: &str) -> Option<Self> {
+        let trimmed = raw
+            .trim()
+            .trim_start_matches('v')
+            .trim_start_matches("python-")
+            .trim_start_matches("node-");
+        let core = trimmed.split(['-', '+']).next().unwrap_or(trimmed);
+
+        let mut parts = Vec::new();
+        for component in core.split('.') {
+            if matches!(component, "x" | "X" | "*") {
+                break;
+            }
+            let digits: String = component.chars().take_while(char::is_ascii_digit).collect();
+            if digits.is_empty() {
+                return None;
+            }
+            parts.push(digits.parse().ok()?);
+            if digits.len() != component.len() || parts.len() == 3 {
+                break;
+            }
+        }
+
+        if parts.is_empty() && !matches!(core, "x" | "X" | "*" | "") {
+            return None;
+        }
+        Some(Self { parts })
+    }
+
+    fn lower(&self) -> Version {
+        let part = |i: usize| self.parts.get(i).copied().unwrap_or(0);
+        Version::new(part(0), part(1), part(2))
+    }
+
+    /// First version after every release matching this prefix. Ends are
+    /// `None` when unbounded, including when a component can't be bumped.
+    fn prefix_end(&self) -> Option<Version> {
+        match self.parts[..] {
+            [] => None,
+            [major] => next_major(major),
+            [major, minor] => next_minor(major, minor),
+            [major, minor, patch, ..] => next_patch(major, minor, patch),
+        }
+    }
+
+    /// First version after the zero-padded version itself.
+    fn point_end(&self) -> Option<Version> {
+        let lower = self.lower();
+        next_patch(lower.major, lower.minor, lower.patch)
+    }
+
+    fn caret_end(&self) -> Option<Version> {
+        match self.parts[..] {
+            [] => None,
+            [major, ..] if major > 0 => next_major(major),
+            [_, minor, ..] if minor > 0 => next_minor(0, minor),
+            _ => self.prefix_end(),
+        }
+    }
+
+    fn tilde_end(&self) -> Option<Version> {
+        match self.parts[..] {
+            [] => None,
+            [major] => next_major(major),
+            [major, minor, ..] => next_minor(major, minor),
+        }
+    }
+
+    /// PEP 440 compatible release: `~=3.9` is `>=3.9, ==3.*`. Only defined
+    /// for two or three components.
+    fn compatible_end(&self) -> Option<Option<Version>> {
+        match self.parts[..] {
+            [major, _] => Some(next_major(major)),
+            [major, minor, _] => Some(next_minor(major, minor)),
+            _ => None,
+        }
+    }
+}
+
+fn next_major(major: u32) -> Option<Version> {
+    Some(Version::new(major.checked_add(1)?, 0, 0))
+}
+
+fn next_minor(major: u32, minor: u32) -> Option<Version> {
+    Some(Version::new(major, minor.checked_add(1)?, 0))
+}
+
+fn next_patch(major: u32, minor: u32, patch: u32) -> Option<Version> {
+    Some(Version::new(major, minor, patch.checked_add(1)?))
+}
+
+fn parse_conjunction(expr: &str, flavor: RangeFlavor) -> Option<VersionRange> {
+    if let Some((low, high)) = expr.split_once(" - ") {
+        let low = PartialVersion::parse(low)?;
+        let high = PartialVersion::parse(high)?;
+        return Some(VersionRange::between(low.lower(), high.prefix_end()));
+    }
+
+    let mut range = VersionRange::any();
+    let mut pending_operator = String::new();
+    for token in expr.split([',', ' ', '\t']).filter(|t| !t.is_empty()) {
+        if token.chars().all(|c| "<>=!~^".contains(c)) {
+            pending_operator.push_str(token);
+            continue;
+        }
+        let comparator = format!("{pending_operator}{token}");
+        pending_operator.clear();
+        range = range.intersect(&parse_comparator(&comparator, flavor)?);
+    }
+
+    pending_operator.is_empty().then_some(range)
+}
+
+fn parse_comparator(comparator: &str, flavor: RangeFlavor) -> Option<VersionRange> {
+    const OPERATORS: &[&str] = &["===", "==", "!=", "~=", ">=", "<=", ">", "<", "^", "~", "="];
+
+    let (operator, version) = OPERATORS
+        .iter()
+        .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", comparator));
+    let version = PartialVersion::parse(version)?;
+    let lower = version.lower();
+    // The first version past the operand, for `>` and `<=`.
+    let past = match flavor {
+        RangeFlavor::Npm => version.prefix_end(),
+        RangeFlavor::Pep440 if version.parts.is_empty() => None,
+        RangeFlavor::Pep440 => version.point_end(),
+    };
+
+    let range = match operator {
+        "" | "=" | "==" | "===" => VersionRange::between(lower, version.prefix_end()),
+        "!=" => match version.prefix_end() {
+            Some(end) => {
+                let mut intervals = VersionRange::below(lower).intervals;
+                intervals.extend(VersionRange::between(end, None).intervals);
+                VersionRange::from_intervals(intervals)
+            }
+            None => VersionRange::from_intervals(vec![]),
+        },
+        ">=" => VersionRange::between(lower, None),
+        ">" => match past {
+            Some(end) => VersionRange::between(end, None),
+            None => VersionRange::from_intervals(vec![]),
+        },
+        "<" => VersionRange::below(lower),
+        "<=" => match past {
+            Some(end) => VersionRange::below(end),
+            None => VersionRange::any(),
+        },
+        "^" => VersionRange::between(lower, version.caret_end()),
+        "~" => VersionRange::between(lower, version.tilde_end()),
+        "~=" => VersionRange::between(lower, version.compatible_end()?),
+        _ => return None,
+    };
+
+    Some(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(expr: &str) -> VersionRange {
+        VersionRange::parse(expr).unwrap()
+    }
+
+    fn v(major: u32, minor: u32, patch: u32) -> Version {
+        Version::new(major, minor, patch)
+    }
+
+    #[test]
+    fn test_partial_versions_cover_their_series() {
+        let r = range("3.11");
+        assert!(r.contains(v(3, 11, 0)));
+        assert!(r.contains(v(3, 11, 9)));
+        assert!(!r.contains(v(3, 12, 0)));
+
+        let r = range("20");
+        assert!(r.contains(v(20, 5, 1)));
+        assert!(!r.contains(v(21, 0, 0)));
+
+        assert!(range("1.22.1").contains(v(1, 22, 1)));
+        assert!(!range("1.22.1").contains(v(1, 22, 2)));
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        assert!(range(">=3.9").contains(v(3, 9, 0)));
+        assert!(!range(">=3.9").contains(v(3, 8, 20)));
+        assert!(!range("<20").contains(v(20, 0, 0)));
+        assert!(range("<20").contains(v(19, 9, 9)));
+        assert!(range("<=3.11").contains(v(3, 11, 7)));
+        assert!(!range(">3.11").contains(v(3, 11, 7)));
+        assert!(range(">3.11").contains(v(3, 12, 0)));
+        assert!(range(">1.2.3").contains(v(1, 2, 4)));
+    }
+
+    #[test]
+    fn test_comma_and_space_separated_clauses() {
+        let r = range(">=3.9,<3.12");
+        assert!(r.contains(v(3, 11, 4)));
+        assert!(!r.contains(v(3, 12, 0)));
+
+        let r = range(">= 18 < 21");
+        assert!(r.contains(v(20, 0, 0)));
+        assert!(!r.contains(v(21, 0, 0)));
+        assert!(!r.contains(v(17, 0, 0)));
+    }
+
+    #[test]
+    fn test_or_expressions() {
+        let r = range("^18 || ^20");
+        assert!(r.contains(v(18, 2, 0)));
+        assert!(r.contains(v(20, 1, 0)));
+        assert!(!r.contains(v(19, 0, 0)));
+        assert_eq!(r.intervals().len(), 2);
+    }
+
+    #[test]
+    fn test_caret_and_tilde() {
+        assert!(!range("^1.2.3").contains(v(2, 0, 0)));
+        assert!(range("^1.2.3").contains(v(1, 9, 0)));
+        assert!(!range("^0.2.3").contains(v(0, 3, 0)));
+        assert!(!range("^0.0.3").contains(v(0, 0, 4)));
+        assert!(range("~1.2.3").contains(v(1, 2, 9)));
+        assert!(!range("~1.2.3").contains(v(1, 3, 0)));
+        assert!(range("~1").contains(v(1, 9, 0)));
+    }
+
+    #[test]
+    fn test_pep440_operators() {
+        let r = range("~=3.9");
+        assert!(r.contains(v(3, 12, 0)));
+        assert!(!r.contains(v(4, 0, 0)));
+        assert!(!r.contains(v(3, 8, 0)));
+
+        let r = range("~=3.9.1");
+        assert!(r.contains(v(3, 9, 5)));
+        assert!(!r.contains(v(3, 10, 0)));
+
+        let r = range(">=3.8, !=3.10.*, <3.13");
+        assert!(!r.contains(v(3, 10, 2)));
+        assert!(r.contains(v(3, 11, 0)));
+        assert!(r.contains(v(3, 9, 0)));
+
+        assert!(VersionRange::parse("~=3").is_none());
+    }
+
+    #[test]
+    fn test_pep440_exclusive_and_inclusive_bounds() {
+        let above = VersionRange::parse_as(">3.8", RangeFlavor::Pep440).unwrap();
+        assert!(!above.contains(v(3, 8, 0)));
+        assert!(above.contains(v(3, 8, 1)));
+
+        let at_most = VersionRange::parse_as("<=3.8", RangeFlavor::Pep440).unwrap();
+        assert!(at_most.contains(v(3, 8, 0)));
+        assert!(!at_most.contains(v(3, 8, 5)));
+
+        assert!(!range(">3.8").contains(v(3, 8, 1)));
+        assert!(range("<=3.8").contains(v(3, 8, 5)));
+    }
+
+    #[test]
+    fn test_component_overflow_is_unbounded() {
+        let max = u32::MAX;
+        assert!(range(&format!("^{max}")).contains(v(max, max, max)));
+        assert!(range(&format!("{max}.{max}.{max}")).contains(v(max, max, max)));
+        assert!(range(&format!("~={max}.{max}")).contains(v(max, max, 0)));
+        assert!(range(&format!(">{max}.{max}.{max}")).is_empty());
+        assert_eq!(range(&format!("<={max}")), VersionRange::any());
+    }
+
+    #[test]
+    fn test_wildcards_and_hyphen_ranges() {
+        assert_eq!(range("*"), VersionRange::any());
+        assert!(range("3.x").contains(v(3, 7, 0)));
+        assert!(!range("3.x").contains(v(4, 0, 0)));
+
+        let r = range("18 - 20");
+        assert!(r.contains(v(20, 9, 0)));
+        assert!(!r.contains(v(21, 0, 0)));
+    }
+
+    #[test]
+    fn test_prefixes_and_suffixes() {
+        assert!(range("v20.11.0").contains(v(20, 11, 0)));
+        assert!(range("python-3.12").contains(v(3, 12, 1)));
+        assert!(range("3.13.0rc1").contains(v(3, 13, 0)));
+        assert!(range("1.0.0-beta.1").contains(v(1, 0, 0)));
+        assert!(VersionRange::parse("lts/*").is_none());
+        assert!(VersionRange::parse("stable").is_none());
+    }
+
+    #[test]
+    fn test_intersect() {
+        let r = range(">=3.9,<3.12").intersect(&range("3.11"));
+        assert!(r.contains(v(3, 11, 0)));
+        assert!(!r.contains(v(3, 10, 0)));
+
+        assert!(range("<20").intersect(&range(">=20")).is_empty());
+    }
+
+    #[test]
+    fn test_overlaps_series() {
+        let r = range(">=3.9,<3.12");
+        assert!(r.overlaps(VersionInterval::minor_series(3, 11)));
+        assert!(!r.overlaps(VersionInterval::minor_series(3, 12)));
+        assert!(range("<20").overlaps(VersionInterval::major_series(18)));
+        assert!(!range("<20").overlaps(VersionInterval::major_series(20)));
+    }
+}
@@ -0,0 +1,320 @@
+use super::{
+    FileSystem, LanguageDetectionSignal, TaskRunnerDetection, TaskRunnerFile, VersionInfo,
+    tool_version_signals, versions_from_signal,
+};
+use std::path::Path;
+
+/// Name carried by the `Custom` variants of languages, sources and runners
+/// contributed by registered detectors.
+///
+/// Kept behind an alias so serde's derive does not tie `Deserialize` to
+/// `'static` input; custom variants are skipped when deserializing.
+pub type CustomName = &'static str;
+
+/// Classifies a single path discovered while walking the project.
+///
+/// Every registered detector sees every path, so one file can signal several
+/// languages. The language is taken from the signal's source.
+pub trait LanguageDetector {
+    fn detect(&self, path: &Path) -> Option<LanguageDetectionSignal>;
+
+    /// Signals that depend on the file's contents, such as one per tool
+    /// pinned in `.tool-versions`. Defaults to [`detect`](Self::detect).
+    fn detect_with_fs(&self, path: &Path, fs: &dyn FileSystem) -> Vec<LanguageDetectionSignal> {
+        let _ = fs;
+        self.detect(path).into_iter().collect()
+    }
+}
+
+/// Extracts version requirements from a language detection signal.
+pub trait VersionDetector {
+    fn detect(&self, signal: &LanguageDetectionSignal, fs: &dyn FileSystem) -> Vec<VersionInfo>;
+}
+
+/// Recognises a task runner definition file and extracts its commands.
+pub trait TaskRunnerDetector {
+    fn detect(&self, path: &Path, fs: &dyn FileSystem) -> Option<TaskRunnerDetection>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinLanguageDetector;
+
+impl LanguageDetector for BuiltinLanguageDetector {
+    fn detect(&self, path: &Path) -> Option<LanguageDetectionSignal> {
+        LanguageDetectionSignal::try_from(path.to_path_buf()).ok()
+    }
+
+    fn detect_with_fs(&self, path: &Path, fs: &dyn FileSystem) -> Vec<LanguageDetectionSignal> {
+        let mut signals = tool_version_signals(path, fs);
+        signals.extend(self.detect(path));
+        signals
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinVersionDetector;
+
+impl VersionDetector for BuiltinVersionDetector {
+    fn detect(&self, signal: &LanguageDetectionSignal, fs: &dyn FileSystem) -> Vec<VersionInfo> {
+        versions_from_signal(signal, fs).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinTaskRunnerDetector;
+
+impl TaskRunnerDetector for BuiltinTaskRunnerDetector {
+    fn detect(&self, path: &Path, fs: &dyn FileSystem) -> Option<TaskRunnerDetection> {
+        TaskRunnerFile::from_path(path.to_path_buf(), fs).map(|file| file.into_detection(fs))
+    }
+}
+
+/// The detectors consulted by a [`DetectionEngine`](super::DetectionEngine).
+///
+/// `DetectorRegistry::default()` holds the built-in detectors; use
+/// [`DetectorRegistry::empty`] to start from scratch.
+pub struct DetectorRegistry {
+    language_detectors: Vec<Box<dyn LanguageDetector>>,
+    version_detectors: Vec<Box<dyn VersionDetector>>,
+    task_runner_detectors: Vec<Box<dyn TaskRunnerDetector>>,
+    builtin_only: bool,
+}
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register_language_detector(BuiltinLanguageDetector)
+            .register_version_detector(BuiltinVersionDetector)
+            .register_task_runner_detector(BuiltinTaskRunnerDetector);
+        registry.builtin_only = true;
+        registry
+    }
+}
+
+impl DetectorRegistry {
+    pub fn empty() -> Self {
+        Self {
+            language_detectors: Vec::new(),
+            version_detectors: Vec::new(),
+            task_runner_detectors: Vec::new(),
+            builtin_only: false,
+        }
+    }
+
+    /// Whether this is the unmodified default registry, whose results can be
+    /// shared through a [`DetectionCache`](super::DetectionCache).
+    pub fn is_builtin_only(&self) -> bool {
+        self.builtin_only
+    }
+
+    pub fn register_language_detector(
+        &mut self,
+        detector: impl LanguageDetector + 'static,
+    ) -> &mut Self {
+        self.language_detectors.push(Box::new(detector));
+        self.builtin_only = false;
+        self
+    }
+
+    pub fn register_version_detector(
+        &mut self,
+        detector: impl VersionDetector + 'static,
+    ) -> &mut Self {
+        self.version_detectors.push(Box::new(detector));
+        self.builtin_only = false;
+        self
+    }
+
+    pub fn register_task_runner_detector(
+        &mut self,
+        detector: impl TaskRunnerDetector + 'static,
+    ) -> &mut Self {
+        self.task_runner_detectors.push(Box::new(detector));
+        self.builtin_only = false;
+        self
+    }
+
+    pub fn detect_languages(
+        &self,
+        path: &Path,
+        fs: &dyn FileSystem,
+    ) -> Vec<LanguageDetectionSignal> {
+        self.language_detectors
+            .iter()
+            .flat_map(|detector| detector.detect_with_fs(path, fs))
+            .collect()
+    }
+
+    pub fn detect_versions(
+        &self,
+        signal: &LanguageDetectionSignal,
+        fs: &dyn FileSystem,
+    ) -> Vec<VersionInfo> {
+        self.version_detectors
+            .iter()
+            .flat_map(|detector| detector.detect(signal, fs))
+            .collect()
+    }
+
+    pub fn detect_task_runners(
+        &self,
+        path: &Path,
+        fs: &dyn FileSystem,
+    ) -> Vec<TaskRunnerDetection> {
+        self.task_runner_detectors
+            .iter()
+            .filter_map(|detector| detector.detect(path, fs))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{
+        CommandExecutable, Confidence, Language, LanguageDetectionSource, LocalFileSystem,
+        TaskCommand, TaskRunner, TaskRunnerCommands, TaskRunnerSource, VersionSource,
+    };
+    use std::path::PathBuf;
+
+    const ZIG: Language = Language::Custom("zig");
+
+    struct ZigLanguageDetector;
+
+    impl LanguageDetector for ZigLanguageDetector {
+        fn detect(&self, path: &Path) -> Option<LanguageDetectionSignal> {
+            let source = match path.file_name()?.to_str()? {
+                "build.zig" => LanguageDetectionSource::Custom {
+                    language: "zig",
+                    name: "BuildZig",
+                },
+                _ => return None,
+            };
+            Some(LanguageDetectionSignal::Strong {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    }
+
+    struct ZigVersionDetector;
+
+    impl VersionDetector for ZigVersionDetector {
+        fn detect(
+            &self,
+            signal: &LanguageDetectionSignal,
+            fs: &dyn FileSystem,
+        ) -> Vec<VersionInfo> {
+            let LanguageDetectionSignal::Strong { path, .. } = signal else {
+                return vec![];
+            };
+            let Some(dir) = path.parent() else {
+                return vec![];
+            };
+            let version_path = dir.join(".zig-version");
+            let Ok(raw) = fs.read_to_string(&version_path) else {
+                return vec![];
+            };
+            vec![VersionInfo {
+                raw: raw.trim().to_string(),
+                parsed: None,
+                source: VersionSource::Custom("ZigVersionFile"),
+                path: version_path,
+            }]
+        }
+    }
+
+    struct ZigBuildDetector;
+
+    impl TaskRunnerDetector for ZigBuildDetector {
+        fn detect(&self, path: &Path, _fs: &dyn FileSystem) -> Option<TaskRunnerDetection> {
+            if path.file_name()? != "build.zig" {
+                return None;
+            }
+            Some(TaskRunnerDetection {
+                task_runner: TaskRunner::Custom("ZigBuild"),
+                source: TaskRunnerSource::Custom("BuildZig"),
+                path: path.to_path_buf(),
+                commands: TaskRunnerCommands {
+                    test: vec![TaskCommand {
+                        name: "test".to_string(),
+                        executable: CommandExecutable::Direct {
+                            command: "zig build test".to_string(),
+                        },
+                        description: None,
+                        depends_on: vec![],
+                        required_args: vec![],
+                        workdir: None,
+                        confidence: Confidence::High,
+                    }],
+                    ..Default::default()
+                },
+                task_graph: None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_default_registry_uses_builtin_detectors() {
+        let registry = DetectorRegistry::default();
+        let detected = registry.detect_languages(&PathBuf::from("go.mod"), &LocalFileSystem);
+
+        assert_eq!(detected.len(), 1);
+        assert_eq!(Language::from(&detected[0]), Language::Go);
+    }
+
+    #[test]
+    fn test_registering_detector_clears_builtin_only() {
+        let mut registry = DetectorRegistry::default();
+        assert!(registry.is_builtin_only());
+
+        registry.register_language_detector(ZigLanguageDetector);
+        assert!(!registry.is_builtin_only());
+        assert!(!DetectorRegistry::empty().is_builtin_only());
+    }
+
+    #[test]
+    fn test_empty_registry_detects_nothing() {
+        let registry = DetectorRegistry::empty();
+
+        assert!(
+            registry
+                .detect_languages(&PathBuf::from("go.mod"), &LocalFileSystem)
+                .is_empty()
+        );
+        assert!(
+            registry
+                .detect_task_runners(&PathBuf::from("Makefile"), &LocalFileSystem)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_custom_detectors_run_alongside_builtins() {
+        let mut registry = DetectorRegistry::default();
+        registry
+            .register_language_detector(ZigLanguageDetector)
+            .register_version_detector(ZigVersionDetector)
+            .register_task_runner_detector(ZigBuildDetector);
+
+        let detected = registry.detect_languages(&PathBuf::from("build.zig"), &LocalFileSystem);
+        assert_eq!(detected.len(), 1);
+        assert_eq!(Language::from(&detected[0]), ZIG);
+
+        let runners = registry.detect_task_runners(&PathBuf::from("build.zig"), &LocalFileSystem);
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].task_runner, TaskRunner::Custom("ZigBuild"));
+        assert_eq!(runners[0].commands.test.len(), 1);
+    }
+
+    #[test]
+    fn test_language_from_custom_source() {
+        let source = LanguageDetectionSource::Custom {
+            language: "zig",
+            name: "BuildZig",
+        };
+        assert_eq!(Language::from(&source), ZIG);
+        assert_eq!(ZIG.dir_name(), "zig");
+    }
+}
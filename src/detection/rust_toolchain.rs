@@ -0,0 +1,221 @@
+use super::FileSystem;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// A rustup toolchain name, split into the parts rust-overlay can select by.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum RustChannel {
+    Stable,
+    /// A stable release such as `1.78.0` or `1.78`.
+    Version(String),
+    /// `beta`, or `beta-YYYY-MM-DD`.
+    Beta(Option<String>),
+    /// `nightly`, or `nightly-YYYY-MM-DD`.
+    Nightly(Option<String>),
+    /// A name rust-overlay has no attribute for, e.g. one with a host triple.
+    Other(String),
+}
+
+impl RustChannel {
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim();
+        let dated = |prefix: &str| -> Option<Option<String>> {
+            let rest = name.strip_prefix(prefix)?;
+            if rest.is_empty() {
+                return Some(None);
+            }
+            let date = rest.strip_prefix('-')?;
+            is_date(date).then(|| Some(date.to_string()))
+        };
+
+        if name == "stable" {
+            RustChannel::Stable
+        } else if let Some(date) = dated("beta") {
+            RustChannel::Beta(date)
+        } else if let Some(date) = dated("nightly") {
+            RustChannel::Nightly(date)
+        } else if is_release(name) {
+            RustChannel::Version(name.to_string())
+        } else {
+            RustChannel::Other(name.to_string())
+        }
+    }
+}
+
+fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    matches!(parts.as_slice(), [y, m, d] if y.len() == 4 && m.len() == 2 && d.len() == 2)
+        && parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn is_release(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('.').collect();
+    (2..=3).contains(&parts.len())
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// The rustup profile, which decides the base set of components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum RustProfile {
+    Minimal,
+    #[default]
+    Default,
+    Complete,
+}
+
+impl RustProfile {
+    fn parse(name: &str) -> Self {
+        match name {
+            "minimal" => RustProfile::Minimal,
+            "complete" => RustProfile::Complete,
+            _ => RustProfile::Default,
+        }
+    }
+}
+
+/// The contents of a `rust-toolchain.toml`, or of a legacy `rust-toolchain`
+/// file holding either a bare channel name or the same TOML.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RustToolchainSpec {
+    pub channel: Option<String>,
+    pub components: Vec<String>,
+    pub targets: Vec<String>,
+    pub profile: RustProfile,
+    pub path: PathBuf,
+}
+
+impl RustToolchainSpec {
+    pub fn from_path(fs: &dyn FileSystem, path: &Path) -> Option<Self> {
+        let content = fs.read_to_string(path).ok()?;
+        if let Ok(parsed) = toml::from_str::<toml::Value>(&content)
+            && parsed.as_table().is_some_and(|t| !t.is_empty())
+        {
+            return Some(Self::from_toml(&parsed, path));
+        }
+
+        // Legacy `rust-toolchain` files may hold just the channel name.
+        let is_legacy = path.file_name().is_some_and(|f| f == "rust-toolchain");
+        let channel = content.lines().next()?.trim();
+        (is_legacy && !channel.is_empty()).then(|| Self {
+            channel: Some(channel.to_string()),
+            components: vec![],
+            targets: vec![],
+            profile: RustProfile::Default,
+            path: path.to_path_buf(),
+        })
+    }
+
+    fn from_toml(parsed: &toml::Value, path: &Path) -> Self {
+        let table = parsed
+            .get("toolchain")
+            .filter(|t| t.is_table())
+            .unwrap_or(parsed);
+        let strings = |key: &str| -> Vec<String> {
+            table
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        Self {
+            channel: table
+                .get("channel")
+                .or_else(|| parsed.get("channel"))
+                .and_then(|c| c.as_str())
+                .map(str::to_string),
+            components: strings("components"),
+            targets: strings("targets"),
+            profile: table
+                .get("profile")
+                .and_then(|p| p.as_str())
+                .map(RustProfile::parse)
+                .unwrap_or_default(),
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn rust_channel(&self) -> Option<RustChannel> {
+        self.channel.as_deref().map(RustChannel::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_channels() {
+        assert_eq!(RustChannel::parse("stable"), RustChannel::Stable);
+        assert_eq!(
+            RustChannel::parse("1.78.0"),
+            RustChannel::Version("1.78.0".to_string())
+        );
+        assert_eq!(
+            RustChannel::parse("1.78"),
+            RustChannel::Version("1.78".to_string())
+        );
+        assert_eq!(RustChannel::parse("beta"), RustChannel::Beta(None));
+        assert_eq!(
+            RustChannel::parse("nightly-2024-05-01"),
+            RustChannel::Nightly(Some("2024-05-01".to_string()))
+        );
+        assert_eq!(
+            RustChannel::parse("nightly-2024-05-01-x86_64-unknown-linux-gnu"),
+            RustChannel::Other("nightly-2024-05-01-x86_64-unknown-linux-gnu".to_string())
+        );
+    }
+
+    #[test]
+    fn test_full_toolchain_toml() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            "rust-toolchain.toml",
+            "[toolchain]\nchannel = \"nightly-2024-05-01\"\ncomponents = [\"clippy\", \"miri\"]\ntargets = [\"wasm32-unknown-unknown\"]\nprofile = \"minimal\"\n",
+        );
+
+        let spec = RustToolchainSpec::from_path(&LocalFileSystem, &path).unwrap();
+        assert_eq!(
+            spec.rust_channel(),
+            Some(RustChannel::Nightly(Some("2024-05-01".to_string())))
+        );
+        assert_eq!(spec.components, ["clippy", "miri"]);
+        assert_eq!(spec.targets, ["wasm32-unknown-unknown"]);
+        assert_eq!(spec.profile, RustProfile::Minimal);
+    }
+
+    #[test]
+    fn test_legacy_file_forms() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "rust-toolchain", "beta\n");
+
+        let spec = RustToolchainSpec::from_path(&LocalFileSystem, &path).unwrap();
+        assert_eq!(spec.rust_channel(), Some(RustChannel::Beta(None)));
+
+        fs::write(
+            &path,
+            "[toolchain]\nchannel = \"1.75.0\"\ncomponents = [\"rustfmt\"]\n",
+        )
+        .unwrap();
+        let spec = RustToolchainSpec::from_path(&LocalFileSystem, &path).unwrap();
+        assert_eq!(spec.channel.as_deref(), Some("1.75.0"));
+        assert_eq!(spec.components, ["rustfmt"]);
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// A package of a monorepo, as its task runner sees it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphPackage {
+    pub name: String,
+    /// The package's directory, relative to the runner file's.
+    pub path: PathBuf,
+    /// Other packages of the graph this one depends on.
+    pub dependencies: Vec<String>,
+}
+
+/// One task of one package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphTask {
+    /// `package#task` for Turborepo or `project:target` for Nx, the form the
+    /// runner itself accepts.
+    pub id: String,
+    pub package: String,
+    pub task: String,
+    /// Ids of the tasks that run first.
+    pub depends_on: Vec<String>,
+    /// Globs of the files the task produces, which the runner caches.
+    pub outputs: Vec<String>,
+    /// Long-running tasks, such as dev servers, that never finish.
+    pub persistent: bool,
+    /// The shell the task runs, when the runner delegates to a script.
+    pub script: Option<String>,
+    /// The Nx executor behind the target, such as `@nx/jest:jest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executor: Option<String>,
+}
+
+/// The tasks of a monorepo runner resolved per package, with the edges the
+/// runner follows between them. Tasks are in topological order: every task
+/// comes after the tasks it depends on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskGraph {
+    pub packages: Vec<GraphPackage>,
+    pub tasks: Vec<GraphTask>,
+}
+
+impl TaskGraph {
+    /// Builds a graph from tasks in any order. Dependencies on unknown tasks
+    /// are dropped; tasks caught in a cycle keep their relative order, after
+    /// everything else.
+    pub fn new(packages: Vec<GraphPackage>, mut tasks: Vec<GraphTask>) -> Self {
+        let ids: HashSet<String> = tasks.iter().map(|t| t.id.clone()).collect();
+        for task in &mut tasks {
+            task.depends_on
+                .retain(|dep| ids.contains(dep) && *dep != task.id);
+            task.depends_on.dedup();
+        }
+
+        let mut placed: HashSet<String> = HashSet::new();
+        let mut ordered: Vec<GraphTask> = Vec::with_capacity(tasks.len());
+        while !tasks.is_empty() {
+            let (ready, waiting): (Vec<GraphTask>, Vec<GraphTask>) = tasks
+                .into_iter()
+                .partition(|task| task.depends_on.iter().all(|dep| placed.contains(dep)));
+            if ready.is_empty() {
+                ordered.extend(waiting);
+                break;
+            }
+            placed.extend(ready.iter().map(|task| task.id.clone()));
+            ordered.extend(ready);
+            tasks = waiting;
+        }
+
+        Self {
+            packages,
+            tasks: ordered,
+        }
+    }
+
+    pub fn task(&self, id: &str) -> Option<&GraphTask> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, depends_on: &[&str]) -> GraphTask {
+        let (package, name) = id.split_once('#').unwrap();
+        GraphTask {
+            id: id.to_string(),
+            package: package.to_string(),
+            task: name.to_string(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            outputs: vec![],
+            persistent: false,
+            script: None,
+            executor: None,
+        }
+    }
+
+    fn ids(graph: &TaskGraph) -> Vec<&str> {
+        graph.tasks.iter().map(|t| t.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_tasks_in_topological_order() {
+        let graph = TaskGraph::new(
+            vec![],
+            vec![
+                task("web#test", &["web#build"]),
+                task("web#build", &["ui#build"]),
+                task("ui#build", &[]),
+                task("ui#test", &["ui#build", "missing#build"]),
+            ],
+        );
+        assert_eq!(
+            ids(&graph),
+            ["ui#build", "web#build", "ui#test", "web#test"]
+        );
+        assert_eq!(graph.task("ui#test").unwrap().depends_on, ["ui#build"]);
+    }
+
+    #[test]
+    fn test_cycles_go_last() {
+        let graph = TaskGraph::new(
+            vec![],
+            vec![
+                task("a#build", &["b#build"]),
+                task("b#build", &["a#build"]),
+                task("c#build", &[]),
+            ],
+        );
+        assert_eq!(ids(&graph), ["c#build", "a#build", "b#build"]);
+    }
+}
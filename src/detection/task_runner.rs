@@ -1,20 +1,31 @@
+use super::{
+    CargoManifest, CustomName, DocsRsFeatures, FileSystem, Justfile, LocalFileSystem, Makefile,
+    TASKFILE_NAMES, TaskGraph, Taskfile, TurboJson, expand_member_glob, in_cargo_workspace,
+    included_by_parent_taskfile, is_package_turbo_json, nx_task_graph, shadowed_taskfile,
+    turbo_task_graph,
+};
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
-use std::{
-    fs,
-    path::{Path, PathBuf},
-};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CommandCategory {
     Test,
     Build,
+    Lint,
+    Format,
+    Typecheck,
+    Bench,
+    /// Long-running commands such as dev servers and watchers.
+    Run,
     Other,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum CommandExecutable {
     Direct {
@@ -24,10 +35,22 @@ pub enum CommandExecutable {
         script_name: String,
         script_body: String,
         package_json_path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        workspace: Option<WorkspaceMember>,
     },
 }
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+/// A package that belongs to a workspace declared in an ancestor directory,
+/// whose commands run from the workspace root.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The directory of the workspace root.
+    pub root: PathBuf,
+    /// The member's package name.
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "PascalCase")]
 pub enum TaskRunner {
     // Universal
@@ -48,21 +71,29 @@ pub enum TaskRunner {
     Tox,
     Nox,
     Invoke,
+    Hatch,
+    Pixi,
 
     // Rust
     Cargo,
 
     // Go
     GoTask,
+
+    // Registered at runtime
+    #[serde(skip_deserializing)]
+    Custom(CustomName),
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TaskRunnerSource {
     // Universal
     Makefile,
     Justfile,
     TaskfileYml,
     TaskfileYaml,
+    TaskfileDistYml,
+    TaskfileDistYaml,
 
     // JavaScript
     PackageJson,
@@ -90,26 +121,69 @@ pub enum TaskRunnerSource {
     Noxfile,
     TasksPy,
     InvokeYaml,
+    HatchToml,
+    /// `[tool.hatch.envs]` scripts in a `pyproject.toml`.
+    PyprojectHatch,
+    PixiToml,
+    /// `[tool.pixi.tasks]` in a `pyproject.toml`.
+    PyprojectPixi,
 
     // Rust
     CargoToml,
 
     // Go
     GoMod,
+    GoWork,
+
+    // Registered at runtime
+    #[serde(skip_deserializing)]
+    Custom(CustomName),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskCommand {
     pub name: String,
     pub executable: CommandExecutable,
     pub description: Option<String>,
+    /// Names of commands from the same runner that run first, such as a
+    /// make target's prerequisites.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Parameters without a default, which must be passed before the command
+    /// can run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_args: Vec<String>,
+    /// Where the runner itself executes the command's steps, relative to the
+    /// runner file, such as a Taskfile task's `dir:`. The command is still
+    /// invoked from the runner file's directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workdir: Option<PathBuf>,
+    /// How sure the command's category is, from what its name and body say.
+    pub confidence: Confidence,
 }
 
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TaskRunnerCommands {
     pub test: Vec<TaskCommand>,
     pub build: Vec<TaskCommand>,
+    #[serde(default)]
+    pub lint: Vec<TaskCommand>,
+    /// Formatters, in check mode wherever the command is ours to choose.
+    #[serde(default)]
+    pub format: Vec<TaskCommand>,
+    #[serde(default)]
+    pub typecheck: Vec<TaskCommand>,
+    #[serde(default)]
+    pub bench: Vec<TaskCommand>,
+    /// Dev servers, watchers and binaries: useful in a shell, never checks.
+    #[serde(default)]
+    pub run: Vec<TaskCommand>,
     pub other: Vec<TaskCommand>,
+    /// Lifecycle scripts a package manager runs after installing
+    /// dependencies, such as `prepare` and `postinstall`, in the order it runs
+    /// them. Not a category of their own: [`Self::all`] leaves them out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub setup: Vec<TaskCommand>,
 }
 
 impl TaskRunnerCommands {
@@ -117,9 +191,34 @@ impl TaskRunnerCommands {
         match category {
             CommandCategory::Test => self.test.push(cmd),
             CommandCategory::Build => self.build.push(cmd),
+            CommandCategory::Lint => self.lint.push(cmd),
+            CommandCategory::Format => self.format.push(cmd),
+            CommandCategory::Typecheck => self.typecheck.push(cmd),
+            CommandCategory::Bench => self.bench.push(cmd),
+            CommandCategory::Run => self.run.push(cmd),
             CommandCategory::Other => self.other.push(cmd),
         }
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.all().next().is_none()
+    }
+
+    /// Every command, in category order.
+    pub fn all(&self) -> impl Iterator<Item = &TaskCommand> {
+        [
+            &self.test,
+            &self.build,
+            &self.lint,
+            &self.format,
+            &self.typecheck,
+            &self.bench,
+            &self.run,
+            &self.other,
+        ]
+        .into_iter()
+        .flatten()
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -128,14 +227,19 @@ pub struct TaskRunnerFile {
     pub source: TaskRunnerSource,
     pub path: PathBuf,
     pub content: Option<String>,
+    pub workspace: Option<WorkspaceMember>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TaskRunnerDetection {
     pub task_runner: TaskRunner,
     pub source: TaskRunnerSource,
     pub path: PathBuf,
     pub commands: TaskRunnerCommands,
+    /// The runner's tasks per workspace package and the order they run in,
+    /// for monorepo runners such as Turborepo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_graph: Option<TaskGraph>,
 }
 
 impl From<&TaskRunnerSource> for TaskRunner {
@@ -143,7 +247,10 @@ impl From<&TaskRunnerSource> for TaskRunner {
         match source {
             TaskRunnerSource::Makefile => TaskRunner::Make,
             TaskRunnerSource::Justfile => TaskRunner::Just,
-            TaskRunnerSource::TaskfileYml | TaskRunnerSource::TaskfileYaml => TaskRunner::Task,
+            TaskRunnerSource::TaskfileYml
+            | TaskRunnerSource::TaskfileYaml
+            | TaskRunnerSource::TaskfileDistYml
+            | TaskRunnerSource::TaskfileDistYaml => TaskRunner::Task,
             TaskRunnerSource::PackageJson => TaskRunner::NpmScripts,
             TaskRunnerSource::ViteConfigJs
             | TaskRunnerSource::ViteConfigTs
@@ -165,28 +272,37 @@ impl From<&TaskRunnerSource> for TaskRunner {
             TaskRunnerSource::ToxIni => TaskRunner::Tox,
             TaskRunnerSource::NoxPy | TaskRunnerSource::Noxfile => TaskRunner::Nox,
             TaskRunnerSource::TasksPy | TaskRunnerSource::InvokeYaml => TaskRunner::Invoke,
+            TaskRunnerSource::HatchToml | TaskRunnerSource::PyprojectHatch => TaskRunner::Hatch,
+            TaskRunnerSource::PixiToml | TaskRunnerSource::PyprojectPixi => TaskRunner::Pixi,
             TaskRunnerSource::CargoToml => TaskRunner::Cargo,
-            TaskRunnerSource::GoMod => TaskRunner::GoTask,
+            TaskRunnerSource::GoMod | TaskRunnerSource::GoWork => TaskRunner::GoTask,
+            TaskRunnerSource::Custom(name) => TaskRunner::Custom(name),
         }
     }
 }
 
-impl TryFrom<PathBuf> for TaskRunnerFile {
-    type Error = ();
-
-    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
-        if !path.is_file() {
-            return Err(());
+impl TaskRunnerFile {
+    pub fn from_path(path: PathBuf, fs: &dyn FileSystem) -> Option<Self> {
+        if !fs.is_file(&path) {
+            return None;
         }
 
-        let filename = path.file_name().ok_or(())?.to_str().ok_or(())?;
+        let filename = path.file_name()?.to_str()?;
 
         let source = match filename {
             // Universal
             "Makefile" | "makefile" | "GNUmakefile" => TaskRunnerSource::Makefile,
-            "justfile" | "Justfile" => TaskRunnerSource::Justfile,
-            "Taskfile.yml" => TaskRunnerSource::TaskfileYml,
-            "Taskfile.yaml" => TaskRunnerSource::TaskfileYaml,
+            "justfile" | "Justfile" | ".justfile" => TaskRunnerSource::Justfile,
+            // Only the Taskfile Task would pick, and not one another includes.
+            name if TASKFILE_NAMES.contains(&name)
+                && (shadowed_taskfile(&path, fs) || included_by_parent_taskfile(&path, fs)) =>
+            {
+                return None;
+            }
+            "Taskfile.yml" | "taskfile.yml" => TaskRunnerSource::TaskfileYml,
+            "Taskfile.yaml" | "taskfile.yaml" => TaskRunnerSource::TaskfileYaml,
+            "Taskfile.dist.yml" | "taskfile.dist.yml" => TaskRunnerSource::TaskfileDistYml,
+            "Taskfile.dist.yaml" | "taskfile.dist.yaml" => TaskRunnerSource::TaskfileDistYaml,
 
             // JavaScript/TypeScript
             "package.json" => TaskRunnerSource::PackageJson,
@@ -205,6 +321,8 @@ impl TryFrom<PathBuf> for TaskRunnerFile {
             "rollup.config.mjs" => TaskRunnerSource::RollupConfigMjs,
             "rollup.config.cjs" => TaskRunnerSource::RollupConfigCjs,
             "rollup.config.ts" | "rollup.config.mts" => TaskRunnerSource::RollupConfigTs,
+            // A package's turbo.json only refines the root's.
+            "turbo.json" if is_package_turbo_json(&path, fs) => return None,
             "turbo.json" => TaskRunnerSource::TurboJson,
             "nx.json" => TaskRunnerSource::NxJson,
 
@@ -214,42 +332,92 @@ impl TryFrom<PathBuf> for TaskRunnerFile {
             "noxfile.py" => TaskRunnerSource::Noxfile,
             "tasks.py" => TaskRunnerSource::TasksPy,
             "invoke.yaml" | "invoke.yml" => TaskRunnerSource::InvokeYaml,
+            "hatch.toml" => TaskRunnerSource::HatchToml,
+            "pixi.toml" => TaskRunnerSource::PixiToml,
+            "pyproject.toml" => pyproject_task_source(&path, fs)?,
 
             // Rust
+            // Members of a workspace are tested through its root manifest.
+            "Cargo.toml" if in_cargo_workspace(&path, fs) => return None,
             "Cargo.toml" => TaskRunnerSource::CargoToml,
 
             // Go
+            // Modules of a workspace are built through its go.work instead.
+            "go.mod" if in_go_workspace(&path, fs) => return None,
             "go.mod" => TaskRunnerSource::GoMod,
+            "go.work" => TaskRunnerSource::GoWork,
 
-            _ => return Err(()),
+            _ => return None,
         };
 
-        let content = fs::read_to_string(&path).ok();
+        let content = fs.read_to_string(&path).ok();
 
         let task_runner = TaskRunner::from(&source);
 
-        Ok(TaskRunnerFile {
+        let workspace = match (&source, &content) {
+            (TaskRunnerSource::PackageJson, Some(content)) => {
+                js_workspace_member(&path, content, fs)
+            }
+            _ => None,
+        };
+
+        Some(TaskRunnerFile {
             task_runner,
             source,
             path,
             content,
+            workspace,
         })
     }
-}
 
-impl TaskRunnerFile {
-    fn extract_commands(&self) -> TaskRunnerCommands {
+    pub fn into_detection(self, fs: &dyn FileSystem) -> TaskRunnerDetection {
+        let task_graph = self.task_graph(fs);
+        let commands = match &task_graph {
+            Some(graph) if !graph.tasks.is_empty() => {
+                extract_task_graph_commands(graph, self.task_runner)
+            }
+            _ => self.extract_commands(fs),
+        };
+
+        TaskRunnerDetection {
+            task_runner: self.task_runner,
+            source: self.source,
+            path: self.path,
+            commands,
+            task_graph,
+        }
+    }
+
+    /// Tasks resolved per workspace package, for runners that model them.
+    fn task_graph(&self, fs: &dyn FileSystem) -> Option<TaskGraph> {
+        match self.source {
+            TaskRunnerSource::TurboJson => turbo_task_graph(&self.path, fs),
+            TaskRunnerSource::NxJson => nx_task_graph(&self.path, fs),
+            _ => None,
+        }
+    }
+
+    fn extract_commands(&self, fs: &dyn FileSystem) -> TaskRunnerCommands {
         let Some(content) = &self.content else {
             return TaskRunnerCommands::default();
         };
 
         match self.source {
-            TaskRunnerSource::Makefile => extract_makefile_commands(content),
-            TaskRunnerSource::Justfile => extract_justfile_commands(content),
-            TaskRunnerSource::PackageJson => extract_npm_commands(content, &self.path),
-            TaskRunnerSource::TaskfileYml | TaskRunnerSource::TaskfileYaml => {
-                extract_taskfile_commands(content)
+            TaskRunnerSource::Makefile => Makefile::from_path(fs, &self.path)
+                .map(|makefile| extract_makefile_commands(&makefile))
+                .unwrap_or_default(),
+            TaskRunnerSource::Justfile => Justfile::from_path(fs, &self.path)
+                .map(|justfile| extract_justfile_commands(&justfile))
+                .unwrap_or_default(),
+            TaskRunnerSource::PackageJson => {
+                extract_npm_commands(content, &self.path, self.workspace.as_ref())
             }
+            TaskRunnerSource::TaskfileYml
+            | TaskRunnerSource::TaskfileYaml
+            | TaskRunnerSource::TaskfileDistYml
+            | TaskRunnerSource::TaskfileDistYaml => Taskfile::from_path(fs, &self.path)
+                .map(|taskfile| extract_taskfile_commands(&taskfile))
+                .unwrap_or_default(),
             TaskRunnerSource::ViteConfigJs
             | TaskRunnerSource::ViteConfigTs
             | TaskRunnerSource::ViteConfigMjs => get_vite_commands(),
@@ -271,80 +439,443 @@ impl TaskRunnerFile {
             TaskRunnerSource::NoxPy | TaskRunnerSource::Noxfile => extract_nox_commands(content),
             TaskRunnerSource::TasksPy => extract_invoke_commands(content),
             TaskRunnerSource::InvokeYaml => extract_invoke_yaml_commands(content),
-            TaskRunnerSource::CargoToml => get_cargo_commands(),
+            TaskRunnerSource::HatchToml => extract_hatch_commands(content, &["envs"]),
+            TaskRunnerSource::PyprojectHatch => {
+                extract_hatch_commands(content, &["tool", "hatch", "envs"])
+            }
+            TaskRunnerSource::PixiToml => extract_pixi_commands(content, &[]),
+            TaskRunnerSource::PyprojectPixi => extract_pixi_commands(content, &["tool", "pixi"]),
+            TaskRunnerSource::CargoToml => CargoManifest::from_path(fs, &self.path)
+                .map(|manifest| extract_cargo_commands(&manifest, fs))
+                .unwrap_or_default(),
             TaskRunnerSource::GoMod => get_go_commands(),
+            TaskRunnerSource::GoWork => extract_go_work_commands(content),
+            TaskRunnerSource::Custom(_) => TaskRunnerCommands::default(),
         }
     }
 }
 
-const TEST_KEYWORDS: &[&str] = &["test", "spec", "jest", "mocha", "pytest"];
+const BENCH_KEYWORDS: &[&str] = &["bench", "benchmark", "benchmarks"];
+const TEST_KEYWORDS: &[&str] = &[
+    "test", "tests", "spec", "specs", "jest", "mocha", "pytest", "vitest", "unittest",
+];
+const TYPECHECK_KEYWORDS: &[&str] = &[
+    "typecheck",
+    "type-check",
+    "check-types",
+    "tsc",
+    "mypy",
+    "pyright",
+];
+const FORMAT_KEYWORDS: &[&str] = &["fmt", "format", "prettier", "black"];
+const LINT_KEYWORDS: &[&str] = &[
+    "lint", "eslint", "clippy", "vet", "ruff", "flake8", "pylint",
+];
 const BUILD_KEYWORDS: &[&str] = &["build", "compile", "bundle", "package", "dist"];
+const RUN_KEYWORDS: &[&str] = &["dev", "start", "serve", "watch", "preview"];
+
+// Checked in order, so `test:lint` is a test and `build:watch` a build.
+const PRIORITY: [CommandCategory; 8] = [
+    CommandCategory::Bench,
+    CommandCategory::Test,
+    CommandCategory::Typecheck,
+    CommandCategory::Format,
+    CommandCategory::Lint,
+    CommandCategory::Build,
+    CommandCategory::Run,
+    CommandCategory::Other,
+];
+
+impl CommandCategory {
+    /// Words in a command's name that give its category away.
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            CommandCategory::Test => TEST_KEYWORDS,
+            CommandCategory::Build => BUILD_KEYWORDS,
+            CommandCategory::Lint => LINT_KEYWORDS,
+            CommandCategory::Format => FORMAT_KEYWORDS,
+            CommandCategory::Typecheck => TYPECHECK_KEYWORDS,
+            CommandCategory::Bench => BENCH_KEYWORDS,
+            CommandCategory::Run => RUN_KEYWORDS,
+            CommandCategory::Other => &[],
+        }
+    }
+}
+
+/// How sure autonix is that a command belongs in its category.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// Defined by the runner itself, or the command's name and body agree.
+    High,
+    /// Only the name or only the body says what the command does.
+    Medium,
+    /// Nothing says what the command does, or the body contradicts the name.
+    Low,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Classification {
+    category: CommandCategory,
+    confidence: Confidence,
+}
+
+/// Splits a name into lowercase words at punctuation and camelCase humps, so
+/// `unitTest` and `test:unit` both contain `test` but `latest` doesn't.
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut after_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() || (c.is_uppercase() && after_lower) {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            after_lower = false;
+            if !c.is_alphanumeric() {
+                continue;
+            }
+        }
+        current.extend(c.to_lowercase());
+        after_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
 
 fn classify_command(name: &str) -> CommandCategory {
-    let name_lower = name.to_lowercase();
+    let words = name_words(name);
+    let has_keyword = |keyword: &&str| {
+        let parts: Vec<&str> = keyword.split('-').collect();
+        words
+            .windows(parts.len())
+            .any(|window| window.iter().zip(&parts).all(|(w, p)| w == p))
+    };
 
-    if TEST_KEYWORDS.iter().any(|&kw| name_lower.contains(kw)) {
-        CommandCategory::Test
-    } else if BUILD_KEYWORDS.iter().any(|&kw| name_lower.contains(kw)) {
-        CommandCategory::Build
+    PRIORITY
+        .into_iter()
+        .find(|category| category.keywords().iter().any(has_keyword))
+        .unwrap_or(CommandCategory::Other)
+}
+
+/// Classifies a command by its name and, when the runner exposes it, the
+/// shell it runs.
+fn classify(name: &str, body: Option<&str>) -> Classification {
+    classify_with(classify_command(name), body)
+}
+
+/// Weighs the category a name suggests against the tools its body invokes;
+/// the body wins a disagreement, since it's what actually runs.
+fn classify_with(by_name: CommandCategory, body: Option<&str>) -> Classification {
+    weigh(by_name, body.map(classify_body).unwrap_or_default(), body)
+}
+
+/// Weighs the category a name suggests against the categories of what the
+/// command runs, most telling first.
+fn weigh(
+    by_name: CommandCategory,
+    signals: Vec<CommandCategory>,
+    body: Option<&str>,
+) -> Classification {
+    let (category, confidence) = if by_name != CommandCategory::Other && signals.contains(&by_name)
+    {
+        (by_name, Confidence::High)
+    } else if let Some(&category) = signals.first() {
+        match by_name {
+            CommandCategory::Other => (category, Confidence::Medium),
+            _ => (category, Confidence::Low),
+        }
     } else {
-        CommandCategory::Other
+        match by_name {
+            // A formatter that isn't run in check mode always passes.
+            CommandCategory::Format if body.is_some_and(|b| !has_format_check_arg(b)) => {
+                (CommandCategory::Other, Confidence::Low)
+            }
+            CommandCategory::Other => (CommandCategory::Other, Confidence::Low),
+            category => (category, Confidence::Medium),
+        }
+    };
+
+    Classification {
+        category,
+        confidence,
     }
 }
 
-fn extract_makefile_commands(content: &str) -> TaskRunnerCommands {
-    let mut commands = TaskRunnerCommands::default();
+/// The category of an Nx executor, such as `@nx/jest:jest`, by the part
+/// after the colon. `None` for executors that run arbitrary commands.
+fn classify_executor(executor: &str) -> Option<CommandCategory> {
+    let name = executor.rsplit(':').next().unwrap_or(executor);
+    match name {
+        "jest" | "vitest" | "test" | "karma" => Some(CommandCategory::Test),
+        "eslint" | "lint" => Some(CommandCategory::Lint),
+        "typecheck" => Some(CommandCategory::Typecheck),
+        "build" | "tsc" | "swc" | "rollup" | "webpack" | "esbuild" | "rspack" | "browser"
+        | "application" | "package" | "browser-esbuild" => Some(CommandCategory::Build),
+        "dev-server" | "serve" | "preview-server" | "server" | "file-server" | "node" | "dev"
+        | "start" | "preview" => Some(CommandCategory::Run),
+        _ => None,
+    }
+}
+
+/// Classifies a target whose executor may say more than its name.
+fn classify_target(name: &str, executor: Option<&str>, body: Option<&str>) -> Classification {
+    match executor.and_then(classify_executor) {
+        Some(category) => weigh(classify_command(name), vec![category], body),
+        None => classify(name, body),
+    }
+}
 
-    let target_re = Regex::new(r"^([a-zA-Z0-9_-]+)\s*:").unwrap();
+const FORMAT_CHECK_ARGS: &[&str] = &["--check", "check", "--list-different", "-l", "--diff"];
 
-    for line in content.lines() {
-        if let Some(caps) = target_re.captures(line) {
-            let target = caps.get(1).unwrap().as_str();
+fn has_format_check_arg(body: &str) -> bool {
+    body.split_whitespace()
+        .any(|arg| FORMAT_CHECK_ARGS.contains(&arg))
+}
 
-            if target.starts_with('.') {
-                continue;
+/// Prefixes that run the tool named after them, each followed by that many
+/// words.
+const COMMAND_WRAPPERS: &[&[&str]] = &[
+    &["npx"],
+    &["pnpx"],
+    &["bunx"],
+    &["pnpm", "exec"],
+    &["pnpm", "dlx"],
+    &["yarn", "exec"],
+    &["yarn", "dlx"],
+    &["npm", "exec"],
+    &["uv", "run"],
+    &["uvx"],
+    &["poetry", "run"],
+    &["pipenv", "run"],
+    &["pdm", "run"],
+    &["rye", "run"],
+    &["hatch", "run"],
+    &["python", "-m"],
+    &["python3", "-m"],
+    &["cross-env"],
+    &["env"],
+    &["time"],
+    &["exec"],
+];
+
+// ` .. ` stands for any arguments in between; every pattern has to end on a
+// word boundary. Checked in order, first match wins.
+const TOOL_PATTERNS: &[(&str, CommandCategory)] = &[
+    (r"\S+ .. --watch(?:=\S*)?", CommandCategory::Run),
+    (
+        r"cargo bench|go test .. -bench(?:=\S*)?|vitest bench|hyperfine",
+        CommandCategory::Bench,
+    ),
+    (
+        r"webpack (?:serve|watch)|webpack-dev-server|vite (?:dev|serve|preview)",
+        CommandCategory::Run,
+    ),
+    (
+        r"pytest|py\.test|jest|vitest|mocha|ava|unittest|(?:go|cargo|deno|bun|zig build|mix|dotnet) test|cargo nextest|node --test|playwright test|cypress run|phpunit|rspec|ctest",
+        CommandCategory::Test,
+    ),
+    (
+        r"tsc .. --noemit|vue-tsc|svelte-check|mypy|pyright|basedpyright",
+        CommandCategory::Typecheck,
+    ),
+    (
+        r#"prettier .. (?:--check|-c|--list-different|-l)|(?:cargo fmt|rustfmt|black|ruff format|nixfmt|alejandra) .. --check|isort .. --check(?:-only)?|(?:test|\[) -z "\$\((?:gofmt|goimports) .. -l|dprint check|clang-format .. --dry-run"#,
+        CommandCategory::Format,
+    ),
+    // Formatters in write mode: they can't fail, so aren't checks. Neither
+    // is a bare `gofmt -l`, which lists unformatted files but exits zero.
+    (
+        r"prettier|cargo fmt|rustfmt|black|ruff format|gofmt|goimports|dprint fmt|nixfmt|alejandra|isort|clang-format",
+        CommandCategory::Other,
+    ),
+    (
+        r"eslint|oxlint|tslint|stylelint|cargo clippy|go vet|golangci-lint|staticcheck|ruff(?: check)?|flake8|pylint|biome (?:lint|check)|shellcheck|hadolint|markdownlint|statix|deadnix",
+        CommandCategory::Lint,
+    ),
+    (
+        r"(?:go|cargo|vite|next|nuxt|astro|docker|nix|zig|dotnet|gradle|gradlew|uv|poetry|hatch|maturin|parcel) build|cmake --build|mvn (?:package|install)|webpack|rspack|rollup|esbuild|tsup|tsc|babel|swc",
+        CommandCategory::Build,
+    ),
+    (
+        r"vite|next (?:dev|start)|(?:nuxt|astro) dev|nodemon|tsx watch|uvicorn|gunicorn|flask run|cargo (?:run|watch)|go run|http-server|live-server|docker(?:-| )compose up|python manage\.py runserver|mkdocs serve|hugo server|jekyll serve",
+        CommandCategory::Run,
+    ),
+];
+
+fn tool_patterns() -> &'static [(Regex, CommandCategory)] {
+    static PATTERNS: OnceLock<Vec<(Regex, CommandCategory)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        TOOL_PATTERNS
+            .iter()
+            .map(|(pattern, category)| {
+                let pattern = pattern.replace(" .. ", r"(?:\s.*)?\s");
+                let regex = Regex::new(&format!(r"^(?:{pattern})(?:\s|$)")).unwrap();
+                (regex, *category)
+            })
+            .collect()
+    })
+}
+
+/// A script or task run through another runner, such as `npm run test` or
+/// `make lint`; its name is classified instead.
+fn delegation_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(?:(?:npm|pnpm|yarn|bun) (?:run )?|make |just |task )([\w:.-]+)").unwrap()
+    })
+}
+
+/// The categories of the tools a shell body invokes, in [`PRIORITY`] order.
+fn classify_body(body: &str) -> Vec<CommandCategory> {
+    let found: Vec<CommandCategory> = shell_segments(body)
+        .filter_map(|segment| {
+            let invocation = invocation(segment);
+            if let Some((_, category)) = tool_patterns()
+                .iter()
+                .find(|(regex, _)| regex.is_match(&invocation))
+            {
+                return Some(*category);
             }
+            let script = delegation_pattern().captures(&invocation)?.get(1)?;
+            Some(classify_command(script.as_str())).filter(|c| *c != CommandCategory::Other)
+        })
+        .collect();
 
-            let cmd = TaskCommand {
-                name: target.to_string(),
-                executable: CommandExecutable::Direct {
-                    command: format!("make {}", target),
-                },
-                description: None,
-            };
+    PRIORITY
+        .into_iter()
+        .filter(|category| found.contains(category))
+        .collect()
+}
 
-            commands.add_command(cmd, classify_command(target));
+/// The commands of a shell body, split at `&&`, `||`, `;`, pipes and
+/// newlines.
+fn shell_segments(body: &str) -> impl Iterator<Item = &str> {
+    static SEPARATOR: OnceLock<Regex> = OnceLock::new();
+    SEPARATOR
+        .get_or_init(|| Regex::new(r"&&|\|\||[;|&\n]").unwrap())
+        .split(body)
+}
+
+/// A shell segment reduced to the tool it runs: lowercased, without make's
+/// `@`/`-`/`+` prefixes, environment assignments or wrappers like `npx`.
+fn invocation(segment: &str) -> String {
+    let segment = segment
+        .trim()
+        .trim_start_matches(['@', '-', '+'])
+        .to_lowercase();
+    let mut words: Vec<&str> = segment.split_whitespace().collect();
+
+    loop {
+        if let Some(first) = words.first_mut() {
+            *first = first
+                .trim_start_matches("./")
+                .trim_start_matches("node_modules/.bin/");
+        }
+        if words
+            .first()
+            .is_some_and(|w| w.contains('=') && !w.starts_with('-'))
+        {
+            words.remove(0);
+        } else if let Some(wrapper) = COMMAND_WRAPPERS.iter().find(|w| words.starts_with(w)) {
+            words.drain(..wrapper.len());
+            while words.first().is_some_and(|w| w.starts_with('-')) {
+                words.remove(0);
+            }
+        } else {
+            break;
         }
     }
 
-    commands
+    words.join(" ")
 }
 
-fn extract_justfile_commands(content: &str) -> TaskRunnerCommands {
+fn extract_makefile_commands(makefile: &Makefile) -> TaskRunnerCommands {
     let mut commands = TaskRunnerCommands::default();
 
-    let recipe_re = Regex::new(r"^([a-zA-Z0-9_-]+)\s*:").unwrap();
+    for target in makefile.tasks() {
+        let recipe = target.recipe.join("\n");
+        let classification = classify(
+            &target.name,
+            Some(recipe.as_str()).filter(|r| !r.is_empty()),
+        );
+        let cmd = TaskCommand {
+            name: target.name.clone(),
+            executable: CommandExecutable::Direct {
+                command: format!("make {}", target.name),
+            },
+            description: target.description.clone(),
+            // File prerequisites are make's business; only other tasks matter.
+            depends_on: target
+                .prerequisites
+                .iter()
+                .filter(|p| makefile.tasks().any(|t| &t.name == *p))
+                .cloned()
+                .collect(),
+            required_args: vec![],
+            workdir: None,
+            confidence: classification.confidence,
+        };
 
-    for line in content.lines() {
-        if let Some(caps) = recipe_re.captures(line) {
-            let recipe = caps.get(1).unwrap().as_str();
+        commands.add_command(cmd, classification.category);
+    }
 
-            let cmd = TaskCommand {
-                name: recipe.to_string(),
-                executable: CommandExecutable::Direct {
-                    command: format!("just {}", recipe),
-                },
-                description: None,
-            };
+    commands
+}
 
-            commands.add_command(cmd, classify_command(recipe));
-        }
+fn extract_justfile_commands(justfile: &Justfile) -> TaskRunnerCommands {
+    let mut commands = TaskRunnerCommands::default();
+
+    for recipe in justfile.public_recipes() {
+        // A `[group('test')]` attribute says more than the recipe's name.
+        let by_name = recipe
+            .groups
+            .iter()
+            .map(|group| classify_command(group))
+            .find(|category| *category != CommandCategory::Other)
+            .unwrap_or_else(|| classify_command(recipe.name.rsplit("::").next().unwrap_or("")));
+        let body = recipe.body.join("\n");
+        let classification = classify_with(by_name, Some(body.as_str()).filter(|b| !b.is_empty()));
+        let cmd = TaskCommand {
+            name: recipe.name.clone(),
+            executable: CommandExecutable::Direct {
+                command: format!("just {}", recipe.name),
+            },
+            description: recipe.description.clone(),
+            depends_on: recipe
+                .dependencies
+                .iter()
+                .filter(|d| justfile.public_recipes().any(|r| &r.name == *d))
+                .cloned()
+                .collect(),
+            required_args: recipe.required_parameters().map(str::to_string).collect(),
+            workdir: None,
+            confidence: classification.confidence,
+        };
+
+        commands.add_command(cmd, classification.category);
     }
 
     commands
 }
 
-fn extract_npm_commands(content: &str, package_json_path: &Path) -> TaskRunnerCommands {
+/// Scripts npm runs on `npm install`, in the order it runs them.
+const NPM_INSTALL_SCRIPTS: &[&str] = &[
+    "preinstall",
+    "install",
+    "postinstall",
+    "preprepare",
+    "prepare",
+    "postprepare",
+];
+
+fn extract_npm_commands(
+    content: &str,
+    package_json_path: &Path,
+    workspace: Option<&WorkspaceMember>,
+) -> TaskRunnerCommands {
     let mut commands = TaskRunnerCommands::default();
 
     let Ok(json) = serde_json::from_str::<JsonValue>(content) else {
@@ -354,49 +885,340 @@ fn extract_npm_commands(content: &str, package_json_path: &Path) -> TaskRunnerCo
     let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else {
         return commands;
     };
-
-    for (name, command_val) in scripts {
-        let Some(command_str) = command_val.as_str() else {
+    let scripts: Vec<(&str, &str)> = scripts
+        .iter()
+        .filter_map(|(name, body)| Some((name.as_str(), body.as_str()?)))
+        .collect();
+    let has_script = |name: &str| scripts.iter().any(|(n, _)| *n == name);
+
+    for &(name, command_str) in &scripts {
+        // `npm run test` runs `pretest` and `posttest` around `test`.
+        let is_hook = ["pre", "post"]
+            .iter()
+            .any(|prefix| name.strip_prefix(prefix).is_some_and(has_script));
+        if is_hook {
             continue;
+        }
+
+        let pre = format!("pre{name}");
+        let post = format!("post{name}");
+        let description = match (has_script(&pre), has_script(&post)) {
+            (true, true) => Some(format!("Runs `{pre}` first and `{post}` after")),
+            (true, false) => Some(format!("Runs `{pre}` first")),
+            (false, true) => Some(format!("Runs `{post}` after")),
+            (false, false) => None,
         };
 
+        let body = expand_npm_script(name, &scripts, &mut HashSet::new());
+        let classification = classify(name, Some(&body));
         let cmd = TaskCommand {
-            name: name.clone(),
+            name: name.to_string(),
             executable: CommandExecutable::PackageManagerScript {
-                script_name: name.clone(),
+                script_name: name.to_string(),
                 script_body: command_str.to_string(),
                 package_json_path: package_json_path.to_path_buf(),
+                workspace: workspace.cloned(),
             },
-            description: None,
+            description,
+            depends_on: vec![],
+            required_args: vec![],
+            workdir: None,
+            confidence: classification.confidence,
         };
 
-        commands.add_command(cmd, classify_command(name));
+        if NPM_INSTALL_SCRIPTS.contains(&name) {
+            commands.setup.push(cmd);
+        } else {
+            commands.add_command(cmd, classification.category);
+        }
     }
+    commands
+        .setup
+        .sort_by_key(|cmd| NPM_INSTALL_SCRIPTS.iter().position(|s| *s == cmd.name));
 
     commands
 }
 
-fn extract_taskfile_commands(content: &str) -> TaskRunnerCommands {
-    let mut commands = TaskRunnerCommands::default();
+/// A script's body with the scripts it runs inlined after the lines that
+/// run them, so `ci: run-p lint test` classifies like its parts.
+fn expand_npm_script(name: &str, scripts: &[(&str, &str)], seen: &mut HashSet<String>) -> String {
+    let Some((_, body)) = scripts.iter().find(|(n, _)| *n == name) else {
+        return String::new();
+    };
+    if !seen.insert(name.to_string()) {
+        return String::new();
+    }
 
-    let Ok(yaml) = serde_yaml::from_str::<YamlValue>(content) else {
-        return commands;
+    let mut lines = vec![];
+    for segment in shell_segments(body) {
+        lines.push(segment.trim().to_string());
+        for referenced in referenced_scripts(segment, scripts) {
+            lines.push(expand_npm_script(referenced, scripts, seen));
+        }
+    }
+    lines.join("\n")
+}
+
+/// `concurrently` options that take a value.
+const CONCURRENTLY_VALUE_FLAGS: &[&str] = &[
+    "-n",
+    "--names",
+    "-c",
+    "--prefix-colors",
+    "-p",
+    "--prefix",
+    "-m",
+    "--max-processes",
+    "-s",
+    "--success",
+];
+
+/// The scripts of `scripts` that a shell segment runs: through the package
+/// manager (`npm run lint`, `yarn lint`), `npm-run-all`/`run-s`/`run-p`
+/// (with `:`-separated globs) or `concurrently` (`"npm:lint"`).
+fn referenced_scripts<'a>(segment: &str, scripts: &[(&'a str, &str)]) -> Vec<&'a str> {
+    let words = shell_words(segment);
+    let words: Vec<&str> = words
+        .iter()
+        .map(String::as_str)
+        .skip_while(|w| w.contains('=') && !w.starts_with('-'))
+        .collect();
+
+    let mut patterns: Vec<String> = vec![];
+    // Scripts run by commands nested in the segment, like `concurrently`'s.
+    let mut nested: Vec<&'a str> = vec![];
+    match words.as_slice() {
+        [
+            "npm" | "pnpm" | "yarn" | "bun",
+            "run" | "run-script",
+            script,
+            ..,
+        ] => {
+            patterns.push(script.to_string());
+        }
+        ["npm" | "pnpm" | "yarn", "test" | "t", ..] => patterns.push("test".to_string()),
+        ["npm" | "pnpm" | "yarn", script @ ("start" | "stop"), ..] => {
+            patterns.push(script.to_string());
+        }
+        ["yarn" | "pnpm", script, ..] if !script.starts_with('-') => {
+            patterns.push(script.to_string());
+        }
+        ["npm-run-all" | "run-s" | "run-p", args @ ..] => {
+            patterns.extend(
+                args.iter()
+                    .filter(|arg| !arg.starts_with('-'))
+                    .filter_map(|arg| arg.split_whitespace().next())
+                    .map(str::to_string),
+            );
+        }
+        ["concurrently", args @ ..] => {
+            let mut args = args.iter();
+            while let Some(arg) = args.next() {
+                if CONCURRENTLY_VALUE_FLAGS.contains(arg) {
+                    args.next();
+                } else if arg.starts_with('-') {
+                    continue;
+                } else if let Some((_, pattern)) = arg
+                    .split_once(':')
+                    .filter(|(pm, _)| ["npm", "pnpm", "yarn", "bun"].contains(pm))
+                {
+                    patterns.extend(pattern.split_whitespace().next().map(str::to_string));
+                } else {
+                    nested.extend(
+                        shell_segments(arg)
+                            .flat_map(|command| referenced_scripts(command, scripts)),
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+
+    scripts
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| {
+            patterns.iter().any(|pattern| {
+                workspace_glob_matches(
+                    &pattern.replace(':', "/"),
+                    Path::new(&name.replace(':', "/")),
+                )
+            })
+        })
+        .chain(nested)
+        .collect()
+}
+
+/// Splits a command line into words, honouring single and double quotes.
+fn shell_words(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_default().push(c),
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                current.get_or_insert_default();
+            }
+            None if c.is_whitespace() => words.extend(current.take()),
+            None => current.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(current);
+    words
+}
+
+/// Member globs declared by a JS workspace root in `dir`: the `workspaces`
+/// field of `package.json` (an array, or Yarn's `{ "packages": [...] }`), or
+/// `packages` in `pnpm-workspace.yaml`.
+fn js_workspace_globs(dir: &Path, fs: &dyn FileSystem) -> Vec<String> {
+    let strings = |values: Option<&Vec<JsonValue>>| -> Vec<String> {
+        values
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
     };
 
-    if let Some(tasks) = yaml.get("tasks").and_then(|t| t.as_mapping()) {
-        for (task_name, _task_data) in tasks {
-            if let Some(name) = task_name.as_str() {
-                let cmd = TaskCommand {
-                    name: name.to_string(),
-                    executable: CommandExecutable::Direct {
-                        command: format!("task {}", name),
-                    },
-                    description: None,
-                };
+    if let Ok(content) = fs.read_to_string(&dir.join("pnpm-workspace.yaml"))
+        && let Ok(yaml) = serde_yaml::from_str::<YamlValue>(&content)
+        && let Some(packages) = yaml.get("packages").and_then(|p| p.as_sequence())
+    {
+        return packages
+            .iter()
+            .filter_map(|p| p.as_str().map(str::to_string))
+            .collect();
+    }
 
-                commands.add_command(cmd, classify_command(name));
+    let Ok(content) = fs.read_to_string(&dir.join("package.json")) else {
+        return vec![];
+    };
+    let Ok(json) = serde_json::from_str::<JsonValue>(&content) else {
+        return vec![];
+    };
+    match json.get("workspaces") {
+        Some(JsonValue::Array(globs)) => strings(Some(globs)),
+        Some(JsonValue::Object(config)) => {
+            strings(config.get("packages").and_then(|p| p.as_array()))
+        }
+        _ => vec![],
+    }
+}
+
+/// The `package.json` files of the members of the JS workspace rooted at
+/// `dir`.
+pub(crate) fn js_workspace_packages(dir: &Path, fs: &dyn FileSystem) -> Vec<PathBuf> {
+    let globs = js_workspace_globs(dir, fs);
+    let mut manifests: Vec<PathBuf> = Vec::new();
+    for glob in globs.iter().filter(|g| !g.starts_with('!')) {
+        for member in expand_member_glob(fs, dir, glob) {
+            let Ok(relative) = member.strip_prefix(dir) else {
+                continue;
+            };
+            let manifest = member.join("package.json");
+            if !relative.as_os_str().is_empty()
+                && in_workspace_globs(&globs, relative)
+                && fs.is_file(&manifest)
+                && !manifests.contains(&manifest)
+            {
+                manifests.push(manifest);
+            }
+        }
+    }
+    manifests
+}
+
+/// Whether `relative` matches a workspace glob, where `*` matches within one
+/// path segment and `**` across any number of them.
+pub(crate) fn workspace_glob_matches(pattern: &str, relative: &Path) -> bool {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
             }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    Regex::new(&regex).is_ok_and(|re| re.is_match(&relative))
+}
+
+/// Whether `relative` is selected by a list of workspace globs, honouring
+/// `!` exclusions.
+fn in_workspace_globs(globs: &[String], relative: &Path) -> bool {
+    let (excludes, includes): (Vec<&String>, Vec<&String>) =
+        globs.iter().partition(|g| g.starts_with('!'));
+    includes.iter().any(|g| workspace_glob_matches(g, relative))
+        && !excludes
+            .iter()
+            .any(|g| workspace_glob_matches(&g[1..], relative))
+}
+
+/// The workspace a `package.json` belongs to, found by walking up to the
+/// nearest ancestor that declares workspaces.
+fn js_workspace_member(
+    package_json: &Path,
+    content: &str,
+    fs: &dyn FileSystem,
+) -> Option<WorkspaceMember> {
+    let package_dir = package_json.parent()?;
+    let json: JsonValue = serde_json::from_str(content).ok()?;
+    let name = json.get("name")?.as_str()?;
+
+    for dir in package_dir.ancestors().skip(1) {
+        let globs = js_workspace_globs(dir, fs);
+        if globs.is_empty() {
+            continue;
         }
+        let relative = package_dir.strip_prefix(dir).ok()?;
+        return in_workspace_globs(&globs, relative).then(|| WorkspaceMember {
+            root: dir.to_path_buf(),
+            name: name.to_string(),
+        });
+    }
+
+    None
+}
+
+fn extract_taskfile_commands(taskfile: &Taskfile) -> TaskRunnerCommands {
+    let mut commands = TaskRunnerCommands::default();
+
+    for task in taskfile.runnable_tasks() {
+        let body = task.cmds.join("\n");
+        let classification = classify(&task.name, Some(body.as_str()).filter(|b| !b.is_empty()));
+        let cmd = TaskCommand {
+            name: task.name.clone(),
+            executable: CommandExecutable::Direct {
+                command: format!("task {}", task.name),
+            },
+            description: task.description.clone(),
+            depends_on: task
+                .deps
+                .iter()
+                .filter(|d| taskfile.runnable_tasks().any(|t| &t.name == *d))
+                .cloned()
+                .collect(),
+            required_args: task.required_vars.clone(),
+            workdir: task.dir.clone(),
+            confidence: classification.confidence,
+        };
+
+        commands.add_command(cmd, classification.category);
     }
 
     commands
@@ -411,8 +1233,12 @@ fn get_vite_commands() -> TaskRunnerCommands {
             command: "vite".to_string(),
         },
         description: Some("Start dev server".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(dev_cmd, CommandCategory::Other);
+    commands.add_command(dev_cmd, CommandCategory::Run);
 
     let build_cmd = TaskCommand {
         name: "build".to_string(),
@@ -420,6 +1246,10 @@ fn get_vite_commands() -> TaskRunnerCommands {
             command: "vite build".to_string(),
         },
         description: Some("Build for production".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
     commands.add_command(build_cmd, CommandCategory::Build);
 
@@ -429,8 +1259,12 @@ fn get_vite_commands() -> TaskRunnerCommands {
             command: "vite preview".to_string(),
         },
         description: Some("Preview production build".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(preview_cmd, CommandCategory::Other);
+    commands.add_command(preview_cmd, CommandCategory::Run);
 
     commands
 }
@@ -444,6 +1278,10 @@ fn get_webpack_commands() -> TaskRunnerCommands {
             command: "webpack build".to_string(),
         },
         description: Some("Build for production".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
     commands.add_command(build_cmd, CommandCategory::Build);
 
@@ -453,8 +1291,12 @@ fn get_webpack_commands() -> TaskRunnerCommands {
             command: "webpack serve".to_string(),
         },
         description: Some("Start dev server".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(serve_cmd, CommandCategory::Other);
+    commands.add_command(serve_cmd, CommandCategory::Run);
 
     let watch_cmd = TaskCommand {
         name: "watch".to_string(),
@@ -462,8 +1304,12 @@ fn get_webpack_commands() -> TaskRunnerCommands {
             command: "webpack watch".to_string(),
         },
         description: Some("Watch for file changes".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(watch_cmd, CommandCategory::Other);
+    commands.add_command(watch_cmd, CommandCategory::Run);
 
     commands
 }
@@ -477,8 +1323,12 @@ fn get_rspack_commands() -> TaskRunnerCommands {
             command: "rspack dev".to_string(),
         },
         description: Some("Start dev server".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(dev_cmd, CommandCategory::Other);
+    commands.add_command(dev_cmd, CommandCategory::Run);
 
     let build_cmd = TaskCommand {
         name: "build".to_string(),
@@ -486,6 +1336,10 @@ fn get_rspack_commands() -> TaskRunnerCommands {
             command: "rspack build".to_string(),
         },
         description: Some("Build for production".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
     commands.add_command(build_cmd, CommandCategory::Build);
 
@@ -495,8 +1349,12 @@ fn get_rspack_commands() -> TaskRunnerCommands {
             command: "rspack preview".to_string(),
         },
         description: Some("Preview production build".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(preview_cmd, CommandCategory::Other);
+    commands.add_command(preview_cmd, CommandCategory::Run);
 
     commands
 }
@@ -510,6 +1368,10 @@ fn get_rollup_commands() -> TaskRunnerCommands {
             command: "rollup -c".to_string(),
         },
         description: Some("Build bundle".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
     commands.add_command(build_cmd, CommandCategory::Build);
 
@@ -519,68 +1381,169 @@ fn get_rollup_commands() -> TaskRunnerCommands {
             command: "rollup -c -w".to_string(),
         },
         description: Some("Watch and rebuild on changes".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(watch_cmd, CommandCategory::Other);
+    commands.add_command(watch_cmd, CommandCategory::Run);
 
     commands
 }
 
-fn get_cargo_commands() -> TaskRunnerCommands {
+/// Cargo commands for a package or workspace root: per-package tests (every
+/// target, then doc tests separately), feature and docs.rs builds, declared
+/// binaries, examples and benches, plus workspace-wide builds and lints.
+fn extract_cargo_commands(manifest: &CargoManifest, fs: &dyn FileSystem) -> TaskRunnerCommands {
     let mut commands = TaskRunnerCommands::default();
-
-    let test_cmd = TaskCommand {
-        name: "test".to_string(),
-        executable: CommandExecutable::Direct {
-            command: "cargo test".to_string(),
-        },
-        description: Some("Run tests".to_string()),
-    };
-    commands.add_command(test_cmd, CommandCategory::Test);
-
-    let build_cmd = TaskCommand {
-        name: "build".to_string(),
-        executable: CommandExecutable::Direct {
-            command: "cargo build".to_string(),
-        },
-        description: Some("Build project".to_string()),
+    let mut seen: Vec<String> = Vec::new();
+    let mut add = |name: String, command: String, description: &str, category| {
+        if seen.contains(&command) {
+            return;
+        }
+        seen.push(command.clone());
+        commands.add_command(
+            TaskCommand {
+                name,
+                executable: CommandExecutable::Direct { command },
+                description: Some(description.to_string()),
+                depends_on: vec![],
+                required_args: vec![],
+                workdir: None,
+                confidence: Confidence::High,
+            },
+            category,
+        );
     };
-    commands.add_command(build_cmd, CommandCategory::Build);
 
-    let build_release_cmd = TaskCommand {
-        name: "build-release".to_string(),
-        executable: CommandExecutable::Direct {
-            command: "cargo build --release".to_string(),
-        },
-        description: Some("Build optimized release".to_string()),
-    };
-    commands.add_command(build_release_cmd, CommandCategory::Build);
+    let workspace = manifest.workspace.as_ref();
+    let mut packages: Vec<CargoManifest> = Vec::new();
+    if manifest.package.is_some() {
+        packages.push(manifest.clone());
+    }
+    packages.extend(manifest.workspace_members(fs));
 
-    let check_cmd = TaskCommand {
-        name: "check".to_string(),
-        executable: CommandExecutable::Direct {
-            command: "cargo check".to_string(),
-        },
-        description: Some("Check without building".to_string()),
-    };
-    commands.add_command(check_cmd, CommandCategory::Other);
+    // A single package needs no `-p`; workspace members are selected by name.
+    let single = workspace.is_none();
+    for package in &packages {
+        let Some(name) = package.package.as_deref() else {
+            continue;
+        };
+        let (select, suffix) = if single {
+            (String::new(), String::new())
+        } else {
+            (format!(" -p {name}"), format!("-{name}"))
+        };
 
-    let clippy_cmd = TaskCommand {
-        name: "clippy".to_string(),
-        executable: CommandExecutable::Direct {
-            command: "cargo clippy".to_string(),
-        },
-        description: Some("Run linter".to_string()),
-    };
-    commands.add_command(clippy_cmd, CommandCategory::Other);
+        add(
+            format!("test{suffix}"),
+            format!("cargo test{select} --all-targets"),
+            "Run tests",
+            CommandCategory::Test,
+        );
+        if package.has_lib {
+            add(
+                format!("doc-test{suffix}"),
+                format!("cargo test{select} --doc"),
+                "Run documentation tests",
+                CommandCategory::Test,
+            );
+        }
+        if !package.features.is_empty() {
+            add(
+                format!("test-all-features{suffix}"),
+                format!("cargo test{select} --all-features"),
+                "Run tests with every feature enabled",
+                CommandCategory::Test,
+            );
+        }
+        if let Some(docs_rs) = &package.docs_rs_features {
+            let features = match docs_rs {
+                DocsRsFeatures::All => " --all-features".to_string(),
+                DocsRsFeatures::Listed(list) if list.is_empty() => String::new(),
+                DocsRsFeatures::Listed(list) => format!(" --features {}", list.join(",")),
+            };
+            add(
+                format!("doc{suffix}"),
+                format!("cargo doc{select} --no-deps{features}"),
+                "Build documentation as docs.rs does",
+                CommandCategory::Build,
+            );
+        }
+        for bin in &package.bins {
+            add(
+                format!("run-{bin}{suffix}"),
+                format!("cargo run{select} --bin {bin}"),
+                "Run binary",
+                CommandCategory::Run,
+            );
+        }
+        for example in &package.examples {
+            add(
+                format!("example-{example}{suffix}"),
+                format!("cargo run{select} --example {example}"),
+                "Run example",
+                CommandCategory::Run,
+            );
+        }
+        for bench in &package.benches {
+            add(
+                format!("bench-{bench}{suffix}"),
+                format!("cargo bench{select} --bench {bench}"),
+                "Run benchmark",
+                CommandCategory::Bench,
+            );
+        }
+    }
 
-    let fmt_cmd = TaskCommand {
-        name: "fmt".to_string(),
-        executable: CommandExecutable::Direct {
-            command: "cargo fmt".to_string(),
-        },
-        description: Some("Format code".to_string()),
+    // `default-members` decide what a bare `cargo build` covers.
+    let scope = match workspace {
+        Some(ws) if ws.default_members.is_empty() => " --workspace",
+        _ => "",
     };
-    commands.add_command(fmt_cmd, CommandCategory::Other);
+    add(
+        "build".to_string(),
+        format!("cargo build{scope}"),
+        "Build project",
+        CommandCategory::Build,
+    );
+    add(
+        "build-release".to_string(),
+        format!("cargo build{scope} --release"),
+        "Build optimized release",
+        CommandCategory::Build,
+    );
+    if packages.iter().any(|p| !p.features.is_empty()) {
+        add(
+            "build-all-features".to_string(),
+            format!("cargo build{scope} --all-features"),
+            "Build with every feature enabled",
+            CommandCategory::Build,
+        );
+    }
+    add(
+        "check".to_string(),
+        format!("cargo check{scope}"),
+        "Check without building",
+        CommandCategory::Other,
+    );
+    add(
+        "clippy".to_string(),
+        format!("cargo clippy{scope} --all-targets"),
+        "Run linter",
+        CommandCategory::Lint,
+    );
+    add(
+        "fmt".to_string(),
+        if single {
+            "cargo fmt --check"
+        } else {
+            "cargo fmt --all --check"
+        }
+        .to_string(),
+        "Check formatting",
+        CommandCategory::Format,
+    );
 
     commands
 }
@@ -594,6 +1557,10 @@ fn get_go_commands() -> TaskRunnerCommands {
             command: "go test ./...".to_string(),
         },
         description: Some("Run tests".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
     commands.add_command(test_cmd, CommandCategory::Test);
 
@@ -603,6 +1570,10 @@ fn get_go_commands() -> TaskRunnerCommands {
             command: "go build".to_string(),
         },
         description: Some("Build project".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
     commands.add_command(build_cmd, CommandCategory::Build);
 
@@ -612,17 +1583,26 @@ fn get_go_commands() -> TaskRunnerCommands {
             command: "go run .".to_string(),
         },
         description: Some("Run project".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(run_cmd, CommandCategory::Other);
+    commands.add_command(run_cmd, CommandCategory::Run);
 
+    // `gofmt -l` only lists unformatted files; it exits zero either way.
     let fmt_cmd = TaskCommand {
         name: "fmt".to_string(),
         executable: CommandExecutable::Direct {
-            command: "go fmt ./...".to_string(),
+            command: "test -z \"$(gofmt -l .)\"".to_string(),
         },
-        description: Some("Format code".to_string()),
+        description: Some("Check formatting".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(fmt_cmd, CommandCategory::Other);
+    commands.add_command(fmt_cmd, CommandCategory::Format);
 
     let vet_cmd = TaskCommand {
         name: "vet".to_string(),
@@ -630,43 +1610,202 @@ fn get_go_commands() -> TaskRunnerCommands {
             command: "go vet ./...".to_string(),
         },
         description: Some("Examine code for issues".to_string()),
+        depends_on: vec![],
+        required_args: vec![],
+        workdir: None,
+        confidence: Confidence::High,
     };
-    commands.add_command(vet_cmd, CommandCategory::Other);
+    commands.add_command(vet_cmd, CommandCategory::Lint);
 
     commands
 }
 
-fn extract_turbo_commands(content: &str) -> TaskRunnerCommands {
+/// One test and one build unit per module in the workspace's `use`
+/// directives, run from the workspace root.
+fn extract_go_work_commands(content: &str) -> TaskRunnerCommands {
     let mut commands = TaskRunnerCommands::default();
 
-    let Ok(json) = serde_json::from_str::<JsonValue>(content) else {
-        return commands;
+    for module in go_work_uses(content) {
+        let (suffix, pattern) = if module == "." {
+            ("root".to_string(), "./...".to_string())
+        } else {
+            (module.replace('/', "-"), format!("./{module}/..."))
+        };
+
+        let test_cmd = TaskCommand {
+            name: format!("test-{suffix}"),
+            executable: CommandExecutable::Direct {
+                command: format!("go test {pattern}"),
+            },
+            description: Some(format!("Run tests in {module}")),
+            depends_on: vec![],
+            required_args: vec![],
+            workdir: None,
+            confidence: Confidence::High,
+        };
+        commands.add_command(test_cmd, CommandCategory::Test);
+
+        let build_cmd = TaskCommand {
+            name: format!("build-{suffix}"),
+            executable: CommandExecutable::Direct {
+                command: format!("go build {pattern}"),
+            },
+            description: Some(format!("Build {module}")),
+            depends_on: vec![],
+            required_args: vec![],
+            workdir: None,
+            confidence: Confidence::High,
+        };
+        commands.add_command(build_cmd, CommandCategory::Build);
+    }
+
+    commands
+}
+
+/// Module directories from `use ./dir` and `use ( ... )` directives,
+/// relative to the go.work file and without a leading `./`.
+pub(crate) fn go_work_uses(content: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let dir = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else {
+            match line.split_once(char::is_whitespace) {
+                Some(("use", rest)) if rest.trim() == "(" => {
+                    in_block = true;
+                    continue;
+                }
+                Some(("use", rest)) => rest.trim(),
+                _ => continue,
+            }
+        };
+
+        let dir = dir.trim_matches('"');
+        if dir.is_empty() {
+            continue;
+        }
+        let dir = dir.strip_prefix("./").unwrap_or(dir).trim_end_matches('/');
+        let dir = if dir.is_empty() { "." } else { dir };
+        if !uses.iter().any(|u| u == dir) {
+            uses.push(dir.to_string());
+        }
+    }
+
+    uses
+}
+
+/// Whether the nearest `go.work` above a `go.mod` lists its module, which
+/// puts the module in workspace mode.
+fn in_go_workspace(go_mod: &Path, fs: &dyn FileSystem) -> bool {
+    let Some(module_dir) = go_mod.parent() else {
+        return false;
     };
 
-    let tasks = json
-        .get("pipeline")
-        .or_else(|| json.get("tasks"))
-        .and_then(|t| t.as_object());
+    for dir in module_dir.ancestors() {
+        let go_work = dir.join("go.work");
+        if !fs.is_file(&go_work) {
+            continue;
+        }
+        let Ok(content) = fs.read_to_string(&go_work) else {
+            return false;
+        };
+        let Ok(relative) = module_dir.strip_prefix(dir) else {
+            return false;
+        };
+        let relative = if relative.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            relative
+        };
+        return go_work_uses(&content)
+            .iter()
+            .any(|module| Path::new(module) == relative);
+    }
 
-    let Some(tasks) = tasks else {
+    false
+}
+
+/// The tasks a `turbo.json` defines, run across the whole workspace; used
+/// when its packages can't be resolved.
+fn extract_turbo_commands(content: &str) -> TaskRunnerCommands {
+    let mut commands = TaskRunnerCommands::default();
+
+    let Some(turbo) = TurboJson::parse(content) else {
         return commands;
     };
 
-    for (task_name, _task_config) in tasks {
+    for (task_name, config) in &turbo.tasks {
+        let classification = persistent_or(config.persistent == Some(true), || {
+            classify(task_name, None)
+        });
         let cmd = TaskCommand {
             name: task_name.clone(),
             executable: CommandExecutable::Direct {
                 command: format!("turbo run {}", task_name),
             },
             description: None,
+            depends_on: vec![],
+            required_args: vec![],
+            workdir: None,
+            confidence: classification.confidence,
+        };
+
+        commands.add_command(cmd, classification.category);
+    }
+
+    commands
+}
+
+/// One command per task of a resolved graph, each running a single package's
+/// task, in the graph's topological order.
+fn extract_task_graph_commands(graph: &TaskGraph, task_runner: TaskRunner) -> TaskRunnerCommands {
+    let mut commands = TaskRunnerCommands::default();
+
+    for task in &graph.tasks {
+        let command = match task_runner {
+            TaskRunner::Turbo => format!("turbo run {} --filter={}", task.task, task.package),
+            TaskRunner::Nx => format!("nx run {}:{}", task.package, task.task),
+            _ => continue,
+        };
+        let classification = persistent_or(task.persistent, || {
+            classify_target(&task.task, task.executor.as_deref(), task.script.as_deref())
+        });
+        let cmd = TaskCommand {
+            name: task.id.clone(),
+            executable: CommandExecutable::Direct { command },
+            description: None,
+            depends_on: task.depends_on.clone(),
+            required_args: vec![],
+            workdir: None,
+            confidence: classification.confidence,
         };
 
-        commands.add_command(cmd, classify_command(task_name));
+        commands.add_command(cmd, classification.category);
     }
 
     commands
 }
 
+/// Tasks a runner marks as long-running never finish, whatever they're
+/// called.
+fn persistent_or(persistent: bool, classify: impl FnOnce() -> Classification) -> Classification {
+    if persistent {
+        Classification {
+            category: CommandCategory::Run,
+            confidence: Confidence::High,
+        }
+    } else {
+        classify()
+    }
+}
+
 fn extract_nx_commands(content: &str) -> TaskRunnerCommands {
     let mut commands = TaskRunnerCommands::default();
 
@@ -677,34 +1816,44 @@ fn extract_nx_commands(content: &str) -> TaskRunnerCommands {
     let target_defaults = json.get("targetDefaults").and_then(|t| t.as_object());
 
     if let Some(targets) = target_defaults {
-        for (target_name, _target_config) in targets {
+        for (target_name, target_config) in targets {
+            let executor = target_config.get("executor").and_then(|e| e.as_str());
+            let classification = classify_target(target_name, executor, None);
             let cmd = TaskCommand {
                 name: target_name.clone(),
                 executable: CommandExecutable::Direct {
                     command: format!("nx run {}", target_name),
                 },
                 description: None,
+                depends_on: vec![],
+                required_args: vec![],
+                workdir: None,
+                confidence: classification.confidence,
             };
 
-            commands.add_command(cmd, classify_command(target_name));
+            commands.add_command(cmd, classification.category);
         }
     }
 
-    if commands.test.is_empty()
-        && commands.build.is_empty()
-        && commands.other.is_empty()
+    if commands.is_empty()
         && let Some(targets) = json.get("targets").and_then(|t| t.as_object())
     {
-        for (target_name, _target_config) in targets {
+        for (target_name, target_config) in targets {
+            let executor = target_config.get("executor").and_then(|e| e.as_str());
+            let classification = classify_target(target_name, executor, None);
             let cmd = TaskCommand {
                 name: target_name.clone(),
                 executable: CommandExecutable::Direct {
                     command: format!("nx run {}", target_name),
                 },
                 description: None,
+                depends_on: vec![],
+                required_args: vec![],
+                workdir: None,
+                confidence: classification.confidence,
             };
 
-            commands.add_command(cmd, classify_command(target_name));
+            commands.add_command(cmd, classification.category);
         }
     }
 
@@ -759,6 +1908,14 @@ fn extract_tox_commands(content: &str) -> TaskRunnerCommands {
                 .unwrap_or_default();
 
             if !commands_found.is_empty() {
+                let classification = if env_name == "default" || env_name.starts_with("py") {
+                    Classification {
+                        category: CommandCategory::Test,
+                        confidence: Confidence::Medium,
+                    }
+                } else {
+                    classify(env_name, Some(&commands_found.join("\n")))
+                };
                 let cmd = TaskCommand {
                     name: env_name.to_string(),
                     executable: CommandExecutable::Direct {
@@ -769,21 +1926,18 @@ fn extract_tox_commands(content: &str) -> TaskRunnerCommands {
                         },
                     },
                     description: None,
+                    depends_on: vec![],
+                    required_args: vec![],
+                    workdir: None,
+                    confidence: classification.confidence,
                 };
 
-                commands.add_command(
-                    cmd,
-                    if env_name == "default" || env_name.starts_with("py") {
-                        CommandCategory::Test
-                    } else {
-                        classify_command(env_name)
-                    },
-                );
+                commands.add_command(cmd, classification.category);
             }
         }
     }
 
-    if commands.test.is_empty() && commands.build.is_empty() && commands.other.is_empty() {
+    if commands.is_empty() {
         commands.add_command(
             TaskCommand {
                 name: "default".to_string(),
@@ -791,6 +1945,10 @@ fn extract_tox_commands(content: &str) -> TaskRunnerCommands {
                     command: "tox".to_string(),
                 },
                 description: Some("Run all tox environments".to_string()),
+                depends_on: vec![],
+                required_args: vec![],
+                workdir: None,
+                confidence: Confidence::High,
             },
             CommandCategory::Test,
         );
@@ -845,11 +2003,14 @@ fn extract_nox_commands(content: &str) -> TaskRunnerCommands {
             if let Some(func_name) = func_name {
                 let session_name = explicit_name.as_ref().unwrap_or(&func_name);
 
-                let category = if session_name.starts_with("py") {
+                let classification = if session_name.starts_with("py") {
                     // Nox-specific: py38, py39, etc. are test sessions >:(
-                    CommandCategory::Test
+                    Classification {
+                        category: CommandCategory::Test,
+                        confidence: Confidence::Medium,
+                    }
                 } else {
-                    classify_command(session_name)
+                    classify(session_name, None)
                 };
 
                 commands.add_command(
@@ -859,14 +2020,18 @@ fn extract_nox_commands(content: &str) -> TaskRunnerCommands {
                             command: format!("nox -s {}", session_name),
                         },
                         description: None,
+                        depends_on: vec![],
+                        required_args: vec![],
+                        workdir: None,
+                        confidence: classification.confidence,
                     },
-                    category,
+                    classification.category,
                 );
             }
         }
     }
 
-    if commands.test.is_empty() && commands.build.is_empty() && commands.other.is_empty() {
+    if commands.is_empty() {
         commands.add_command(
             TaskCommand {
                 name: "default".to_string(),
@@ -874,6 +2039,10 @@ fn extract_nox_commands(content: &str) -> TaskRunnerCommands {
                     command: "nox".to_string(),
                 },
                 description: Some("Run all nox sessions".to_string()),
+                depends_on: vec![],
+                required_args: vec![],
+                workdir: None,
+                confidence: Confidence::High,
             },
             CommandCategory::Test,
         );
@@ -903,6 +2072,7 @@ fn extract_invoke_commands(content: &str) -> TaskRunnerCommands {
                 .find_map(|t| func_re.captures(t)?.get(1).map(|m| m.as_str().to_string()));
 
             if let Some(task_name) = func_name {
+                let classification = classify(&task_name, None);
                 commands.add_command(
                     TaskCommand {
                         name: task_name.clone(),
@@ -910,8 +2080,12 @@ fn extract_invoke_commands(content: &str) -> TaskRunnerCommands {
                             command: format!("invoke {}", task_name),
                         },
                         description: None,
+                        depends_on: vec![],
+                        required_args: vec![],
+                        workdir: None,
+                        confidence: classification.confidence,
                     },
-                    classify_command(&task_name),
+                    classification.category,
                 );
             }
         }
@@ -930,6 +2104,7 @@ fn extract_invoke_yaml_commands(content: &str) -> TaskRunnerCommands {
     if let Some(tasks) = yaml.get("tasks").and_then(|t| t.as_mapping()) {
         for (task_name, _task_data) in tasks {
             if let Some(name) = task_name.as_str() {
+                let classification = classify(name, None);
                 commands.add_command(
                     TaskCommand {
                         name: name.to_string(),
@@ -937,8 +2112,12 @@ fn extract_invoke_yaml_commands(content: &str) -> TaskRunnerCommands {
                             command: format!("invoke {}", name),
                         },
                         description: None,
+                        depends_on: vec![],
+                        required_args: vec![],
+                        workdir: None,
+                        confidence: classification.confidence,
                     },
-                    classify_command(name),
+                    classification.category,
                 );
             }
         }
@@ -947,22 +2126,165 @@ fn extract_invoke_yaml_commands(content: &str) -> TaskRunnerCommands {
     commands
 }
 
-impl From<TaskRunnerFile> for TaskRunnerDetection {
-    fn from(file: TaskRunnerFile) -> Self {
-        let commands = file.extract_commands();
+fn toml_table_at<'a>(root: &'a toml::Value, keys: &[&str]) -> Option<&'a toml::Table> {
+    keys.iter()
+        .try_fold(root, |value, key| value.get(key))?
+        .as_table()
+}
 
-        TaskRunnerDetection {
-            task_runner: file.task_runner,
-            source: file.source,
-            path: file.path,
-            commands,
+/// A `pyproject.toml` is a task runner file when it declares Hatch scripts or
+/// pixi tasks; Hatch wins when it has both.
+fn pyproject_task_source(path: &Path, fs: &dyn FileSystem) -> Option<TaskRunnerSource> {
+    let parsed: toml::Value = toml::from_str(&fs.read_to_string(path).ok()?).ok()?;
+
+    let has_hatch_scripts = toml_table_at(&parsed, &["tool", "hatch", "envs"])
+        .is_some_and(|envs| envs.values().any(|env| env.get("scripts").is_some()));
+    let has_pixi_tasks = !pixi_tasks(&parsed, &["tool", "pixi"]).is_empty();
+
+    if has_hatch_scripts {
+        Some(TaskRunnerSource::PyprojectHatch)
+    } else if has_pixi_tasks {
+        Some(TaskRunnerSource::PyprojectPixi)
+    } else {
+        None
+    }
+}
+
+/// `hatch run <env>:<script>` for every script of every environment under
+/// `envs_path`; scripts of the `default` environment run without a prefix.
+fn extract_hatch_commands(content: &str, envs_path: &[&str]) -> TaskRunnerCommands {
+    let mut commands = TaskRunnerCommands::default();
+
+    let Ok(parsed) = toml::from_str::<toml::Value>(content) else {
+        return commands;
+    };
+    let Some(envs) = toml_table_at(&parsed, envs_path) else {
+        return commands;
+    };
+
+    for (env, config) in envs {
+        let Some(scripts) = config.get("scripts").and_then(|s| s.as_table()) else {
+            continue;
+        };
+
+        for (script, body) in scripts {
+            let steps: Vec<&str> = match body {
+                toml::Value::String(step) => vec![step.as_str()],
+                toml::Value::Array(steps) => steps.iter().filter_map(|s| s.as_str()).collect(),
+                _ => continue,
+            };
+            let target = if env == "default" {
+                script.clone()
+            } else {
+                format!("{env}:{script}")
+            };
+
+            let classification = classify(script, Some(&steps.join("\n")));
+            commands.add_command(
+                TaskCommand {
+                    name: target.clone(),
+                    executable: CommandExecutable::Direct {
+                        command: format!("hatch run {target}"),
+                    },
+                    description: Some(steps.join(" && ")),
+                    depends_on: vec![],
+                    required_args: vec![],
+                    workdir: None,
+                    confidence: classification.confidence,
+                },
+                classification.category,
+            );
         }
     }
+
+    commands
+}
+
+/// The tasks of a pixi manifest rooted at `base`: its `tasks` table and the
+/// `tasks` of each `feature`.
+fn pixi_tasks<'a>(parsed: &'a toml::Value, base: &[&str]) -> Vec<(&'a String, &'a toml::Value)> {
+    let Some(manifest) = toml_table_at(parsed, base) else {
+        return vec![];
+    };
+
+    let top_level = manifest.get("tasks").and_then(|t| t.as_table());
+    let features = manifest
+        .get("feature")
+        .and_then(|f| f.as_table())
+        .into_iter()
+        .flat_map(|features| features.values())
+        .filter_map(|feature| feature.get("tasks")?.as_table());
+
+    top_level.into_iter().chain(features).flatten().collect()
+}
+
+fn extract_pixi_commands(content: &str, base: &[&str]) -> TaskRunnerCommands {
+    let mut commands = TaskRunnerCommands::default();
+
+    let Ok(parsed) = toml::from_str::<toml::Value>(content) else {
+        return commands;
+    };
+
+    for (name, task) in pixi_tasks(&parsed, base) {
+        let cmd = match task {
+            toml::Value::String(cmd) => Some(cmd.clone()),
+            toml::Value::Table(table) => match table.get("cmd") {
+                Some(toml::Value::String(cmd)) => Some(cmd.clone()),
+                Some(toml::Value::Array(parts)) => Some(
+                    parts
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                _ => None,
+            },
+            _ => None,
+        };
+        let classification = classify(name, cmd.as_deref());
+        let description = task
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(str::to_string)
+            .or(cmd);
+
+        commands.add_command(
+            TaskCommand {
+                name: name.clone(),
+                executable: CommandExecutable::Direct {
+                    command: format!("pixi run {name}"),
+                },
+                description,
+                depends_on: vec![],
+                required_args: vec![],
+                workdir: None,
+                confidence: classification.confidence,
+            },
+            classification.category,
+        );
+    }
+
+    commands
+}
+
+impl TryFrom<PathBuf> for TaskRunnerFile {
+    type Error = ();
+
+    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+        Self::from_path(path, &LocalFileSystem).ok_or(())
+    }
+}
+
+impl From<TaskRunnerFile> for TaskRunnerDetection {
+    fn from(file: TaskRunnerFile) -> Self {
+        file.into_detection(&LocalFileSystem)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -1002,6 +2324,10 @@ mod tests {
             TaskRunner::from(&TaskRunnerSource::TaskfileYaml),
             TaskRunner::Task
         );
+        assert_eq!(
+            TaskRunner::from(&TaskRunnerSource::TaskfileDistYml),
+            TaskRunner::Task
+        );
     }
 
     #[test]
@@ -1015,9 +2341,7 @@ mod tests {
     #[test]
     fn test_task_runner_commands_default() {
         let commands = TaskRunnerCommands::default();
-        assert!(commands.test.is_empty());
-        assert!(commands.build.is_empty());
-        assert!(commands.other.is_empty());
+        assert!(commands.is_empty());
     }
 
     #[test]
@@ -1043,6 +2367,56 @@ mod tests {
         assert_eq!(file.source, TaskRunnerSource::Justfile);
     }
 
+    #[test]
+    fn test_try_from_taskfile_names() {
+        let dir = TempDir::new().unwrap();
+        let dist = create_temp_file(&dir, "Taskfile.dist.yml", "version: '3'\n");
+        let lower = create_temp_file(&dir, "ci/taskfile.yaml", "version: '3'\n");
+
+        let file = TaskRunnerFile::try_from(dist.clone()).unwrap();
+        assert_eq!(file.source, TaskRunnerSource::TaskfileDistYml);
+        let file = TaskRunnerFile::try_from(lower).unwrap();
+        assert_eq!(file.source, TaskRunnerSource::TaskfileYaml);
+
+        // A local Taskfile.yml takes precedence over the checked-in dist one.
+        create_temp_file(&dir, "Taskfile.yml", "version: '3'\n");
+        assert!(TaskRunnerFile::try_from(dist).is_err());
+    }
+
+    #[test]
+    fn test_extract_taskfile_commands() {
+        let content = r#"
+version: '3'
+tasks:
+  test:
+    desc: Run the tests
+    deps: [generate]
+    cmds: [go test ./...]
+  generate:
+    internal: true
+    cmds: [go generate ./...]
+  lint:
+    dir: tools
+    cmds: [golangci-lint run]
+  deploy:
+    requires:
+      vars: [ENV]
+    cmds: [./deploy.sh]
+  sign:
+    platforms: [windows]
+    cmds: [signtool sign app.exe]
+"#;
+        let commands = extract_taskfile_commands(&Taskfile::parse(content).unwrap());
+        let names: Vec<&str> = commands.all().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["test", "lint", "deploy"]);
+
+        let test = &commands.test[0];
+        assert_eq!(test.description.as_deref(), Some("Run the tests"));
+        assert!(test.depends_on.is_empty());
+        assert_eq!(commands.lint[0].workdir, Some(PathBuf::from("tools")));
+        assert_eq!(commands.other[0].required_args, ["ENV"]);
+    }
+
     #[test]
     fn test_try_from_package_json() {
         let dir = TempDir::new().unwrap();
@@ -1091,12 +2465,14 @@ lint:
 	cargo clippy
 
 fmt:
-	cargo fmt
+	cargo fmt --check
 "#;
-        let commands = extract_makefile_commands(content);
+        let commands = extract_makefile_commands(&Makefile::parse(content));
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 2);
+        assert_eq!(commands.lint.len(), 1);
+        assert_eq!(commands.format.len(), 1);
+        assert!(commands.other.is_empty());
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
             &commands.test[0].executable,
@@ -1104,6 +2480,70 @@ fmt:
         ));
     }
 
+    #[test]
+    fn test_makefile_help_and_prerequisites() {
+        let content = r#"
+VERSION := 1.0
+.PHONY: ci test tools/clean
+
+ci: test lint ## Run the CI suite
+test: target/debug/app ## Run tests
+	cargo test
+
+lint:
+	cargo clippy
+
+tools/clean:
+	rm -rf tools/out
+
+target/debug/app:
+	cargo build
+"#;
+        let commands = extract_makefile_commands(&Makefile::parse(content));
+        let names: Vec<&str> = commands.all().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["test", "lint", "ci", "tools/clean"]);
+
+        let ci = commands.other.iter().find(|c| c.name == "ci").unwrap();
+        assert_eq!(ci.description.as_deref(), Some("Run the CI suite"));
+        assert_eq!(ci.depends_on, ["test", "lint"]);
+        assert!(commands.test[0].depends_on.is_empty());
+    }
+
+    #[test]
+    fn test_extract_justfile_commands() {
+        let content = r#"
+set dotenv-load
+alias b := build
+
+# Compile everything
+build: _prepare
+    cargo build
+
+[group('lint')]
+check: build
+    cargo clippy
+
+test filter='':
+    cargo test {{filter}}
+
+release version:
+    ./release.sh {{version}}
+
+_prepare:
+    mkdir -p out
+"#;
+        let commands = extract_justfile_commands(&Justfile::parse(content));
+        let names: Vec<&str> = commands.all().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["test", "build", "check", "release"]);
+
+        let build = &commands.build[0];
+        assert_eq!(build.description.as_deref(), Some("Compile everything"));
+        assert!(build.depends_on.is_empty());
+        assert_eq!(commands.lint[0].depends_on, ["build"]);
+        assert!(commands.test[0].required_args.is_empty());
+        assert_eq!(commands.other[0].required_args, ["version"]);
+    }
+
     #[test]
     fn test_extract_npm_commands() {
         let content = r#"{
@@ -1116,10 +2556,14 @@ fmt:
   }
 }"#;
         let package_json_path = PathBuf::from("/test/package.json");
-        let commands = extract_npm_commands(content, &package_json_path);
+        let commands = extract_npm_commands(content, &package_json_path, None);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 3);
+        assert_eq!(commands.lint.len(), 1);
+        assert_eq!(commands.run.len(), 1);
+        // `prettier --write` rewrites files, so it is no format check.
+        assert!(commands.format.is_empty());
+        assert_eq!(commands.other.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -1127,7 +2571,8 @@ fmt:
             CommandExecutable::PackageManagerScript {
                 script_name,
                 script_body,
-                package_json_path: _
+                package_json_path: _,
+                workspace: None,
             } if script_name == "test" && script_body == "jest"
         ));
         assert_eq!(commands.test[0].description, None);
@@ -1138,32 +2583,221 @@ fmt:
             CommandExecutable::PackageManagerScript {
                 script_name,
                 script_body,
-                package_json_path: _
+                package_json_path: _,
+                workspace: None,
             } if script_name == "build" && script_body == "vite build"
         ));
         assert_eq!(commands.build[0].description, None);
     }
 
+    #[test]
+    fn test_npm_pre_and_post_scripts_fold_into_their_script() {
+        let content = r#"{
+  "scripts": {
+    "pretest": "eslint .",
+    "test": "jest",
+    "posttest": "rm -rf coverage",
+    "prebuild": "rimraf dist",
+    "build": "tsc -p .",
+    "prettier": "prettier --check ."
+  }
+}"#;
+        let commands = extract_npm_commands(content, Path::new("/test/package.json"), None);
+
+        let names: Vec<&str> = commands.all().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["test", "build", "prettier"]);
+        assert_eq!(
+            commands.test[0].description.as_deref(),
+            Some("Runs `pretest` first and `posttest` after")
+        );
+        assert_eq!(
+            commands.build[0].description.as_deref(),
+            Some("Runs `prebuild` first")
+        );
+        assert_eq!(commands.format[0].description, None);
+    }
+
+    #[test]
+    fn test_npm_install_lifecycle_scripts_are_setup() {
+        let content = r#"{
+  "scripts": {
+    "prepare": "husky",
+    "postprepare": "echo done",
+    "postinstall": "patch-package",
+    "test": "vitest run"
+  }
+}"#;
+        let commands = extract_npm_commands(content, Path::new("/test/package.json"), None);
+
+        let setup: Vec<&str> = commands.setup.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(setup, ["postinstall", "prepare"]);
+        assert_eq!(
+            commands.setup[1].description.as_deref(),
+            Some("Runs `postprepare` after")
+        );
+        let names: Vec<&str> = commands.all().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["test"]);
+    }
+
+    #[test]
+    fn test_npm_script_composition_is_followed() {
+        let content = r#"{
+  "scripts": {
+    "ci": "npm-run-all --parallel lint:* unit",
+    "verify": "run-s check:*",
+    "check:types": "tsc --noEmit",
+    "all": "concurrently -n a,b \"npm:unit\" \"yarn e2e\"",
+    "lint:js": "eslint .",
+    "lint:css": "stylelint **/*.css",
+    "unit": "node --test",
+    "e2e": "playwright test",
+    "loop": "npm run loop"
+  }
+}"#;
+        let commands = extract_npm_commands(content, Path::new("/test/package.json"), None);
+
+        let mut tests: Vec<&str> = commands.test.iter().map(|c| c.name.as_str()).collect();
+        tests.sort();
+        assert_eq!(tests, ["all", "ci", "e2e", "unit"]);
+        let ci = commands.test.iter().find(|c| c.name == "ci").unwrap();
+        assert_eq!(ci.confidence, Confidence::Medium);
+
+        let typecheck: Vec<&str> = commands.typecheck.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(typecheck, ["check:types", "verify"]);
+        let others: Vec<&str> = commands.other.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(others, ["loop"]);
+    }
+
     #[test]
     fn test_classify_command() {
         assert_eq!(classify_command("test"), CommandCategory::Test);
         assert_eq!(classify_command("unit-test"), CommandCategory::Test);
         assert_eq!(classify_command("build"), CommandCategory::Build);
         assert_eq!(classify_command("compile"), CommandCategory::Build);
-        assert_eq!(classify_command("dev"), CommandCategory::Other);
-        assert_eq!(classify_command("serve"), CommandCategory::Other);
-        assert_eq!(classify_command("lint"), CommandCategory::Other);
-        assert_eq!(classify_command("eslint"), CommandCategory::Other);
-        assert_eq!(classify_command("format"), CommandCategory::Other);
-        assert_eq!(classify_command("fmt"), CommandCategory::Other);
+        assert_eq!(classify_command("dev"), CommandCategory::Run);
+        assert_eq!(classify_command("serve"), CommandCategory::Run);
+        assert_eq!(classify_command("lint"), CommandCategory::Lint);
+        assert_eq!(classify_command("eslint"), CommandCategory::Lint);
+        assert_eq!(classify_command("format"), CommandCategory::Format);
+        assert_eq!(classify_command("fmt"), CommandCategory::Format);
+        assert_eq!(classify_command("typecheck"), CommandCategory::Typecheck);
+        assert_eq!(classify_command("bench"), CommandCategory::Bench);
         assert_eq!(classify_command("random"), CommandCategory::Other);
+
+        // Earlier categories win for compound names.
+        assert_eq!(classify_command("test:lint"), CommandCategory::Test);
+        assert_eq!(classify_command("lint:types"), CommandCategory::Lint);
+        assert_eq!(classify_command("build:watch"), CommandCategory::Build);
+        assert_eq!(classify_command("bench:build"), CommandCategory::Bench);
+    }
+
+    #[test]
+    fn test_classify_command_matches_whole_words() {
+        assert_eq!(classify_command("latest"), CommandCategory::Other);
+        assert_eq!(classify_command("prepackage"), CommandCategory::Other);
+        assert_eq!(classify_command("unitTest"), CommandCategory::Test);
+        assert_eq!(classify_command("checkTypes"), CommandCategory::Typecheck);
+        assert_eq!(classify_command("type-check"), CommandCategory::Typecheck);
+        assert_eq!(classify_command("devtools"), CommandCategory::Other);
+    }
+
+    #[test]
+    fn test_classify_requires_check_mode_for_format() {
+        let category = |name, body| classify(name, Some(body)).category;
+        assert_eq!(
+            category("format", "prettier --write ."),
+            CommandCategory::Other
+        );
+        assert_eq!(
+            category("format:check", "prettier --check ."),
+            CommandCategory::Format
+        );
+        assert_eq!(category("fmt", "gofmt -l ."), CommandCategory::Other);
+        assert_eq!(
+            category("fmt", "test -z \"$(gofmt -l .)\""),
+            CommandCategory::Format
+        );
+        assert_eq!(
+            classify("fmt", Some("test -z \"$(gofmt -l .)\"")).confidence,
+            Confidence::High
+        );
+        assert_eq!(category("fmt", "./scripts/fmt.sh"), CommandCategory::Other);
+        assert_eq!(category("lint", "eslint ."), CommandCategory::Lint);
+    }
+
+    #[test]
+    fn test_classify_by_body() {
+        let category = |name, body| classify(name, Some(body)).category;
+        assert_eq!(category("ci", "go test ./..."), CommandCategory::Test);
+        assert_eq!(
+            category("check", "cargo test --workspace"),
+            CommandCategory::Test
+        );
+        assert_eq!(category("verify", "npx vitest run"), CommandCategory::Test);
+        assert_eq!(
+            category("py", "poetry run python -m pytest -q"),
+            CommandCategory::Test
+        );
+        assert_eq!(category("all", "@CI=1 jest --ci"), CommandCategory::Test);
+        assert_eq!(
+            category("types", "tsc --noEmit"),
+            CommandCategory::Typecheck
+        );
+        assert_eq!(category("compile-ts", "tsc -p ."), CommandCategory::Build);
+        assert_eq!(
+            category("latest", "webpack --mode production"),
+            CommandCategory::Build
+        );
+        assert_eq!(category("webpack", "webpack serve"), CommandCategory::Run);
+        assert_eq!(category("test", "jest --watch"), CommandCategory::Run);
+        assert_eq!(
+            category("ci", "npm run lint && npm test"),
+            CommandCategory::Test
+        );
+        assert_eq!(category("verify", "make lint"), CommandCategory::Lint);
+
+        // Tool names only count as whole words at the start of a command.
+        assert_eq!(category("misc", "echo pytest"), CommandCategory::Other);
+        assert_eq!(category("misc", "./tscript.sh"), CommandCategory::Other);
+        assert_eq!(category("misc", "jester serve"), CommandCategory::Other);
+    }
+
+    #[test]
+    fn test_classification_confidence() {
+        let confidence = |name, body| classify(name, body).confidence;
+        assert_eq!(confidence("test", Some("pytest")), Confidence::High);
+        assert_eq!(confidence("ci", Some("pytest")), Confidence::Medium);
+        assert_eq!(confidence("test", None), Confidence::Medium);
+        assert_eq!(confidence("test", Some("./run.sh")), Confidence::Medium);
+        assert_eq!(confidence("build", Some("pytest")), Confidence::Low);
+        assert_eq!(confidence("misc", Some("./run.sh")), Confidence::Low);
+        assert_eq!(confidence("misc", None), Confidence::Low);
+    }
+
+    #[test]
+    fn test_makefile_targets_classified_by_recipe() {
+        let makefile = Makefile::parse(
+            "ci:\n\tgo vet ./...\n\tgo test ./...\n\nlatest:\n\tgit pull\n\nprepackage:\n\techo ok\n",
+        );
+        let commands = extract_makefile_commands(&makefile);
+
+        assert_eq!(direct_commands(&commands.test), ["make ci"]);
+        assert_eq!(commands.test[0].confidence, Confidence::Medium);
+        assert_eq!(
+            direct_commands(&commands.other),
+            ["make latest", "make prepackage"]
+        );
+        assert!(commands.build.is_empty());
+
+        let json = serde_json::to_string(&commands.test[0]).unwrap();
+        assert!(json.contains(r#""confidence":"medium""#));
     }
 
     #[test]
     fn test_extract_vite_commands() {
         let commands = get_vite_commands();
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 2);
+        assert_eq!(commands.run.len(), 2);
         assert_eq!(commands.test.len(), 0);
 
         assert_eq!(commands.build[0].name, "build");
@@ -1176,14 +2810,14 @@ fmt:
             Some("Build for production".to_string())
         );
 
-        let dev_cmd = commands.other.iter().find(|c| c.name == "dev").unwrap();
+        let dev_cmd = commands.run.iter().find(|c| c.name == "dev").unwrap();
         assert!(matches!(
             &dev_cmd.executable,
             CommandExecutable::Direct { command } if command == "vite"
         ));
         assert_eq!(dev_cmd.description, Some("Start dev server".to_string()));
 
-        let preview_cmd = commands.other.iter().find(|c| c.name == "preview").unwrap();
+        let preview_cmd = commands.run.iter().find(|c| c.name == "preview").unwrap();
         assert!(matches!(
             &preview_cmd.executable,
             CommandExecutable::Direct { command } if command == "vite preview"
@@ -1214,7 +2848,7 @@ fmt:
 
         assert_eq!(detection.task_runner, TaskRunner::Vite);
         assert_eq!(detection.commands.build.len(), 1);
-        assert_eq!(detection.commands.other.len(), 2);
+        assert_eq!(detection.commands.run.len(), 2);
         assert_eq!(detection.commands.test.len(), 0);
     }
 
@@ -1234,14 +2868,14 @@ fmt:
             Some("Build for production".to_string())
         );
 
-        let serve_cmd = commands.other.iter().find(|c| c.name == "serve").unwrap();
+        let serve_cmd = commands.run.iter().find(|c| c.name == "serve").unwrap();
         assert!(matches!(
             &serve_cmd.executable,
             CommandExecutable::Direct { command } if command == "webpack serve"
         ));
         assert_eq!(serve_cmd.description, Some("Start dev server".to_string()));
 
-        let watch_cmd = commands.other.iter().find(|c| c.name == "watch").unwrap();
+        let watch_cmd = commands.run.iter().find(|c| c.name == "watch").unwrap();
         assert!(matches!(
             &watch_cmd.executable,
             CommandExecutable::Direct { command } if command == "webpack watch"
@@ -1256,7 +2890,7 @@ fmt:
     fn test_extract_rspack_commands() {
         let commands = get_rspack_commands();
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 2);
+        assert_eq!(commands.run.len(), 2);
         assert_eq!(commands.test.len(), 0);
 
         assert_eq!(commands.build[0].name, "build");
@@ -1269,14 +2903,14 @@ fmt:
             Some("Build for production".to_string())
         );
 
-        let dev_cmd = commands.other.iter().find(|c| c.name == "dev").unwrap();
+        let dev_cmd = commands.run.iter().find(|c| c.name == "dev").unwrap();
         assert!(matches!(
             &dev_cmd.executable,
             CommandExecutable::Direct { command } if command == "rspack dev"
         ));
         assert_eq!(dev_cmd.description, Some("Start dev server".to_string()));
 
-        let preview_cmd = commands.other.iter().find(|c| c.name == "preview").unwrap();
+        let preview_cmd = commands.run.iter().find(|c| c.name == "preview").unwrap();
         assert!(matches!(
             &preview_cmd.executable,
             CommandExecutable::Direct { command } if command == "rspack preview"
@@ -1340,7 +2974,7 @@ fmt:
 
         assert_eq!(detection.task_runner, TaskRunner::Webpack);
         assert_eq!(detection.commands.build.len(), 1);
-        assert_eq!(detection.commands.other.len(), 2);
+        assert_eq!(detection.commands.run.len(), 2);
         assert_eq!(detection.commands.test.len(), 0);
     }
 
@@ -1353,7 +2987,7 @@ fmt:
 
         assert_eq!(detection.task_runner, TaskRunner::Rspack);
         assert_eq!(detection.commands.build.len(), 1);
-        assert_eq!(detection.commands.other.len(), 2);
+        assert_eq!(detection.commands.run.len(), 2);
         assert_eq!(detection.commands.test.len(), 0);
     }
 
@@ -1361,7 +2995,7 @@ fmt:
     fn test_extract_rollup_commands() {
         let commands = get_rollup_commands();
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.run.len(), 1);
         assert_eq!(commands.test.len(), 0);
 
         assert_eq!(commands.build[0].name, "build");
@@ -1374,7 +3008,7 @@ fmt:
             Some("Build bundle".to_string())
         );
 
-        let watch_cmd = commands.other.iter().find(|c| c.name == "watch").unwrap();
+        let watch_cmd = commands.run.iter().find(|c| c.name == "watch").unwrap();
         assert!(matches!(
             &watch_cmd.executable,
             CommandExecutable::Direct { command } if command == "rollup -c -w"
@@ -1405,7 +3039,7 @@ fmt:
 
         assert_eq!(detection.task_runner, TaskRunner::Rollup);
         assert_eq!(detection.commands.build.len(), 1);
-        assert_eq!(detection.commands.other.len(), 1);
+        assert_eq!(detection.commands.run.len(), 1);
         assert_eq!(detection.commands.test.len(), 0);
     }
 
@@ -1466,7 +3100,8 @@ fmt:
         let commands = extract_turbo_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 2);
+        assert_eq!(commands.run.len(), 1);
+        assert_eq!(commands.lint.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -1480,13 +3115,13 @@ fmt:
             CommandExecutable::Direct { command } if command == "turbo run build"
         ));
 
-        let dev_cmd = commands.other.iter().find(|c| c.name == "dev").unwrap();
+        let dev_cmd = commands.run.iter().find(|c| c.name == "dev").unwrap();
         assert!(matches!(
             &dev_cmd.executable,
             CommandExecutable::Direct { command } if command == "turbo run dev"
         ));
 
-        let lint_cmd = commands.other.iter().find(|c| c.name == "lint").unwrap();
+        let lint_cmd = commands.lint.iter().find(|c| c.name == "lint").unwrap();
         assert!(matches!(
             &lint_cmd.executable,
             CommandExecutable::Direct { command } if command == "turbo run lint"
@@ -1511,20 +3146,18 @@ fmt:
         let commands = extract_turbo_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.run.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert_eq!(commands.build[0].name, "build");
-        assert_eq!(commands.other[0].name, "dev");
+        assert_eq!(commands.run[0].name, "dev");
     }
 
     #[test]
     fn test_extract_turbo_commands_invalid_json() {
         let content = "not valid json";
         let commands = extract_turbo_commands(content);
-        assert!(commands.test.is_empty());
-        assert!(commands.build.is_empty());
-        assert!(commands.other.is_empty());
+        assert!(commands.is_empty());
     }
 
     #[test]
@@ -1563,7 +3196,110 @@ fmt:
         assert_eq!(detection.task_runner, TaskRunner::Turbo);
         assert_eq!(detection.commands.build.len(), 1);
         assert_eq!(detection.commands.test.len(), 1);
-        assert_eq!(detection.commands.other.len(), 2);
+        assert_eq!(detection.commands.lint.len(), 1);
+        assert_eq!(detection.commands.run.len(), 1);
+    }
+
+    #[test]
+    fn test_turbo_workspace_commands_per_package() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "root", "workspaces": ["packages/*"]}"#,
+        );
+        create_temp_file(
+            &dir,
+            "packages/ui/package.json",
+            r#"{"name": "ui", "scripts": {"build": "tsup", "check": "vitest run"}}"#,
+        );
+        create_temp_file(
+            &dir,
+            "packages/web/package.json",
+            r#"{"name": "web", "dependencies": {"ui": "*"}, "scripts": {"build": "next build", "dev": "next dev"}}"#,
+        );
+        let web_turbo = create_temp_file(
+            &dir,
+            "packages/web/turbo.json",
+            r#"{"extends": ["//"], "tasks": {"build": {"outputs": [".next/**"]}}}"#,
+        );
+        let path = create_temp_file(
+            &dir,
+            "turbo.json",
+            r#"{"tasks": {"build": {"dependsOn": ["^build"]}, "check": {"dependsOn": ["build"]}, "dev": {"persistent": true}}}"#,
+        );
+
+        assert!(TaskRunnerFile::try_from(web_turbo).is_err());
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(path).unwrap());
+        assert_eq!(
+            direct_commands(&detection.commands.build),
+            [
+                "turbo run build --filter=ui",
+                "turbo run build --filter=web"
+            ]
+        );
+        assert_eq!(detection.commands.build[1].name, "web#build");
+        assert_eq!(detection.commands.build[1].depends_on, ["ui#build"]);
+        assert_eq!(
+            direct_commands(&detection.commands.test),
+            ["turbo run check --filter=ui"]
+        );
+        assert_eq!(detection.commands.test[0].confidence, Confidence::Medium);
+        assert_eq!(
+            direct_commands(&detection.commands.run),
+            ["turbo run dev --filter=web"]
+        );
+
+        let graph = detection.task_graph.as_ref().unwrap();
+        assert_eq!(graph.packages.len(), 2);
+        assert_eq!(graph.task("web#build").unwrap().outputs, [".next/**"]);
+
+        let json = serde_json::to_string(&detection).unwrap();
+        assert!(json.contains(r#""task_graph":{"packages""#));
+    }
+
+    #[test]
+    fn test_nx_workspace_commands_per_project() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "libs/ui/project.json",
+            r#"{"name": "ui", "targets": {"build": {"executor": "@nx/js:tsc"}, "check": {"executor": "@nx/vite:test", "dependsOn": ["build"]}}}"#,
+        );
+        create_temp_file(
+            &dir,
+            "apps/web/project.json",
+            r#"{"name": "web", "implicitDependencies": ["ui"], "targets": {"build": {"command": "vite build"}, "dev": {"executor": "@nx/vite:dev-server", "continuous": true}}}"#,
+        );
+        let path = create_temp_file(
+            &dir,
+            "nx.json",
+            r#"{"targetDefaults": {"build": {"dependsOn": ["^build"]}}}"#,
+        );
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(path).unwrap());
+        assert_eq!(
+            direct_commands(&detection.commands.build),
+            ["nx run ui:build", "nx run web:build"]
+        );
+        assert_eq!(detection.commands.build[1].name, "web:build");
+        assert_eq!(detection.commands.build[1].depends_on, ["ui:build"]);
+        assert_eq!(detection.commands.build[1].confidence, Confidence::High);
+
+        // The executor tells what `check` runs.
+        assert_eq!(
+            direct_commands(&detection.commands.test),
+            ["nx run ui:check"]
+        );
+        assert_eq!(detection.commands.test[0].confidence, Confidence::Medium);
+        assert_eq!(direct_commands(&detection.commands.run), ["nx run web:dev"]);
+
+        let graph = detection.task_graph.as_ref().unwrap();
+        assert_eq!(
+            graph.task("ui:build").unwrap().executor.as_deref(),
+            Some("@nx/js:tsc")
+        );
     }
 
     #[test]
@@ -1589,7 +3325,8 @@ fmt:
         let commands = extract_nx_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 2);
+        assert_eq!(commands.lint.len(), 1);
+        assert_eq!(commands.other.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -1603,7 +3340,7 @@ fmt:
             CommandExecutable::Direct { command } if command == "nx run build"
         ));
 
-        assert!(commands.other.iter().any(|c| c.name == "lint"));
+        assert!(commands.lint.iter().any(|c| c.name == "lint"));
         assert!(commands.other.iter().any(|c| c.name == "e2e"));
     }
 
@@ -1627,7 +3364,7 @@ fmt:
         let commands = extract_nx_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.run.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -1641,7 +3378,7 @@ fmt:
             CommandExecutable::Direct { command } if command == "nx run build"
         ));
 
-        let serve_cmd = commands.other.iter().find(|c| c.name == "serve").unwrap();
+        let serve_cmd = commands.run.iter().find(|c| c.name == "serve").unwrap();
         assert!(matches!(
             &serve_cmd.executable,
             CommandExecutable::Direct { command } if command == "nx run serve"
@@ -1652,9 +3389,7 @@ fmt:
     fn test_extract_nx_commands_invalid_json() {
         let content = "not valid json";
         let commands = extract_nx_commands(content);
-        assert!(commands.test.is_empty());
-        assert!(commands.build.is_empty());
-        assert!(commands.other.is_empty());
+        assert!(commands.is_empty());
     }
 
     #[test]
@@ -1663,9 +3398,7 @@ fmt:
   "$schema": "./node_modules/nx/schemas/nx-schema.json"
 }"#;
         let commands = extract_nx_commands(content);
-        assert!(commands.test.is_empty());
-        assert!(commands.build.is_empty());
-        assert!(commands.other.is_empty());
+        assert!(commands.is_empty());
     }
 
     #[test]
@@ -1708,7 +3441,8 @@ fmt:
         assert_eq!(detection.task_runner, TaskRunner::Nx);
         assert_eq!(detection.commands.build.len(), 1);
         assert_eq!(detection.commands.test.len(), 1);
-        assert_eq!(detection.commands.other.len(), 2);
+        assert_eq!(detection.commands.lint.len(), 1);
+        assert_eq!(detection.commands.run.len(), 1);
     }
 
     #[test]
@@ -1743,7 +3477,7 @@ commands =
 "#;
         let commands = extract_tox_commands(content);
         assert_eq!(commands.test.len(), 2);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.lint.len(), 1);
 
         let py39_cmd = commands.test.iter().find(|c| c.name == "py39").unwrap();
         assert!(matches!(
@@ -1759,7 +3493,7 @@ commands =
         ));
         assert_eq!(py310_cmd.description, None);
 
-        let lint_cmd = commands.other.iter().find(|c| c.name == "lint").unwrap();
+        let lint_cmd = commands.lint.iter().find(|c| c.name == "lint").unwrap();
         assert!(matches!(
             &lint_cmd.executable,
             CommandExecutable::Direct { command } if command == "tox -e lint"
@@ -1885,7 +3619,7 @@ commands = flake8 src/
 
         assert_eq!(detection.task_runner, TaskRunner::Tox);
         assert_eq!(detection.commands.test.len(), 2);
-        assert_eq!(detection.commands.other.len(), 1);
+        assert_eq!(detection.commands.lint.len(), 1);
 
         assert!(detection.commands.test.iter().any(|c| c.name == "py39"
             && matches!(
@@ -1897,7 +3631,7 @@ commands = flake8 src/
                 &c.executable,
                 CommandExecutable::Direct { command } if command == "tox -e py310"
             )));
-        assert!(detection.commands.other.iter().any(|c| c.name == "lint"
+        assert!(detection.commands.lint.iter().any(|c| c.name == "lint"
             && matches!(
                 &c.executable,
                 CommandExecutable::Direct { command } if command == "tox -e lint"
@@ -1943,7 +3677,7 @@ def build(session):
         let commands = extract_nox_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.lint.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -1957,7 +3691,7 @@ def build(session):
             CommandExecutable::Direct { command } if command == "nox -s build"
         ));
 
-        let lint_cmd = commands.other.iter().find(|c| c.name == "lint").unwrap();
+        let lint_cmd = commands.lint.iter().find(|c| c.name == "lint").unwrap();
         assert!(matches!(
             &lint_cmd.executable,
             CommandExecutable::Direct { command } if command == "nox -s lint"
@@ -2047,7 +3781,7 @@ def lint(session):
 "#;
         let commands = extract_nox_commands(content);
         assert_eq!(commands.test.len(), 1);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.lint.len(), 1);
     }
 
     #[test]
@@ -2187,7 +3921,7 @@ def build(session):
         assert_eq!(detection.task_runner, TaskRunner::Nox);
         assert_eq!(detection.commands.test.len(), 1);
         assert_eq!(detection.commands.build.len(), 1);
-        assert_eq!(detection.commands.other.len(), 1);
+        assert_eq!(detection.commands.lint.len(), 1);
 
         assert!(detection.commands.test.iter().any(|c| c.name == "test"
             && matches!(
@@ -2199,7 +3933,7 @@ def build(session):
                 &c.executable,
                 CommandExecutable::Direct { command } if command == "nox -s build"
             )));
-        assert!(detection.commands.other.iter().any(|c| c.name == "lint"
+        assert!(detection.commands.lint.iter().any(|c| c.name == "lint"
             && matches!(
                 &c.executable,
                 CommandExecutable::Direct { command } if command == "nox -s lint"
@@ -2248,7 +3982,8 @@ def clean(c):
         let commands = extract_invoke_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 2);
+        assert_eq!(commands.lint.len(), 1);
+        assert_eq!(commands.other.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -2262,7 +3997,7 @@ def clean(c):
             CommandExecutable::Direct { command } if command == "invoke build"
         ));
 
-        let lint_cmd = commands.other.iter().find(|c| c.name == "lint").unwrap();
+        let lint_cmd = commands.lint.iter().find(|c| c.name == "lint").unwrap();
         assert!(matches!(
             &lint_cmd.executable,
             CommandExecutable::Direct { command } if command == "invoke lint"
@@ -2362,7 +4097,7 @@ tasks:
         let commands = extract_invoke_yaml_commands(content);
         assert_eq!(commands.test.len(), 1);
         assert_eq!(commands.build.len(), 1);
-        assert_eq!(commands.other.len(), 1);
+        assert_eq!(commands.lint.len(), 1);
 
         assert_eq!(commands.test[0].name, "test");
         assert!(matches!(
@@ -2376,7 +4111,7 @@ tasks:
             CommandExecutable::Direct { command } if command == "invoke build"
         ));
 
-        let lint_cmd = commands.other.iter().find(|c| c.name == "lint").unwrap();
+        let lint_cmd = commands.lint.iter().find(|c| c.name == "lint").unwrap();
         assert!(matches!(
             &lint_cmd.executable,
             CommandExecutable::Direct { command } if command == "invoke lint"
@@ -2390,18 +4125,14 @@ tasks:
 other_config: value
 "#;
         let commands = extract_invoke_yaml_commands(content);
-        assert!(commands.test.is_empty());
-        assert!(commands.build.is_empty());
-        assert!(commands.other.is_empty());
+        assert!(commands.is_empty());
     }
 
     #[test]
     fn test_extract_invoke_yaml_commands_invalid() {
         let content = "not valid yaml: [";
         let commands = extract_invoke_yaml_commands(content);
-        assert!(commands.test.is_empty());
-        assert!(commands.build.is_empty());
-        assert!(commands.other.is_empty());
+        assert!(commands.is_empty());
     }
 
     #[test]
@@ -2472,7 +4203,7 @@ def build(c):
         assert_eq!(detection.task_runner, TaskRunner::Invoke);
         assert_eq!(detection.commands.test.len(), 1);
         assert_eq!(detection.commands.build.len(), 1);
-        assert_eq!(detection.commands.other.len(), 1);
+        assert_eq!(detection.commands.lint.len(), 1);
 
         assert!(detection.commands.test.iter().any(|c| c.name == "test"
             && matches!(
@@ -2484,10 +4215,362 @@ def build(c):
                 &c.executable,
                 CommandExecutable::Direct { command } if command == "invoke build"
             )));
-        assert!(detection.commands.other.iter().any(|c| c.name == "lint"
+        assert!(detection.commands.lint.iter().any(|c| c.name == "lint"
             && matches!(
                 &c.executable,
                 CommandExecutable::Direct { command } if command == "invoke lint"
             )));
     }
+
+    fn direct_commands(commands: &[TaskCommand]) -> Vec<&str> {
+        commands
+            .iter()
+            .filter_map(|c| match &c.executable {
+                CommandExecutable::Direct { command } => Some(command.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_hatch_commands() {
+        let content = r#"
+[envs.default.scripts]
+test = "pytest {args}"
+cov = ["coverage run -m pytest", "coverage report"]
+
+[envs.docs.scripts]
+build = "mkdocs build"
+"#;
+        let commands = extract_hatch_commands(content, &["envs"]);
+        assert_eq!(direct_commands(&commands.test), ["hatch run test"]);
+        assert_eq!(direct_commands(&commands.build), ["hatch run docs:build"]);
+        let cov = commands.other.iter().find(|c| c.name == "cov").unwrap();
+        assert_eq!(
+            cov.description.as_deref(),
+            Some("coverage run -m pytest && coverage report")
+        );
+    }
+
+    #[test]
+    fn test_extract_pixi_commands() {
+        let content = r#"
+[tasks]
+test = "pytest"
+build = { cmd = ["python", "-m", "build"], description = "Build wheels" }
+ci = { depends-on = ["test", "build"] }
+
+[feature.lint.tasks]
+lint = "ruff check ."
+"#;
+        let commands = extract_pixi_commands(content, &[]);
+        assert_eq!(direct_commands(&commands.test), ["pixi run test"]);
+        assert_eq!(direct_commands(&commands.build), ["pixi run build"]);
+        assert_eq!(
+            commands.build[0].description.as_deref(),
+            Some("Build wheels")
+        );
+        assert_eq!(direct_commands(&commands.lint), ["pixi run lint"]);
+        assert_eq!(direct_commands(&commands.other), ["pixi run ci"]);
+    }
+
+    #[test]
+    fn test_pyproject_task_sources() {
+        let dir = TempDir::new().unwrap();
+        let hatch = create_temp_file(
+            &dir,
+            "hatch/pyproject.toml",
+            "[tool.hatch.envs.test.scripts]\nrun = \"pytest\"\n",
+        );
+        let pixi = create_temp_file(
+            &dir,
+            "pixi/pyproject.toml",
+            "[tool.pixi.tasks]\ntest = \"pytest\"\n",
+        );
+        let plain = create_temp_file(&dir, "plain/pyproject.toml", "[project]\nname = \"x\"\n");
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(hatch).unwrap());
+        assert_eq!(detection.task_runner, TaskRunner::Hatch);
+        assert_eq!(
+            direct_commands(&detection.commands.test),
+            ["hatch run test:run"]
+        );
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(pixi).unwrap());
+        assert_eq!(detection.task_runner, TaskRunner::Pixi);
+        assert_eq!(direct_commands(&detection.commands.test), ["pixi run test"]);
+
+        assert!(TaskRunnerFile::try_from(plain).is_err());
+    }
+
+    #[test]
+    fn test_workspace_glob_matches() {
+        assert!(workspace_glob_matches(
+            "packages/*",
+            Path::new("packages/web")
+        ));
+        assert!(!workspace_glob_matches(
+            "packages/*",
+            Path::new("packages/web/ui")
+        ));
+        assert!(workspace_glob_matches("./apps/**", Path::new("apps/a/b")));
+        assert!(workspace_glob_matches(
+            "libs/**/core",
+            Path::new("libs/core")
+        ));
+        assert!(workspace_glob_matches("tools/cli", Path::new("tools/cli")));
+        assert!(!in_workspace_globs(
+            &["packages/*".to_string(), "!packages/legacy".to_string()],
+            Path::new("packages/legacy")
+        ));
+    }
+
+    #[test]
+    fn test_js_workspace_members() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "root", "workspaces": {"packages": ["packages/*"]}}"#,
+        );
+        let member = create_temp_file(
+            &dir,
+            "packages/web/package.json",
+            r#"{"name": "@acme/web", "scripts": {"test": "vitest"}}"#,
+        );
+        let outsider = create_temp_file(
+            &dir,
+            "examples/demo/package.json",
+            r#"{"name": "demo", "scripts": {"test": "vitest"}}"#,
+        );
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(member).unwrap());
+        let CommandExecutable::PackageManagerScript { workspace, .. } =
+            &detection.commands.test[0].executable
+        else {
+            panic!("expected a package manager script");
+        };
+        assert_eq!(
+            workspace.as_ref(),
+            Some(&WorkspaceMember {
+                root: dir.path().to_path_buf(),
+                name: "@acme/web".to_string(),
+            })
+        );
+
+        let file = TaskRunnerFile::try_from(outsider).unwrap();
+        assert_eq!(file.workspace, None);
+    }
+
+    #[test]
+    fn test_pnpm_workspace_yaml_members() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "package.json", r#"{"name": "root"}"#);
+        create_temp_file(
+            &dir,
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'apps/**'\n  - '!apps/legacy'\n",
+        );
+        let member = create_temp_file(&dir, "apps/site/package.json", r#"{"name": "site"}"#);
+        let excluded = create_temp_file(&dir, "apps/legacy/package.json", r#"{"name": "old"}"#);
+
+        let file = TaskRunnerFile::try_from(member).unwrap();
+        assert_eq!(file.workspace.map(|m| m.name).as_deref(), Some("site"));
+        let file = TaskRunnerFile::try_from(excluded).unwrap();
+        assert_eq!(file.workspace, None);
+    }
+
+    #[test]
+    fn test_cargo_single_package_commands() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(
+            &dir,
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\n\n[features]\nserde = []\n\n[[bin]]\nname = \"demo\"\n",
+        );
+        create_temp_file(&dir, "src/lib.rs", "");
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(path).unwrap());
+        assert_eq!(
+            direct_commands(&detection.commands.test),
+            [
+                "cargo test --all-targets",
+                "cargo test --doc",
+                "cargo test --all-features"
+            ]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.build),
+            [
+                "cargo build",
+                "cargo build --release",
+                "cargo build --all-features"
+            ]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.lint),
+            ["cargo clippy --all-targets"]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.format),
+            ["cargo fmt --check"]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.run),
+            ["cargo run --bin demo"]
+        );
+        assert_eq!(direct_commands(&detection.commands.other), ["cargo check"]);
+    }
+
+    #[test]
+    fn test_cargo_workspace_target_names_per_member() {
+        let dir = TempDir::new().unwrap();
+        let root = create_temp_file(
+            &dir,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"api\", \"worker\"]\n",
+        );
+        for member in ["api", "worker"] {
+            create_temp_file(
+                &dir,
+                &format!("{member}/Cargo.toml"),
+                &format!(
+                    "[package]\nname = \"{member}\"\n\n[[bin]]\nname = \"server\"\n\n[[example]]\nname = \"demo\"\n\n[[bench]]\nname = \"speed\"\n"
+                ),
+            );
+        }
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(root).unwrap());
+        let names = |commands: &[TaskCommand]| -> Vec<String> {
+            commands.iter().map(|c| c.name.clone()).collect()
+        };
+        assert_eq!(
+            names(&detection.commands.run),
+            [
+                "run-server-api",
+                "example-demo-api",
+                "run-server-worker",
+                "example-demo-worker"
+            ]
+        );
+        assert_eq!(
+            names(&detection.commands.bench),
+            ["bench-speed-api", "bench-speed-worker"]
+        );
+    }
+
+    #[test]
+    fn test_cargo_workspace_commands() {
+        let dir = TempDir::new().unwrap();
+        let root = create_temp_file(
+            &dir,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        let member = create_temp_file(
+            &dir,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\n\n[package.metadata.docs.rs]\nfeatures = [\"a\", \"b\"]\n",
+        );
+        create_temp_file(&dir, "crates/core/src/lib.rs", "");
+        create_temp_file(
+            &dir,
+            "crates/app/Cargo.toml",
+            "[package]\nname = \"app\"\n\n[[example]]\nname = \"hello\"\n",
+        );
+
+        assert!(TaskRunnerFile::try_from(member).is_err());
+
+        let detection = TaskRunnerDetection::from(TaskRunnerFile::try_from(root).unwrap());
+        assert_eq!(
+            direct_commands(&detection.commands.test),
+            [
+                "cargo test -p app --all-targets",
+                "cargo test -p core --all-targets",
+                "cargo test -p core --doc"
+            ]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.build),
+            [
+                "cargo doc -p core --no-deps --features a,b",
+                "cargo build --workspace",
+                "cargo build --workspace --release"
+            ]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.run),
+            ["cargo run -p app --example hello"]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.lint),
+            ["cargo clippy --workspace --all-targets"]
+        );
+        assert_eq!(
+            direct_commands(&detection.commands.format),
+            ["cargo fmt --all --check"]
+        );
+    }
+
+    #[test]
+    fn test_go_mod_commands() {
+        let commands = get_go_commands();
+        assert_eq!(direct_commands(&commands.lint), ["go vet ./..."]);
+        assert_eq!(
+            direct_commands(&commands.format),
+            ["test -z \"$(gofmt -l .)\""]
+        );
+        assert_eq!(direct_commands(&commands.run), ["go run ."]);
+        assert!(commands.other.is_empty());
+    }
+
+    #[test]
+    fn test_go_work_uses() {
+        let content =
+            "go 1.22\n\nuse ./tools // helpers\n\nuse (\n\t.\n\t./services/api/\n\t\"./web\"\n)\n";
+
+        assert_eq!(go_work_uses(content), ["tools", ".", "services/api", "web"]);
+    }
+
+    #[test]
+    fn test_go_work_commands_per_module() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(
+            &dir,
+            "go.work",
+            "go 1.22\n\nuse (\n\t.\n\t./services/api\n)\n",
+        );
+
+        let file = TaskRunnerFile::try_from(path).unwrap();
+        let detection = TaskRunnerDetection::from(file);
+
+        assert_eq!(detection.task_runner, TaskRunner::GoTask);
+        assert_eq!(detection.source, TaskRunnerSource::GoWork);
+        let tests: Vec<(&str, &CommandExecutable)> = detection
+            .commands
+            .test
+            .iter()
+            .map(|c| (c.name.as_str(), &c.executable))
+            .collect();
+        assert_eq!(tests.len(), 2);
+        assert!(tests.iter().any(|(name, exec)| *name == "test-root"
+            && matches!(exec, CommandExecutable::Direct { command } if command == "go test ./...")));
+        assert!(tests.iter().any(|(name, exec)| *name == "test-services-api"
+            && matches!(exec, CommandExecutable::Direct { command } if command == "go test ./services/api/...")));
+        assert!(detection.commands.build.iter().any(|c| matches!(
+            &c.executable,
+            CommandExecutable::Direct { command } if command == "go build ./services/api/..."
+        )));
+    }
+
+    #[test]
+    fn test_go_mod_inside_workspace_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "go.work", "go 1.22\n\nuse ./api\n");
+        let api = create_temp_file(&dir, "api/go.mod", "module example.com/api\n\ngo 1.22\n");
+        let tool = create_temp_file(&dir, "tool/go.mod", "module example.com/tool\n\ngo 1.22\n");
+
+        assert!(TaskRunnerFile::try_from(api).is_err());
+        assert!(TaskRunnerFile::try_from(tool).is_ok());
+    }
 }
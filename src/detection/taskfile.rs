@@ -0,0 +1,494 @@
+use super::FileSystem;
+use serde::Serialize;
+use serde_yaml::Value as YamlValue;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// File names Task looks for, in the order it prefers them.
+pub const TASKFILE_NAMES: &[&str] = &[
+    "Taskfile.yml",
+    "taskfile.yml",
+    "Taskfile.yaml",
+    "taskfile.yaml",
+    "Taskfile.dist.yml",
+    "taskfile.dist.yml",
+    "Taskfile.dist.yaml",
+    "taskfile.dist.yaml",
+];
+
+/// Operating systems a `platforms:` entry can name; anything else is an
+/// architecture.
+const GOOS: &[&str] = &[
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "illumos",
+    "ios",
+    "js",
+    "linux",
+    "netbsd",
+    "openbsd",
+    "plan9",
+    "solaris",
+    "wasip1",
+    "windows",
+];
+
+/// A task from a Taskfile or one of its includes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskfileTask {
+    /// The name as passed to `task`, with included tasks written `ns:name`.
+    pub name: String,
+    /// `desc`, or the first line of `summary`.
+    pub description: Option<String>,
+    pub deps: Vec<String>,
+    /// Where Task runs the commands, relative to the root Taskfile, when that
+    /// is not the root Taskfile's own directory.
+    pub dir: Option<PathBuf>,
+    pub internal: bool,
+    pub platforms: Vec<String>,
+    /// `requires: vars` that no `vars:` block defines.
+    pub required_vars: Vec<String>,
+    pub cmds: Vec<String>,
+}
+
+impl TaskfileTask {
+    pub fn runs_on_linux(&self) -> bool {
+        self.platforms.is_empty()
+            || self.platforms.iter().any(|platform| {
+                let os = platform.split('/').next().unwrap_or("");
+                os == "linux" || !GOOS.contains(&os)
+            })
+    }
+}
+
+/// The tasks of a Taskfile, including those of the Taskfiles it includes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Taskfile {
+    pub tasks: Vec<TaskfileTask>,
+    /// Included Taskfiles that were found and read.
+    pub includes: Vec<PathBuf>,
+}
+
+impl Taskfile {
+    pub fn from_path(fs: &dyn FileSystem, path: &Path) -> Option<Self> {
+        let content = fs.read_to_string(path).ok()?;
+        let yaml = serde_yaml::from_str::<YamlValue>(&content).ok()?;
+        let mut parser = Parser {
+            fs: Some(fs),
+            visited: HashSet::from([path.to_path_buf()]),
+            ..Parser::default()
+        };
+        parser.load(&yaml, path.parent(), &Scope::default());
+        Some(Taskfile {
+            tasks: parser.tasks,
+            includes: parser.includes,
+        })
+    }
+
+    /// Parses a single Taskfile; includes are ignored.
+    pub fn parse(content: &str) -> Option<Self> {
+        let yaml = serde_yaml::from_str::<YamlValue>(content).ok()?;
+        let mut parser = Parser::default();
+        parser.load(&yaml, None, &Scope::default());
+        Some(Taskfile {
+            tasks: parser.tasks,
+            includes: parser.includes,
+        })
+    }
+
+    /// Tasks that can be called from the command line on Linux.
+    pub fn runnable_tasks(&self) -> impl Iterator<Item = &TaskfileTask> {
+        self.tasks
+            .iter()
+            .filter(|t| !t.internal && t.runs_on_linux())
+    }
+}
+
+/// Whether `path` is shadowed by a Taskfile Task prefers in the same
+/// directory, such as `Taskfile.dist.yml` next to `Taskfile.yml`.
+pub fn shadowed_taskfile(path: &Path, fs: &dyn FileSystem) -> bool {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return false;
+    };
+    // Compare listed names, so case-insensitive file systems don't match a
+    // file against itself.
+    let siblings: Vec<_> = fs
+        .read_dir(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|p| p.file_name().map(|n| n.to_os_string()))
+        .collect();
+    TASKFILE_NAMES
+        .iter()
+        .take_while(|preferred| **preferred != name)
+        .any(|preferred| siblings.iter().any(|s| s == preferred))
+}
+
+/// Whether a Taskfile in an ancestor directory includes `path`, which then
+/// runs its tasks under a namespace instead.
+pub fn included_by_parent_taskfile(path: &Path, fs: &dyn FileSystem) -> bool {
+    let Some(dir) = path.parent() else {
+        return false;
+    };
+    dir.ancestors().skip(1).any(|ancestor| {
+        TASKFILE_NAMES
+            .iter()
+            .map(|name| ancestor.join(name))
+            .find(|candidate| fs.is_file(candidate))
+            .and_then(|root| Taskfile::from_path(fs, &root))
+            .is_some_and(|taskfile| taskfile.includes.iter().any(|p| p == path))
+    })
+}
+
+/// Where included tasks end up: their namespace, their directory relative to
+/// the root Taskfile, and options inherited from the `includes:` entry.
+#[derive(Default, Clone)]
+struct Scope {
+    prefix: String,
+    dir: PathBuf,
+    internal: bool,
+    excludes: Vec<String>,
+}
+
+#[derive(Default)]
+struct Parser<'a> {
+    fs: Option<&'a dyn FileSystem>,
+    visited: HashSet<PathBuf>,
+    tasks: Vec<TaskfileTask>,
+    includes: Vec<PathBuf>,
+}
+
+impl Parser<'_> {
+    fn load(&mut self, yaml: &YamlValue, file_dir: Option<&Path>, scope: &Scope) {
+        let global_vars = var_names(yaml.get("vars"));
+
+        if let Some(tasks) = yaml.get("tasks").and_then(|t| t.as_mapping()) {
+            for (name, task) in tasks {
+                let Some(name) = name.as_str() else {
+                    continue;
+                };
+                if scope.excludes.iter().any(|e| e == name) {
+                    continue;
+                }
+                let mut task = parse_task(name, task, scope, &global_vars);
+                if !self.tasks.iter().any(|t| t.name == task.name) {
+                    task.internal |= scope.internal;
+                    self.tasks.push(task);
+                }
+            }
+        }
+
+        let Some(includes) = yaml.get("includes").and_then(|i| i.as_mapping()) else {
+            return;
+        };
+        for (namespace, spec) in includes {
+            let Some(namespace) = namespace.as_str() else {
+                continue;
+            };
+            self.include(namespace, spec, file_dir, scope);
+        }
+    }
+
+    fn include(
+        &mut self,
+        namespace: &str,
+        spec: &YamlValue,
+        file_dir: Option<&Path>,
+        scope: &Scope,
+    ) {
+        let (Some(fs), Some(file_dir)) = (self.fs, file_dir) else {
+            return;
+        };
+        let taskfile = spec
+            .as_str()
+            .or_else(|| spec.get("taskfile")?.as_str())
+            .unwrap_or("");
+        // Templated and remote Taskfiles can't be resolved here.
+        if taskfile.is_empty() || taskfile.contains("{{") || taskfile.contains("://") {
+            return;
+        }
+
+        let target = file_dir.join(taskfile);
+        let path = if fs.is_dir(&target) {
+            match TASKFILE_NAMES
+                .iter()
+                .map(|name| target.join(name))
+                .find(|p| fs.is_file(p))
+            {
+                Some(path) => path,
+                None => return,
+            }
+        } else {
+            target
+        };
+        if !self.visited.insert(path.clone()) {
+            return;
+        }
+        let Some(yaml) = fs
+            .read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<YamlValue>(&content).ok())
+        else {
+            return;
+        };
+        self.includes.push(path.clone());
+
+        let flag = |key: &str| spec.get(key).and_then(|v| v.as_bool()) == Some(true);
+        let dir = spec
+            .get("dir")
+            .and_then(|d| d.as_str())
+            .filter(|d| !d.contains("{{"));
+        let child = Scope {
+            prefix: if flag("flatten") {
+                scope.prefix.clone()
+            } else {
+                format!("{}{namespace}:", scope.prefix)
+            },
+            dir: match dir {
+                Some(dir) => normalize(&scope.dir.join(dir)),
+                None => scope.dir.clone(),
+            },
+            internal: scope.internal || flag("internal"),
+            excludes: string_list(spec.get("excludes")),
+        };
+        self.load(&yaml, path.parent(), &child);
+    }
+}
+
+fn parse_task(
+    name: &str,
+    task: &YamlValue,
+    scope: &Scope,
+    global_vars: &HashSet<String>,
+) -> TaskfileTask {
+    let qualified = |task: &str| format!("{}{task}", scope.prefix);
+
+    // `task: cmd` and `task: [cmd, ...]` are shorthand for `cmds`.
+    let cmds = match task {
+        YamlValue::String(cmd) => vec![cmd.clone()],
+        YamlValue::Sequence(_) => string_list(Some(task)),
+        _ => task
+            .get("cmds")
+            .and_then(|c| c.as_sequence())
+            .into_iter()
+            .flatten()
+            .filter_map(|cmd| cmd.as_str().or_else(|| cmd.get("cmd")?.as_str()))
+            .map(str::to_string)
+            .collect(),
+    };
+
+    let description = task
+        .get("desc")
+        .and_then(|d| d.as_str())
+        .or_else(|| task.get("summary")?.as_str()?.lines().next())
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+
+    let deps = task
+        .get("deps")
+        .and_then(|d| d.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| dep.as_str().or_else(|| dep.get("task")?.as_str()))
+        .map(qualified)
+        .collect();
+
+    let dir = match task.get("dir").and_then(|d| d.as_str()) {
+        Some(dir) if !dir.contains("{{") => normalize(&scope.dir.join(dir)),
+        Some(_) => PathBuf::new(),
+        None => scope.dir.clone(),
+    };
+
+    let defined_vars = var_names(task.get("vars"));
+    let required_vars = task
+        .get("requires")
+        .and_then(|r| r.get("vars"))
+        .and_then(|v| v.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|var| var.as_str().or_else(|| var.get("name")?.as_str()))
+        .filter(|var| !global_vars.contains(*var) && !defined_vars.contains(*var))
+        .map(str::to_string)
+        .collect();
+
+    TaskfileTask {
+        name: qualified(name),
+        description,
+        deps,
+        dir: (!dir.as_os_str().is_empty()).then_some(dir),
+        internal: task.get("internal").and_then(|i| i.as_bool()) == Some(true),
+        platforms: string_list(task.get("platforms")),
+        required_vars,
+        cmds,
+    }
+}
+
+fn var_names(vars: Option<&YamlValue>) -> HashSet<String> {
+    vars.and_then(|v| v.as_mapping())
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, _)| name.as_str().map(str::to_string))
+        .collect()
+}
+
+fn string_list(value: Option<&YamlValue>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Resolves `.` and `..` components without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component.as_os_str().to_str() {
+            Some(".") => {}
+            Some("..") if out.file_name().is_some() => {
+                out.pop();
+            }
+            _ => out.push(component),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn names(taskfile: &Taskfile) -> Vec<&str> {
+        taskfile.runnable_tasks().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_task_fields() {
+        let taskfile = Taskfile::parse(
+            r#"
+version: '3'
+vars:
+  REGISTRY: ghcr.io
+tasks:
+  build:
+    desc: Build the binary
+    deps: [generate, {task: fetch}]
+    dir: ./cmd/app
+    cmds:
+      - go build ./...
+  generate:
+    internal: true
+    cmds: [go generate ./...]
+  fetch: go mod download
+  push:
+    summary: |
+      Push the image.
+
+      Needs a tag.
+    requires:
+      vars: [TAG, REGISTRY]
+    cmds:
+      - docker push {{.REGISTRY}}/app:{{.TAG}}
+  notarize:
+    platforms: [darwin]
+    cmds: [xcrun notarytool submit app]
+  release:
+    platforms: [linux/amd64, windows]
+    cmds: [goreleaser]
+"#,
+        )
+        .unwrap();
+        assert_eq!(names(&taskfile), ["build", "fetch", "push", "release"]);
+
+        let build = &taskfile.tasks[0];
+        assert_eq!(build.description.as_deref(), Some("Build the binary"));
+        assert_eq!(build.deps, ["generate", "fetch"]);
+        assert_eq!(build.dir, Some(PathBuf::from("cmd/app")));
+        assert_eq!(build.cmds, ["go build ./..."]);
+
+        let push = taskfile.tasks.iter().find(|t| t.name == "push").unwrap();
+        assert_eq!(push.description.as_deref(), Some("Push the image."));
+        assert_eq!(push.required_vars, ["TAG"]);
+    }
+
+    #[test]
+    fn test_includes() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::create_dir_all(dir.path().join("tasks")).unwrap();
+        fs::write(
+            dir.path().join("Taskfile.yml"),
+            r#"
+version: '3'
+includes:
+  docs: ./docs
+  docker:
+    taskfile: ./tasks/Docker.yml
+    dir: ./deploy
+  shared:
+    taskfile: ./tasks/Shared.yml
+    internal: true
+  extra:
+    taskfile: ./missing.yml
+    optional: true
+tasks:
+  test: go test ./...
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("docs/Taskfile.yml"),
+            "version: '3'\ntasks:\n  serve: mkdocs serve\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("tasks/Docker.yml"),
+            "version: '3'\ntasks:\n  build:\n    deps: [login]\n    cmds: [docker build .]\n  login: docker login\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("tasks/Shared.yml"),
+            "version: '3'\ntasks:\n  setup: echo setup\n",
+        )
+        .unwrap();
+
+        let root = dir.path().join("Taskfile.yml");
+        let taskfile = Taskfile::from_path(&LocalFileSystem, &root).unwrap();
+        assert_eq!(
+            names(&taskfile),
+            ["test", "docs:serve", "docker:build", "docker:login"]
+        );
+        let docker_build = &taskfile.tasks[2];
+        assert_eq!(docker_build.deps, ["docker:login"]);
+        assert_eq!(docker_build.dir, Some(PathBuf::from("deploy")));
+
+        assert!(included_by_parent_taskfile(
+            &dir.path().join("docs/Taskfile.yml"),
+            &LocalFileSystem
+        ));
+        assert!(!included_by_parent_taskfile(&root, &LocalFileSystem));
+    }
+
+    #[test]
+    fn test_shadowed_taskfile() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Taskfile.yml"), "version: '3'\n").unwrap();
+        fs::write(dir.path().join("Taskfile.dist.yml"), "version: '3'\n").unwrap();
+
+        assert!(!shadowed_taskfile(
+            &dir.path().join("Taskfile.yml"),
+            &LocalFileSystem
+        ));
+        assert!(shadowed_taskfile(
+            &dir.path().join("Taskfile.dist.yml"),
+            &LocalFileSystem
+        ));
+    }
+}
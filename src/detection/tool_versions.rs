@@ -0,0 +1,320 @@
+use super::{
+    FileSystem, Language, LanguageDetectionSignal, LanguageDetectionSource, VersionInfo,
+    VersionSource, version::parse_version_or_expression,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A tool pinned by a version manager file: asdf's `.tool-versions` or mise's
+/// `mise.toml` / `.mise.toml`.
+///
+/// Tools outside the built-in languages are reported as
+/// [`Language::Custom`] under their mise name, so a registered
+/// [`LanguageGenerator`](crate::generation::LanguageGenerator) can pick them up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ManagedTool {
+    Go,
+    Node,
+    Bun,
+    Deno,
+    Python,
+    Rust,
+    Ruby,
+    Java,
+    Elixir,
+    Erlang,
+    Zig,
+}
+
+impl ManagedTool {
+    /// Resolves asdf plugin names (`golang`, `nodejs`) and mise tool names
+    /// (`go`, `node`, `core:node`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name
+            .strip_prefix("core:")
+            .or_else(|| name.strip_prefix("asdf:"))
+            .unwrap_or(name);
+
+        match name {
+            "go" | "golang" => Some(ManagedTool::Go),
+            "node" | "nodejs" => Some(ManagedTool::Node),
+            "bun" => Some(ManagedTool::Bun),
+            "deno" => Some(ManagedTool::Deno),
+            "python" => Some(ManagedTool::Python),
+            "rust" => Some(ManagedTool::Rust),
+            "ruby" => Some(ManagedTool::Ruby),
+            "java" => Some(ManagedTool::Java),
+            "elixir" => Some(ManagedTool::Elixir),
+            "erlang" => Some(ManagedTool::Erlang),
+            "zig" => Some(ManagedTool::Zig),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ManagedTool::Go => "go",
+            ManagedTool::Node => "node",
+            ManagedTool::Bun => "bun",
+            ManagedTool::Deno => "deno",
+            ManagedTool::Python => "python",
+            ManagedTool::Rust => "rust",
+            ManagedTool::Ruby => "ruby",
+            ManagedTool::Java => "java",
+            ManagedTool::Elixir => "elixir",
+            ManagedTool::Erlang => "erlang",
+            ManagedTool::Zig => "zig",
+        }
+    }
+
+    pub fn language(self) -> Language {
+        match self {
+            ManagedTool::Go => Language::Go,
+            ManagedTool::Node | ManagedTool::Bun | ManagedTool::Deno => Language::JavaScript,
+            ManagedTool::Python => Language::Python,
+            ManagedTool::Rust => Language::Rust,
+            ManagedTool::Ruby
+            | ManagedTool::Java
+            | ManagedTool::Elixir
+            | ManagedTool::Erlang
+            | ManagedTool::Zig => Language::Custom(self.name()),
+        }
+    }
+}
+
+/// One strong signal per tool pinned in a version manager file at `path`.
+pub fn tool_version_signals(path: &Path, fs: &dyn FileSystem) -> Vec<LanguageDetectionSignal> {
+    let Some(filename) = path.file_name().and_then(|f| f.to_str()) else {
+        return vec![];
+    };
+    let source: fn(ManagedTool) -> LanguageDetectionSource = match filename {
+        ".tool-versions" => LanguageDetectionSource::ToolVersions,
+        "mise.toml" | ".mise.toml" => LanguageDetectionSource::MiseToml,
+        _ => return vec![],
+    };
+
+    let mut tools: Vec<ManagedTool> = Vec::new();
+    for (tool, _) in read_entries(fs, path) {
+        if !tools.contains(&tool) {
+            tools.push(tool);
+        }
+    }
+
+    tools
+        .into_iter()
+        .map(|tool| LanguageDetectionSignal::Strong {
+            path: path.to_path_buf(),
+            source: source(tool),
+        })
+        .collect()
+}
+
+pub(crate) fn parse_tool_versions(
+    fs: &dyn FileSystem,
+    path: &Path,
+    tool: ManagedTool,
+) -> Vec<VersionInfo> {
+    versions_for(fs, path, tool, VersionSource::ToolVersionsFile(tool))
+}
+
+pub(crate) fn parse_mise_toml(
+    fs: &dyn FileSystem,
+    path: &Path,
+    tool: ManagedTool,
+) -> Vec<VersionInfo> {
+    versions_for(fs, path, tool, VersionSource::MiseToml(tool))
+}
+
+fn versions_for(
+    fs: &dyn FileSystem,
+    path: &Path,
+    tool: ManagedTool,
+    source: VersionSource,
+) -> Vec<VersionInfo> {
+    read_entries(fs, path)
+        .into_iter()
+        .filter(|(entry_tool, version)| *entry_tool == tool && !is_non_version(version))
+        .map(|(_, version)| VersionInfo {
+            parsed: parse_version_or_expression(&version),
+            raw: version,
+            source,
+            path: path.to_path_buf(),
+        })
+        .collect()
+}
+
+/// Values that stand for a toolchain without naming a release: a git ref,
+/// a local install or whatever the system provides.
+fn is_non_version(value: &str) -> bool {
+    value == "system" || value.starts_with("ref:") || value.starts_with("path:")
+}
+
+fn read_entries(fs: &dyn FileSystem, path: &Path) -> Vec<(ManagedTool, String)> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+
+    if path.extension().is_some_and(|ext| ext == "toml") {
+        mise_toml_entries(&content)
+    } else {
+        tool_versions_entries(&content)
+    }
+}
+
+/// `<tool> <version> [<fallback versions>...]` per line; only the preferred
+/// (first) version is kept.
+fn tool_versions_entries(content: &str) -> Vec<(ManagedTool, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next()?;
+            let mut words = line.split_whitespace();
+            let tool = ManagedTool::from_name(words.next()?)?;
+            Some((tool, words.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// The `[tools]` table, whose values are a version, a list of versions
+/// (first preferred) or a table with a `version` key.
+fn mise_toml_entries(content: &str) -> Vec<(ManagedTool, String)> {
+    let Ok(parsed) = toml::from_str::<toml::Value>(content) else {
+        return vec![];
+    };
+    let Some(tools) = parsed.get("tools").and_then(|t| t.as_table()) else {
+        return vec![];
+    };
+
+    tools
+        .iter()
+        .filter_map(|(name, value)| {
+            let tool = ManagedTool::from_name(name)?;
+            let value = match value {
+                toml::Value::Array(values) => values.first()?,
+                value => value,
+            };
+            let version = match value {
+                toml::Value::Table(table) => table.get("version")?.as_str()?,
+                value => value.as_str()?,
+            };
+            Some((tool, version.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_tool_versions_entries() {
+        let entries = tool_versions_entries(
+            "# pinned toolchains\ngolang 1.22.1\nnodejs 20.11.0 18.19.0 # lts\nterraform 1.7.0\n\nruby 3.3.0\n",
+        );
+
+        assert_eq!(
+            entries,
+            vec![
+                (ManagedTool::Go, "1.22.1".to_string()),
+                (ManagedTool::Node, "20.11.0".to_string()),
+                (ManagedTool::Ruby, "3.3.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mise_toml_entries() {
+        let entries = mise_toml_entries(
+            "[env]\nFOO = \"bar\"\n\n[tools]\nnode = \"22\"\npython = [\"3.12\", \"3.11\"]\ngo = { version = \"1.23\" }\n\"npm:prettier\" = \"3\"\n",
+        );
+
+        assert!(entries.contains(&(ManagedTool::Node, "22".to_string())));
+        assert!(entries.contains(&(ManagedTool::Python, "3.12".to_string())));
+        assert!(entries.contains(&(ManagedTool::Go, "1.23".to_string())));
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn test_tool_version_signals_fan_out_per_tool() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            ".tool-versions",
+            "nodejs 20.11.0\npython 3.12.1\njava 21\n",
+        );
+
+        let languages: Vec<Language> = tool_version_signals(&path, &LocalFileSystem)
+            .iter()
+            .map(Language::from)
+            .collect();
+
+        assert_eq!(
+            languages,
+            vec![
+                Language::JavaScript,
+                Language::Python,
+                Language::Custom("java")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_versions_for_one_tool() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            ".mise.toml",
+            "[tools]\nnode = \"20.11\"\nbun = \"1.1\"\n",
+        );
+
+        let versions = parse_mise_toml(&LocalFileSystem, &path, ManagedTool::Node);
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].raw, "20.11");
+        assert_eq!(
+            versions[0].source,
+            VersionSource::MiseToml(ManagedTool::Node)
+        );
+        assert_eq!(versions[0].parsed.as_ref().unwrap().minor, Some(11));
+    }
+
+    #[test]
+    fn test_non_version_values_give_no_versions() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            ".tool-versions",
+            "golang ref:abc
+nodejs system
+python path:/opt/python
+ruby 3.3.0
+",
+        );
+
+        for tool in [ManagedTool::Go, ManagedTool::Node, ManagedTool::Python] {
+            assert!(parse_tool_versions(&LocalFileSystem, &path, tool).is_empty());
+        }
+        assert_eq!(
+            parse_tool_versions(&LocalFileSystem, &path, ManagedTool::Ruby).len(),
+            1
+        );
+        // The tools are still in use, just not pinned to a release.
+        assert_eq!(tool_version_signals(&path, &LocalFileSystem).len(), 4);
+    }
+
+    #[test]
+    fn test_unrelated_files_give_no_signals() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "config.toml", "[tools]\nnode = \"20\"\n");
+
+        assert!(tool_version_signals(&path, &LocalFileSystem).is_empty());
+    }
+}
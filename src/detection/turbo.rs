@@ -0,0 +1,449 @@
+use super::{FileSystem, GraphPackage, GraphTask, TaskGraph, js_workspace_packages};
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// The name turbo gives the workspace root, as in `//#format`.
+const ROOT_PACKAGE: &str = "//";
+
+/// Stands for the root configuration's `dependsOn` in a package's.
+const EXTENDS_MARKER: &str = "$TURBO_EXTENDS$";
+
+/// A task definition from `turbo.json`. Keys it leaves unset are `None`, so
+/// a package's `turbo.json` can inherit them from the root's.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TurboTaskConfig {
+    /// `task`, `^task` for the task in each dependency, or `package#task`.
+    pub depends_on: Option<Vec<String>>,
+    pub outputs: Option<Vec<String>>,
+    pub persistent: Option<bool>,
+}
+
+/// The tasks of a `turbo.json`: `tasks` since turbo 2, `pipeline` before.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TurboJson {
+    /// Keyed by task name, or `package#task` for one package's task.
+    pub tasks: Vec<(String, TurboTaskConfig)>,
+    /// Whether this is a package's configuration, extending the root's with
+    /// `"extends": ["//"]`.
+    pub extends_root: bool,
+}
+
+impl TurboJson {
+    pub fn parse(content: &str) -> Option<Self> {
+        let json: JsonValue = serde_json::from_str(content).ok()?;
+        let strings = |value: &JsonValue| -> Vec<String> {
+            value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        };
+
+        let tasks = json
+            .get("tasks")
+            .or_else(|| json.get("pipeline"))
+            .and_then(|t| t.as_object())
+            .into_iter()
+            .flatten()
+            .map(|(name, config)| {
+                let config = TurboTaskConfig {
+                    // Turbo 1 listed environment variables as `$VAR`.
+                    depends_on: config.get("dependsOn").map(|deps| {
+                        strings(deps)
+                            .into_iter()
+                            .filter(|dep| !dep.starts_with('$') || dep == EXTENDS_MARKER)
+                            .collect()
+                    }),
+                    outputs: config.get("outputs").map(strings),
+                    persistent: config.get("persistent").and_then(|p| p.as_bool()),
+                };
+                (name.clone(), config)
+            })
+            .collect();
+
+        Some(Self {
+            tasks,
+            extends_root: json
+                .get("extends")
+                .is_some_and(|extends| strings(extends).iter().any(|e| e == ROOT_PACKAGE)),
+        })
+    }
+
+    fn task(&self, name: &str) -> Option<&TurboTaskConfig> {
+        self.tasks.iter().find(|(n, _)| n == name).map(|(_, c)| c)
+    }
+}
+
+/// Whether `path` is a workspace package's `turbo.json`, which only refines
+/// the root configuration.
+pub fn is_package_turbo_json(path: &Path, fs: &dyn FileSystem) -> bool {
+    fs.read_to_string(path)
+        .ok()
+        .and_then(|content| TurboJson::parse(&content))
+        .is_some_and(|turbo| turbo.extends_root)
+}
+
+struct Package {
+    name: String,
+    dir: PathBuf,
+    scripts: Vec<(String, String)>,
+    /// Every declared dependency, narrowed to workspace packages later.
+    dependencies: Vec<String>,
+    turbo: Option<TurboJson>,
+}
+
+impl Package {
+    fn from_path(fs: &dyn FileSystem, package_json: &Path) -> Option<Self> {
+        let json: JsonValue = serde_json::from_str(&fs.read_to_string(package_json).ok()?).ok()?;
+        let dir = package_json.parent()?.to_path_buf();
+        let scripts = json
+            .get("scripts")
+            .and_then(|s| s.as_object())
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, body)| Some((name.clone(), body.as_str()?.to_string())))
+            .collect();
+        let dependencies = ["dependencies", "devDependencies", "optionalDependencies"]
+            .iter()
+            .filter_map(|key| json.get(key)?.as_object())
+            .flat_map(|deps| deps.keys().cloned())
+            .collect();
+        let turbo = fs
+            .read_to_string(&dir.join("turbo.json"))
+            .ok()
+            .and_then(|content| TurboJson::parse(&content))
+            .filter(|turbo| turbo.extends_root);
+
+        Some(Self {
+            name: json
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            dir,
+            scripts,
+            dependencies,
+            turbo,
+        })
+    }
+
+    fn script(&self, task: &str) -> Option<&str> {
+        self.scripts
+            .iter()
+            .find(|(name, _)| name == task)
+            .map(|(_, body)| body.as_str())
+    }
+}
+
+/// Resolves a root `turbo.json` against the workspace it sits in: one task
+/// per package script turbo would run, with `dependsOn` expanded into edges
+/// between them. `None` outside a workspace.
+pub fn turbo_task_graph(path: &Path, fs: &dyn FileSystem) -> Option<TaskGraph> {
+    let root_config = TurboJson::parse(&fs.read_to_string(path).ok()?)?;
+    let root_dir = path.parent()?;
+
+    let members: Vec<Package> = js_workspace_packages(root_dir, fs)
+        .iter()
+        .filter_map(|package_json| Package::from_path(fs, package_json))
+        .filter(|package| !package.name.is_empty())
+        .collect();
+    if members.is_empty() {
+        return None;
+    }
+    let names: HashSet<&str> = members.iter().map(|p| p.name.as_str()).collect();
+    let root = Package::from_path(fs, &root_dir.join("package.json")).map(|root| Package {
+        name: ROOT_PACKAGE.to_string(),
+        ..root
+    });
+
+    let mut tasks = vec![];
+    for package in &members {
+        for (key, root_task) in &root_config.tasks {
+            if key.contains('#') {
+                continue;
+            }
+            let scoped = root_config.task(&format!("{}#{key}", package.name));
+            let config = effective_config(root_task, scoped, package.turbo.as_ref(), key);
+            tasks.extend(graph_task(package, key, &config, &members));
+        }
+        // Tasks only the package's own configuration defines.
+        for (key, config) in package.turbo.iter().flat_map(|t| &t.tasks) {
+            if root_config.task(key).is_none() && !key.contains('#') {
+                tasks.extend(graph_task(package, key, config, &members));
+            }
+        }
+    }
+    for (key, config) in &root_config.tasks {
+        let Some((scope, task)) = key.split_once('#') else {
+            continue;
+        };
+        if root_config.task(task).is_some() {
+            // Already resolved as an override of the general task.
+            continue;
+        }
+        let package = match scope {
+            ROOT_PACKAGE => root.as_ref(),
+            name => members.iter().find(|p| p.name == name),
+        };
+        if let Some(package) = package {
+            tasks.extend(graph_task(package, task, config, &members));
+        }
+    }
+
+    let packages = members
+        .iter()
+        .map(|package| GraphPackage {
+            name: package.name.clone(),
+            path: package
+                .dir
+                .strip_prefix(root_dir)
+                .unwrap_or(&package.dir)
+                .to_path_buf(),
+            dependencies: package
+                .dependencies
+                .iter()
+                .filter(|dep| names.contains(dep.as_str()))
+                .cloned()
+                .collect(),
+        })
+        .collect();
+
+    Some(TaskGraph::new(packages, tasks))
+}
+
+/// A package's view of a root task: `package#task` in the root configuration
+/// replaces it, and the package's own `turbo.json` refines that, inheriting
+/// the keys it leaves unset.
+fn effective_config(
+    root_task: &TurboTaskConfig,
+    scoped: Option<&TurboTaskConfig>,
+    package_turbo: Option<&TurboJson>,
+    task: &str,
+) -> TurboTaskConfig {
+    let base = scoped.unwrap_or(root_task).clone();
+    let Some(own) = package_turbo.and_then(|t| t.task(task)) else {
+        return base;
+    };
+
+    let depends_on = match &own.depends_on {
+        Some(own_deps) => {
+            let mut depends_on = vec![];
+            for dep in own_deps {
+                if dep == EXTENDS_MARKER {
+                    depends_on.extend(base.depends_on.iter().flatten().cloned());
+                } else {
+                    depends_on.push(dep.clone());
+                }
+            }
+            Some(depends_on)
+        }
+        None => base.depends_on,
+    };
+    TurboTaskConfig {
+        depends_on,
+        outputs: own.outputs.clone().or(base.outputs),
+        persistent: own.persistent.or(base.persistent),
+    }
+}
+
+/// The node for `task` in `package`, if the package has a script to run.
+fn graph_task(
+    package: &Package,
+    task: &str,
+    config: &TurboTaskConfig,
+    members: &[Package],
+) -> Option<GraphTask> {
+    let script = package.script(task)?;
+
+    let mut depends_on = vec![];
+    for dep in config.depends_on.iter().flatten() {
+        if let Some(upstream) = dep.strip_prefix('^') {
+            let mut seen = HashSet::new();
+            upstream_tasks(package, upstream, members, &mut seen, &mut depends_on);
+        } else if dep.contains('#') {
+            depends_on.push(dep.clone());
+        } else if dep != EXTENDS_MARKER {
+            depends_on.push(format!("{}#{dep}", package.name));
+        }
+    }
+
+    Some(GraphTask {
+        id: format!("{}#{task}", package.name),
+        package: package.name.clone(),
+        task: task.to_string(),
+        depends_on,
+        outputs: config.outputs.clone().unwrap_or_default(),
+        persistent: config.persistent == Some(true),
+        script: Some(script.to_string()),
+        executor: None,
+    })
+}
+
+/// `^task`: the task in each workspace dependency of `package`, looking
+/// through dependencies that don't define it.
+fn upstream_tasks(
+    package: &Package,
+    task: &str,
+    members: &[Package],
+    seen: &mut HashSet<String>,
+    out: &mut Vec<String>,
+) {
+    for dependency in &package.dependencies {
+        let Some(member) = members.iter().find(|m| &m.name == dependency) else {
+            continue;
+        };
+        if !seen.insert(member.name.clone()) {
+            continue;
+        }
+        if member.script(task).is_some() {
+            out.push(format!("{}#{task}", member.name));
+        } else {
+            upstream_tasks(member, task, members, seen, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LocalFileSystem;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn monorepo(turbo: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "package.json",
+            r#"{"name": "root", "workspaces": ["packages/*", "apps/*"], "scripts": {"format": "prettier --check ."}}"#,
+        );
+        write(
+            &dir,
+            "packages/ui/package.json",
+            r#"{"name": "ui", "scripts": {"build": "tsup", "test": "vitest run"}}"#,
+        );
+        write(
+            &dir,
+            "packages/config/package.json",
+            r#"{"name": "config", "dependencies": {"ui": "*"}}"#,
+        );
+        write(
+            &dir,
+            "apps/web/package.json",
+            r#"{"name": "web", "dependencies": {"config": "*", "react": "^18"}, "scripts": {"build": "next build", "test": "jest", "dev": "next dev"}}"#,
+        );
+        let path = write(&dir, "turbo.json", turbo);
+        (dir, path)
+    }
+
+    fn ids(graph: &TaskGraph) -> Vec<&str> {
+        graph.tasks.iter().map(|t| t.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_v1_pipeline() {
+        let turbo = TurboJson::parse(
+            r#"{"pipeline": {"build": {"dependsOn": ["^build", "$NODE_ENV"], "outputs": ["dist/**"]}, "dev": {"persistent": true}}}"#,
+        )
+        .unwrap();
+        assert_eq!(turbo.tasks.len(), 2);
+        let build = turbo.task("build").unwrap();
+        assert_eq!(build.depends_on.as_deref().unwrap(), ["^build"]);
+        assert_eq!(build.outputs.as_deref().unwrap(), ["dist/**"]);
+        assert_eq!(build.persistent, None);
+        assert_eq!(turbo.task("dev").unwrap().persistent, Some(true));
+        assert!(!turbo.extends_root);
+    }
+
+    #[test]
+    fn test_graph_resolves_tasks_per_package() {
+        let (_dir, path) = monorepo(
+            r#"{"tasks": {
+                "build": {"dependsOn": ["^build"], "outputs": ["dist/**"]},
+                "test": {"dependsOn": ["build"]},
+                "dev": {"persistent": true, "cache": false},
+                "web#test": {"dependsOn": ["build", "ui#test"]},
+                "//#format": {}
+            }}"#,
+        );
+        let graph = turbo_task_graph(&path, &LocalFileSystem).unwrap();
+
+        assert_eq!(
+            ids(&graph),
+            [
+                "ui#build",
+                "web#dev",
+                "//#format",
+                "ui#test",
+                "web#build",
+                "web#test"
+            ]
+        );
+        // `config` has no build script, so web's `^build` looks through it.
+        assert_eq!(graph.task("web#build").unwrap().depends_on, ["ui#build"]);
+        assert_eq!(
+            graph.task("web#test").unwrap().depends_on,
+            ["web#build", "ui#test"]
+        );
+        assert_eq!(graph.task("ui#build").unwrap().outputs, ["dist/**"]);
+        assert!(graph.task("web#dev").unwrap().persistent);
+
+        let web = graph.packages.iter().find(|p| p.name == "web").unwrap();
+        assert_eq!(web.path, Path::new("apps/web"));
+        assert_eq!(web.dependencies, ["config"]);
+    }
+
+    #[test]
+    fn test_package_turbo_json_extends_root() {
+        let (dir, path) =
+            monorepo(r#"{"tasks": {"build": {"dependsOn": ["^build"]}, "test": {}}}"#);
+        let package_turbo = write(
+            &dir,
+            "apps/web/turbo.json",
+            r#"{"extends": ["//"], "tasks": {"build": {"dependsOn": ["$TURBO_EXTENDS$", "test"], "outputs": [".next/**"]}}}"#,
+        );
+        assert!(is_package_turbo_json(&package_turbo, &LocalFileSystem));
+        assert!(!is_package_turbo_json(&path, &LocalFileSystem));
+
+        let graph = turbo_task_graph(&path, &LocalFileSystem).unwrap();
+        let build = graph.task("web#build").unwrap();
+        assert_eq!(build.depends_on, ["ui#build", "web#test"]);
+        assert_eq!(build.outputs, [".next/**"]);
+    }
+
+    #[test]
+    fn test_package_turbo_json_inherits_unset_keys() {
+        let (dir, path) = monorepo(
+            r#"{"tasks": {"build": {"dependsOn": ["^build"], "outputs": ["dist/**"]}, "dev": {"persistent": true}}}"#,
+        );
+        write(
+            &dir,
+            "apps/web/turbo.json",
+            r#"{"extends": ["//"], "tasks": {"build": {"outputs": [".next/**"]}, "dev": {"dependsOn": ["build"]}}}"#,
+        );
+
+        let graph = turbo_task_graph(&path, &LocalFileSystem).unwrap();
+        let build = graph.task("web#build").unwrap();
+        assert_eq!(build.depends_on, ["ui#build"]);
+        assert_eq!(build.outputs, [".next/**"]);
+        let dev = graph.task("web#dev").unwrap();
+        assert_eq!(dev.depends_on, ["web#build"]);
+        assert!(dev.persistent);
+    }
+
+    #[test]
+    fn test_no_graph_outside_a_workspace() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "turbo.json", r#"{"tasks": {"build": {}}}"#);
+        assert!(turbo_task_graph(&path, &LocalFileSystem).is_none());
+    }
+}
@@ -1,12 +1,18 @@
-use super::{Language, LanguageDetection, LanguageDetectionSignal, LanguageDetectionSource};
-use serde::Serialize;
-use std::fs;
-use std::path::PathBuf;
+use super::{
+    CustomName, FileSystem, Language, LanguageDetection, LanguageDetectionSignal,
+    LanguageDetectionSource, LocalFileSystem, ManagedTool, PackageManager, RangeFlavor,
+    RustToolchainSpec, VersionRange, go_work_uses, parse_mise_toml, parse_tool_versions,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum VersionSource {
     GoModDirective,
+    GoModToolchain,
+    GoWorkDirective,
+    GoWorkToolchain,
     GoVersionFile,
 
     RustToolchainFile,
@@ -17,18 +23,132 @@ pub enum VersionSource {
     PythonVersionFile,
     PipfilePythonVersion,
     SetupPyPythonRequires,
+    SetupCfgPythonRequires,
+    PythonVersionsFile,
+    RuntimeTxt,
+    CondaEnvironment,
+    ToxEnvlist,
+    NoxSession,
 
     PackageJsonEnginesNode,
     NvmrcFile,
     NodeVersionFile,
+    VoltaNode,
+    PackageJsonPackageManager(PackageManager),
+    VoltaPackageManager(PackageManager),
 
     BunVersionFile,
     PackageJsonEnginesBun,
 
     PackageJsonTypescript,
+
+    ToolVersionsFile(ManagedTool),
+    MiseToml(ManagedTool),
+
+    GithubActions(ManagedTool),
+    Dockerfile(ManagedTool),
+
+    #[serde(skip_deserializing)]
+    Custom(CustomName),
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+impl VersionSource {
+    /// The tool this source pins. Sources only compete with sources of the
+    /// same tool, so `.bun-version` never conflicts with `.nvmrc`.
+    pub fn tool(&self) -> &'static str {
+        match self {
+            VersionSource::GoModDirective
+            | VersionSource::GoModToolchain
+            | VersionSource::GoWorkDirective
+            | VersionSource::GoWorkToolchain
+            | VersionSource::GoVersionFile => "go",
+            VersionSource::RustToolchainFile
+            | VersionSource::RustToolchainToml
+            | VersionSource::CargoTomlRustVersion => "rust",
+            VersionSource::PyprojectRequiresPython
+            | VersionSource::PythonVersionFile
+            | VersionSource::PipfilePythonVersion
+            | VersionSource::SetupPyPythonRequires
+            | VersionSource::SetupCfgPythonRequires
+            | VersionSource::PythonVersionsFile
+            | VersionSource::RuntimeTxt
+            | VersionSource::CondaEnvironment
+            | VersionSource::ToxEnvlist
+            | VersionSource::NoxSession => "python",
+            VersionSource::PackageJsonEnginesNode
+            | VersionSource::NvmrcFile
+            | VersionSource::NodeVersionFile
+            | VersionSource::VoltaNode => "node",
+            VersionSource::BunVersionFile | VersionSource::PackageJsonEnginesBun => "bun",
+            VersionSource::PackageJsonTypescript => "typescript",
+            VersionSource::PackageJsonPackageManager(pm)
+            | VersionSource::VoltaPackageManager(pm) => pm.command_name().unwrap_or_default(),
+            VersionSource::ToolVersionsFile(tool)
+            | VersionSource::MiseToml(tool)
+            | VersionSource::GithubActions(tool)
+            | VersionSource::Dockerfile(tool) => tool.name(),
+            VersionSource::Custom(name) => name,
+        }
+    }
+}
+
+impl VersionSource {
+    /// Sources that take precedence over this one when both govern the same
+    /// module: Go picks its compiler from `toolchain` before `go`, and from
+    /// `go.work` before the `go.mod` of any module it uses.
+    pub fn overridden_by(&self) -> &'static [VersionSource] {
+        match self {
+            VersionSource::GoModDirective => &[
+                VersionSource::GoModToolchain,
+                VersionSource::GoWorkDirective,
+                VersionSource::GoWorkToolchain,
+            ],
+            VersionSource::GoModToolchain => &[
+                VersionSource::GoWorkDirective,
+                VersionSource::GoWorkToolchain,
+            ],
+            VersionSource::GoWorkDirective => &[VersionSource::GoWorkToolchain],
+            _ => &[],
+        }
+    }
+
+    /// Whether this source names the oldest version that works rather than
+    /// one to use, as the `go` directive and Cargo's `rust-version` do.
+    pub fn is_minimum(&self) -> bool {
+        matches!(
+            self,
+            VersionSource::GoModDirective
+                | VersionSource::GoWorkDirective
+                | VersionSource::CargoTomlRustVersion
+        )
+    }
+
+    /// Whether this source only records what CI, a container image or a
+    /// test matrix runs, and so yields to any other source of the same tool.
+    pub fn is_fallback(&self) -> bool {
+        matches!(
+            self,
+            VersionSource::GithubActions(_)
+                | VersionSource::Dockerfile(_)
+                | VersionSource::ToxEnvlist
+                | VersionSource::NoxSession
+        )
+    }
+
+    /// Whether this source lists versions the project is run against side
+    /// by side, so each of them is needed rather than just one.
+    pub fn is_matrix(&self) -> bool {
+        matches!(
+            self,
+            VersionSource::GithubActions(_)
+                | VersionSource::ToxEnvlist
+                | VersionSource::NoxSession
+                | VersionSource::PythonVersionsFile
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum VersionConstraint {
     Exact,
     GreaterOrEqual,
@@ -40,7 +160,7 @@ pub enum VersionConstraint {
     Wildcard,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticVersion {
     pub major: Option<u32>,
     pub minor: Option<u32>,
@@ -50,7 +170,7 @@ pub struct SemanticVersion {
     pub constraint: VersionConstraint,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionInfo {
     pub raw: String,
     pub parsed: Option<SemanticVersion>,
@@ -58,34 +178,154 @@ pub struct VersionInfo {
     pub path: PathBuf,
 }
 
+impl VersionInfo {
+    /// The set of versions this requirement accepts, if `raw` is a version
+    /// or range expression. A [minimum](VersionSource::is_minimum) accepts
+    /// every later version too.
+    pub fn range(&self) -> Option<VersionRange> {
+        let flavor = match self.source.tool() {
+            "python" => RangeFlavor::Pep440,
+            _ => RangeFlavor::Npm,
+        };
+        if self.source.is_minimum() {
+            return VersionRange::parse_as(&format!(">={}", self.raw), flavor);
+        }
+        VersionRange::parse_as(&self.raw, flavor)
+    }
+}
+
+/// A `go.work` file and the module directories its `use` directives list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoWorkspace {
+    pub path: PathBuf,
+    pub modules: Vec<PathBuf>,
+}
+
+impl GoWorkspace {
+    pub fn read(path: &Path, fs: &dyn FileSystem) -> Option<Self> {
+        let content = fs.read_to_string(path).ok()?;
+        let dir = path.parent()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            modules: go_work_uses(&content)
+                .iter()
+                .map(|module| dir.join(module))
+                .collect(),
+        })
+    }
+
+    /// Whether the module of `go_mod` is part of this workspace.
+    pub fn includes(&self, go_mod: &Path) -> bool {
+        go_mod
+            .parent()
+            .is_some_and(|dir| self.modules.iter().any(|module| module == dir))
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct VersionDetection {
     pub language: Language,
     pub versions: Vec<VersionInfo>,
+    /// The workspaces of the `go.work` files among `versions`, which decide
+    /// the modules their directives override.
+    #[serde(skip)]
+    pub go_workspaces: Vec<GoWorkspace>,
 }
 
-impl TryFrom<&LanguageDetection> for VersionDetection {
-    type Error = ();
+impl VersionDetection {
+    pub fn new(language: Language, versions: Vec<VersionInfo>, fs: &dyn FileSystem) -> Self {
+        let mut go_workspaces: Vec<GoWorkspace> = Vec::new();
+        for version in &versions {
+            if matches!(
+                version.source,
+                VersionSource::GoWorkDirective | VersionSource::GoWorkToolchain
+            ) && !go_workspaces.iter().any(|ws| ws.path == version.path)
+                && let Some(workspace) = GoWorkspace::read(&version.path, fs)
+            {
+                go_workspaces.push(workspace);
+            }
+        }
 
-    fn try_from(lang_detection: &LanguageDetection) -> Result<Self, Self::Error> {
+        Self {
+            language,
+            versions,
+            go_workspaces,
+        }
+    }
+
+    /// Whether `winner` takes precedence over `loser`: its source
+    /// [overrides](VersionSource::overridden_by) the other's and it governs
+    /// the same module, from the same file or a workspace using the module.
+    fn overrides(&self, winner: &VersionInfo, loser: &VersionInfo) -> bool {
+        loser.source.overridden_by().contains(&winner.source)
+            && (winner.path == loser.path
+                || self
+                    .go_workspaces
+                    .iter()
+                    .any(|ws| ws.path == winner.path && ws.includes(&loser.path)))
+    }
+
+    /// The versions that decide the toolchain, leaving out sources
+    /// [overridden](VersionSource::overridden_by) by another one for the same
+    /// module and [fallbacks](VersionSource::is_fallback) for a tool pinned
+    /// elsewhere.
+    pub fn effective_versions(&self) -> impl Iterator<Item = &VersionInfo> {
+        self.versions.iter().filter(|v| {
+            let overridden = self.versions.iter().any(|other| self.overrides(other, v));
+            let shadowed = v.source.is_fallback()
+                && self.versions.iter().any(|other| {
+                    !other.source.is_fallback() && other.source.tool() == v.source.tool()
+                });
+            !overridden && !shadowed
+        })
+    }
+
+    /// The set of versions the project needs side by side: every entry of a
+    /// [matrix](VersionSource::is_matrix) source, first occurrence first.
+    pub fn required_versions(&self) -> Vec<&VersionInfo> {
+        let mut required: Vec<&VersionInfo> = Vec::new();
+        for version in self.versions.iter().filter(|v| v.source.is_matrix()) {
+            if !required
+                .iter()
+                .any(|r| r.raw == version.raw && r.source.tool() == version.source.tool())
+            {
+                required.push(version);
+            }
+        }
+        required
+    }
+
+    pub fn from_language_detection(
+        lang_detection: &LanguageDetection,
+        fs: &dyn FileSystem,
+    ) -> Option<Self> {
         let versions: Vec<VersionInfo> = lang_detection
             .sources
             .iter()
-            .filter_map(|signal| Vec::<VersionInfo>::try_from(signal).ok())
+            .filter_map(|signal| versions_from_signal(signal, fs))
             .flatten()
             .collect();
 
         if versions.is_empty() {
-            Err(())
+            None
         } else {
-            Ok(VersionDetection {
-                language: lang_detection.language.clone(),
+            Some(VersionDetection::new(
+                lang_detection.language.clone(),
                 versions,
-            })
+                fs,
+            ))
         }
     }
 }
 
+impl TryFrom<&LanguageDetection> for VersionDetection {
+    type Error = ();
+
+    fn try_from(lang_detection: &LanguageDetection) -> Result<Self, Self::Error> {
+        Self::from_language_detection(lang_detection, &LocalFileSystem).ok_or(())
+    }
+}
+
 fn parse_constraint(s: &str) -> (VersionConstraint, &str) {
     const CONSTRAINTS: &[(&str, VersionConstraint)] = &[
         (">=", VersionConstraint::GreaterOrEqual),
@@ -156,7 +396,7 @@ fn parse_semantic_version(raw: &str) -> Option<SemanticVersion> {
     })
 }
 
-fn parse_version_or_expression(raw: &str) -> Option<SemanticVersion> {
+pub(crate) fn parse_version_or_expression(raw: &str) -> Option<SemanticVersion> {
     if !raw.contains("||") {
         return parse_semantic_version(raw);
     }
@@ -166,28 +406,76 @@ fn parse_version_or_expression(raw: &str) -> Option<SemanticVersion> {
         .max_by_key(|v| (v.major, v.minor, v.patch))
 }
 
-fn parse_go_mod(path: &PathBuf) -> Vec<VersionInfo> {
-    let Ok(content) = fs::read_to_string(path) else {
+fn parse_go_mod(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_go_directives(
+        fs,
+        path,
+        VersionSource::GoModDirective,
+        VersionSource::GoModToolchain,
+    )
+}
+
+fn parse_go_work(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_go_directives(
+        fs,
+        path,
+        VersionSource::GoWorkDirective,
+        VersionSource::GoWorkToolchain,
+    )
+}
+
+/// The `go` and `toolchain` directives of a `go.mod` or `go.work` file.
+/// `toolchain go1.22.3` is reported as `1.22.3`; `toolchain default` is
+/// skipped.
+fn parse_go_directives(
+    fs: &dyn FileSystem,
+    path: &Path,
+    go_source: VersionSource,
+    toolchain_source: VersionSource,
+) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
         return vec![];
     };
 
+    let mut versions = Vec::new();
+    let mut seen_go = false;
+    let mut seen_toolchain = false;
+
     for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("go ") {
-            let version = trimmed.trim_start_matches("go ").trim();
-            return vec![VersionInfo {
-                raw: version.to_string(),
-                parsed: parse_semantic_version(version),
-                source: VersionSource::GoModDirective,
-                path: path.clone(),
-            }];
-        }
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let mut words = line.split_whitespace();
+        let (directive, version) = match (words.next(), words.next()) {
+            (Some("go"), Some(version)) if !seen_go => {
+                seen_go = true;
+                (go_source, version)
+            }
+            (Some("toolchain"), Some(name)) if !seen_toolchain => {
+                seen_toolchain = true;
+                let Some(version) = name.strip_prefix("go") else {
+                    continue;
+                };
+                (toolchain_source, version)
+            }
+            _ => continue,
+        };
+
+        versions.push(VersionInfo {
+            raw: version.to_string(),
+            parsed: parse_semantic_version(version),
+            source: directive,
+            path: path.to_path_buf(),
+        });
     }
-    vec![]
+
+    versions
 }
 
-fn parse_simple_version_file(path: &PathBuf, source: VersionSource) -> Vec<VersionInfo> {
-    let Ok(content) = fs::read_to_string(path) else {
+fn parse_simple_version_file(
+    fs: &dyn FileSystem,
+    path: &Path,
+    source: VersionSource,
+) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
         return vec![];
     };
     let version = content.trim();
@@ -200,24 +488,42 @@ fn parse_simple_version_file(path: &PathBuf, source: VersionSource) -> Vec<Versi
         raw: version.to_string(),
         parsed: parse_semantic_version(version),
         source,
-        path: path.clone(),
+        path: path.to_path_buf(),
     }]
 }
 
-fn parse_go_version_file(path: &PathBuf) -> Vec<VersionInfo> {
-    parse_simple_version_file(path, VersionSource::GoVersionFile)
+fn parse_go_version_file(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_simple_version_file(fs, path, VersionSource::GoVersionFile)
 }
 
-fn parse_rust_toolchain(path: &PathBuf) -> Vec<VersionInfo> {
-    parse_simple_version_file(path, VersionSource::RustToolchainFile)
+fn parse_rust_toolchain(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    rust_toolchain_channel(fs, path, VersionSource::RustToolchainFile)
+}
+
+fn rust_toolchain_channel(
+    fs: &dyn FileSystem,
+    path: &Path,
+    source: VersionSource,
+) -> Vec<VersionInfo> {
+    let Some(channel) = RustToolchainSpec::from_path(fs, path).and_then(|spec| spec.channel) else {
+        return vec![];
+    };
+
+    vec![VersionInfo {
+        parsed: parse_semantic_version(&channel),
+        raw: channel,
+        source,
+        path: path.to_path_buf(),
+    }]
 }
 
 fn parse_toml_field(
-    path: &PathBuf,
+    fs: &dyn FileSystem,
+    path: &Path,
     field_path: &[&str],
     source: VersionSource,
 ) -> Vec<VersionInfo> {
-    let Ok(content) = fs::read_to_string(path) else {
+    let Ok(content) = fs.read_to_string(path) else {
         return vec![];
     };
     let Ok(parsed) = toml::from_str::<toml::Value>(&content) else {
@@ -240,67 +546,49 @@ fn parse_toml_field(
         raw: version_str.to_string(),
         parsed: parse_semantic_version(version_str),
         source,
-        path: path.clone(),
+        path: path.to_path_buf(),
     }]
 }
 
-fn parse_rust_toolchain_toml(path: &PathBuf) -> Vec<VersionInfo> {
-    let Ok(content) = fs::read_to_string(path) else {
-        return vec![];
-    };
-    let Ok(parsed) = toml::from_str::<toml::Value>(&content) else {
-        return vec![];
-    };
-
-    let Some(channel) = parsed
-        .get("toolchain")
-        .and_then(|t| t.get("channel"))
-        .or_else(|| parsed.get("channel"))
-        .and_then(|c| c.as_str())
-    else {
-        return vec![];
-    };
-
-    vec![VersionInfo {
-        raw: channel.to_string(),
-        parsed: parse_semantic_version(channel),
-        source: VersionSource::RustToolchainToml,
-        path: path.clone(),
-    }]
+fn parse_rust_toolchain_toml(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    rust_toolchain_channel(fs, path, VersionSource::RustToolchainToml)
 }
 
-fn parse_cargo_toml_rust_version(path: &PathBuf) -> Vec<VersionInfo> {
+fn parse_cargo_toml_rust_version(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
     parse_toml_field(
+        fs,
         path,
         &["package", "rust-version"],
         VersionSource::CargoTomlRustVersion,
     )
 }
 
-fn parse_pyproject_toml(path: &PathBuf) -> Vec<VersionInfo> {
+fn parse_pyproject_toml(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
     parse_toml_field(
+        fs,
         path,
         &["project", "requires-python"],
         VersionSource::PyprojectRequiresPython,
     )
 }
 
-fn parse_python_version_file(path: &PathBuf) -> Vec<VersionInfo> {
-    parse_simple_version_file(path, VersionSource::PythonVersionFile)
+fn parse_python_version_file(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_simple_version_file(fs, path, VersionSource::PythonVersionFile)
 }
 
-fn parse_pipfile(path: &PathBuf) -> Vec<VersionInfo> {
+fn parse_pipfile(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
     parse_toml_field(
+        fs,
         path,
         &["requires", "python_version"],
         VersionSource::PipfilePythonVersion,
     )
 }
 
-fn parse_setup_py(path: &PathBuf) -> Vec<VersionInfo> {
+fn parse_setup_py(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
     static PYTHON_REQUIRES_RE: OnceLock<regex::Regex> = OnceLock::new();
 
-    let Ok(content) = fs::read_to_string(path) else {
+    let Ok(content) = fs.read_to_string(path) else {
         return vec![];
     };
     let re = PYTHON_REQUIRES_RE.get_or_init(|| {
@@ -316,17 +604,287 @@ fn parse_setup_py(path: &PathBuf) -> Vec<VersionInfo> {
             raw: version.to_string(),
             parsed: parse_semantic_version(version),
             source: VersionSource::SetupPyPythonRequires,
-            path: path.clone(),
+            path: path.to_path_buf(),
         }];
     }
 
     vec![]
 }
 
-fn parse_package_json(path: &PathBuf) -> Vec<VersionInfo> {
+fn parse_setup_cfg(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+    let Some(version) = ini_value(&content, "options", "python_requires") else {
+        return vec![];
+    };
+
+    vec![VersionInfo {
+        parsed: parse_semantic_version(&version),
+        raw: version,
+        source: VersionSource::SetupCfgPythonRequires,
+        path: path.to_path_buf(),
+    }]
+}
+
+/// uv's `.python-versions`: one interpreter per line.
+fn parse_python_versions_file(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|version| VersionInfo {
+            raw: version.to_string(),
+            parsed: parse_semantic_version(version),
+            source: VersionSource::PythonVersionsFile,
+            path: path.to_path_buf(),
+        })
+        .collect()
+}
+
+/// Heroku-style `runtime.txt`: `python-3.11.4`.
+fn parse_runtime_txt(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+    let Some(version) = content.trim().strip_prefix("python-") else {
+        return vec![];
+    };
+
+    vec![VersionInfo {
+        raw: version.to_string(),
+        parsed: parse_semantic_version(version),
+        source: VersionSource::RuntimeTxt,
+        path: path.to_path_buf(),
+    }]
+}
+
+/// The `python` entry of a conda `environment.yml`'s `dependencies`, as a
+/// version expression: `python=3.10` becomes `3.10`, `python>=3.9` stays.
+fn parse_environment_yml(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+    let Ok(parsed) = serde_yaml::from_str::<serde_yaml::Value>(&content) else {
+        return vec![];
+    };
+    let Some(dependencies) = parsed.get("dependencies").and_then(|d| d.as_sequence()) else {
+        return vec![];
+    };
+
+    dependencies
+        .iter()
+        .filter_map(|dependency| {
+            let spec = dependency.as_str()?;
+            // Channel-qualified specs: `conda-forge::python=3.11`.
+            let spec = spec.rsplit("::").next()?.trim();
+            let constraint = spec.strip_prefix("python")?.trim_start();
+            let version = match constraint.strip_prefix('=') {
+                Some(rest) => rest.strip_prefix('=').unwrap_or(rest),
+                None if constraint.starts_with(['<', '>', '!', '~']) => constraint,
+                None if constraint.starts_with(|c: char| c.is_ascii_digit()) => constraint,
+                None => return None,
+            }
+            .trim()
+            .trim_end_matches(".*");
+
+            (!version.is_empty()).then(|| VersionInfo {
+                raw: version.to_string(),
+                parsed: parse_version_or_expression(version),
+                source: VersionSource::CondaEnvironment,
+                path: path.to_path_buf(),
+            })
+        })
+        .collect()
+}
+
+/// One interpreter per Python factor in tox's `envlist`, e.g. `py39`,
+/// `py3.11` or `py{310,311}-django42`.
+fn parse_tox_envlist(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+    let Some(envlist) =
+        ini_value(&content, "tox", "envlist").or_else(|| ini_value(&content, "tox", "env_list"))
+    else {
+        return vec![];
+    };
+
+    let mut versions: Vec<String> = Vec::new();
+    for env in split_envlist(&envlist)
+        .iter()
+        .flat_map(|env| expand_braces(env))
+    {
+        let Some(version) = env.split('-').find_map(tox_factor_version) else {
+            continue;
+        };
+        if !versions.contains(&version) {
+            versions.push(version);
+        }
+    }
+
+    versions
+        .into_iter()
+        .map(|version| VersionInfo {
+            parsed: parse_semantic_version(&version),
+            raw: version,
+            source: VersionSource::ToxEnvlist,
+            path: path.to_path_buf(),
+        })
+        .collect()
+}
+
+/// Interpreters named by `@nox.session(python=...)`, either inline or
+/// through a module-level list such as `PYTHON_VERSIONS = ["3.10", "3.11"]`.
+fn parse_noxfile(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    static SESSION_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static QUOTED_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static LIST_ASSIGNMENT_RE: OnceLock<regex::Regex> = OnceLock::new();
+
+    let Ok(content) = fs.read_to_string(path) else {
+        return vec![];
+    };
+    let session_re = SESSION_RE.get_or_init(|| {
+        regex::Regex::new(
+            r"@(?:nox\.)?session\s*\([^)]*?python\s*=\s*(\[[^\]]*\]|[A-Za-z_][A-Za-z0-9_]*|'[^']*'|\x22[^\x22]*\x22)",
+        )
+        .expect("invalid regex pattern")
+    });
+    let quoted_re = QUOTED_RE.get_or_init(|| {
+        regex::Regex::new(r#"["']([0-9][0-9.]*)["']"#).expect("invalid regex pattern")
+    });
+    // Module-level lists a session may name, as in `python=PYTHON_VERSIONS`.
+    let list_assignments: Vec<(&str, &str)> = LIST_ASSIGNMENT_RE
+        .get_or_init(|| {
+            regex::Regex::new(r"(?m)^([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(\[[^\]]*\])")
+                .expect("invalid regex pattern")
+        })
+        .captures_iter(&content)
+        .filter_map(|c| Some((c.get(1)?.as_str(), c.get(2)?.as_str())))
+        .collect();
+
+    let mut versions: Vec<String> = Vec::new();
+    for captures in session_re.captures_iter(&content) {
+        let value = &captures[1];
+        let listed = if value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            match list_assignments.iter().find(|(name, _)| *name == value) {
+                Some((_, list)) => list.to_string(),
+                None => continue,
+            }
+        } else {
+            value.to_string()
+        };
+
+        for version in quoted_re.captures_iter(&listed) {
+            let version = version[1].to_string();
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+    }
+
+    versions
+        .into_iter()
+        .map(|version| VersionInfo {
+            parsed: parse_semantic_version(&version),
+            raw: version,
+            source: VersionSource::NoxSession,
+            path: path.to_path_buf(),
+        })
+        .collect()
+}
+
+/// Splits on commas and newlines outside `{...}`.
+fn split_envlist(envlist: &str) -> Vec<String> {
+    let mut envs = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0_usize;
+
+    for ch in envlist.chars() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' | '\n' if depth == 0 => {
+                envs.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    envs.push(current);
+
+    envs.into_iter()
+        .map(|env| env.trim().to_string())
+        .filter(|env| !env.is_empty())
+        .collect()
+}
+
+/// `py{39,310}-lint` -> `py39-lint`, `py310-lint`.
+fn expand_braces(env: &str) -> Vec<String> {
+    let Some((before, rest)) = env.split_once('{') else {
+        return vec![env.to_string()];
+    };
+    let Some((choices, after)) = rest.split_once('}') else {
+        return vec![env.to_string()];
+    };
+
+    choices
+        .split(',')
+        .flat_map(|choice| expand_braces(&format!("{before}{}{after}", choice.trim())))
+        .collect()
+}
+
+/// `py39` -> `3.9`, `py310` -> `3.10`, `py3.11` -> `3.11`.
+fn tox_factor_version(factor: &str) -> Option<String> {
+    let digits = factor.strip_prefix("py")?;
+    if digits.contains('.') {
+        return parse_semantic_version(digits).map(|_| digits.to_string());
+    }
+    if digits.len() < 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (major, minor) = digits.split_at(1);
+    Some(format!("{major}.{minor}"))
+}
+
+/// The value of `key` in `[section]` of an INI file, joining indented
+/// continuation lines with newlines.
+fn ini_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let header = format!("[{section}]");
+    let mut lines = content
+        .lines()
+        .skip_while(|line| line.trim() != header)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['));
+
+    let first = lines.find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        (!line.starts_with([' ', '\t']) && name.trim() == key).then(|| value.trim().to_string())
+    })?;
+
+    let mut value = first;
+    for line in lines.take_while(|line| line.starts_with([' ', '\t']) || line.trim().is_empty()) {
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with('#') {
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(line);
+        }
+    }
+
+    (!value.is_empty()).then_some(value)
+}
+
+fn parse_package_json(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
     let mut versions = Vec::new();
 
-    let Ok(content) = fs::read_to_string(path) else {
+    let Ok(content) = fs.read_to_string(path) else {
         return versions;
     };
 
@@ -342,7 +900,7 @@ fn parse_package_json(path: &PathBuf) -> Vec<VersionInfo> {
                 raw: node_version.to_string(),
                 parsed: parsed_version,
                 source: VersionSource::PackageJsonEnginesNode,
-                path: path.clone(),
+                path: path.to_path_buf(),
             });
         }
 
@@ -353,11 +911,24 @@ fn parse_package_json(path: &PathBuf) -> Vec<VersionInfo> {
                 raw: bun_version.to_string(),
                 parsed: parsed_version,
                 source: VersionSource::PackageJsonEnginesBun,
-                path: path.clone(),
+                path: path.to_path_buf(),
             });
         }
     }
 
+    if let Some(node_version) = parsed
+        .get("volta")
+        .and_then(|v| v.get("node"))
+        .and_then(|v| v.as_str())
+    {
+        versions.push(VersionInfo {
+            raw: node_version.to_string(),
+            parsed: parse_version_or_expression(node_version),
+            source: VersionSource::VoltaNode,
+            path: path.to_path_buf(),
+        });
+    }
+
     for deps_key in ["dependencies", "devDependencies"] {
         if let Some(deps) = parsed.get(deps_key).and_then(|d| d.as_object())
             && let Some(ts_version) = deps.get("typescript").and_then(|v| v.as_str())
@@ -368,7 +939,7 @@ fn parse_package_json(path: &PathBuf) -> Vec<VersionInfo> {
                 raw: ts_version.to_string(),
                 parsed: parsed_version,
                 source: VersionSource::PackageJsonTypescript,
-                path: path.clone(),
+                path: path.to_path_buf(),
             });
             break;
         }
@@ -377,57 +948,75 @@ fn parse_package_json(path: &PathBuf) -> Vec<VersionInfo> {
     versions
 }
 
-fn parse_nvmrc(path: &PathBuf) -> Vec<VersionInfo> {
-    parse_simple_version_file(path, VersionSource::NvmrcFile)
+fn parse_nvmrc(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_simple_version_file(fs, path, VersionSource::NvmrcFile)
+}
+
+fn parse_node_version_file(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_simple_version_file(fs, path, VersionSource::NodeVersionFile)
 }
 
-fn parse_node_version_file(path: &PathBuf) -> Vec<VersionInfo> {
-    parse_simple_version_file(path, VersionSource::NodeVersionFile)
+fn parse_bun_version_file(fs: &dyn FileSystem, path: &Path) -> Vec<VersionInfo> {
+    parse_simple_version_file(fs, path, VersionSource::BunVersionFile)
 }
 
-fn parse_bun_version_file(path: &PathBuf) -> Vec<VersionInfo> {
-    parse_simple_version_file(path, VersionSource::BunVersionFile)
+pub fn versions_from_signal(
+    signal: &LanguageDetectionSignal,
+    fs: &dyn FileSystem,
+) -> Option<Vec<VersionInfo>> {
+    match signal {
+        LanguageDetectionSignal::Strong { path, source } => {
+            let versions = match source {
+                // Go
+                LanguageDetectionSource::GoMod => parse_go_mod(fs, path),
+                LanguageDetectionSource::GoWork => parse_go_work(fs, path),
+                LanguageDetectionSource::GoVersionFile => parse_go_version_file(fs, path),
+
+                // Rust
+                LanguageDetectionSource::CargoToml => parse_cargo_toml_rust_version(fs, path),
+                LanguageDetectionSource::RustToolchain => parse_rust_toolchain(fs, path),
+                LanguageDetectionSource::RustToolchainToml => parse_rust_toolchain_toml(fs, path),
+
+                // Python
+                LanguageDetectionSource::PyprojectToml => parse_pyproject_toml(fs, path),
+                LanguageDetectionSource::PythonVersionFile => parse_python_version_file(fs, path),
+                LanguageDetectionSource::Pipfile => parse_pipfile(fs, path),
+                LanguageDetectionSource::SetupPy => parse_setup_py(fs, path),
+                LanguageDetectionSource::SetupCfg => parse_setup_cfg(fs, path),
+                LanguageDetectionSource::PythonVersionsFile => parse_python_versions_file(fs, path),
+                LanguageDetectionSource::RuntimeTxt => parse_runtime_txt(fs, path),
+                LanguageDetectionSource::EnvironmentYml => parse_environment_yml(fs, path),
+                LanguageDetectionSource::ToxIni => parse_tox_envlist(fs, path),
+                LanguageDetectionSource::NoxfilePy => parse_noxfile(fs, path),
+
+                // JavaScript/Node
+                LanguageDetectionSource::PackageJson => parse_package_json(fs, path),
+                LanguageDetectionSource::NvmrcFile => parse_nvmrc(fs, path),
+                LanguageDetectionSource::NodeVersionFile => parse_node_version_file(fs, path),
+                LanguageDetectionSource::BunVersionFile => parse_bun_version_file(fs, path),
+
+                // Version managers
+                LanguageDetectionSource::ToolVersions(tool) => parse_tool_versions(fs, path, *tool),
+                LanguageDetectionSource::MiseToml(tool) => parse_mise_toml(fs, path, *tool),
+
+                _ => vec![],
+            };
+
+            if versions.is_empty() {
+                None
+            } else {
+                Some(versions)
+            }
+        }
+        LanguageDetectionSignal::Weak(_) => None,
+    }
 }
 
 impl TryFrom<&LanguageDetectionSignal> for Vec<VersionInfo> {
     type Error = ();
 
     fn try_from(signal: &LanguageDetectionSignal) -> Result<Self, Self::Error> {
-        match signal {
-            LanguageDetectionSignal::Strong { path, source } => {
-                let versions = match source {
-                    // Go
-                    LanguageDetectionSource::GoMod => parse_go_mod(path),
-                    LanguageDetectionSource::GoVersionFile => parse_go_version_file(path),
-
-                    // Rust
-                    LanguageDetectionSource::CargoToml => parse_cargo_toml_rust_version(path),
-                    LanguageDetectionSource::RustToolchain => parse_rust_toolchain(path),
-                    LanguageDetectionSource::RustToolchainToml => parse_rust_toolchain_toml(path),
-
-                    // Python
-                    LanguageDetectionSource::PyprojectToml => parse_pyproject_toml(path),
-                    LanguageDetectionSource::PythonVersionFile => parse_python_version_file(path),
-                    LanguageDetectionSource::Pipfile => parse_pipfile(path),
-                    LanguageDetectionSource::SetupPy => parse_setup_py(path),
-
-                    // JavaScript/Node
-                    LanguageDetectionSource::PackageJson => parse_package_json(path),
-                    LanguageDetectionSource::NvmrcFile => parse_nvmrc(path),
-                    LanguageDetectionSource::NodeVersionFile => parse_node_version_file(path),
-                    LanguageDetectionSource::BunVersionFile => parse_bun_version_file(path),
-
-                    _ => vec![],
-                };
-
-                if versions.is_empty() {
-                    Err(())
-                } else {
-                    Ok(versions)
-                }
-            }
-            LanguageDetectionSignal::Weak(_) => Err(()),
-        }
+        versions_from_signal(signal, &LocalFileSystem).ok_or(())
     }
 }
 
@@ -703,6 +1292,36 @@ mod tests {
         }
     }
 
+    mod range {
+        use super::*;
+        use crate::detection::Version;
+
+        fn info(raw: &str, source: VersionSource) -> VersionInfo {
+            VersionInfo {
+                raw: raw.to_string(),
+                parsed: parse_version_or_expression(raw),
+                source,
+                path: PathBuf::from("."),
+            }
+        }
+
+        #[test]
+        fn test_minimum_sources_accept_later_versions() {
+            let go_mod = info("1.22", VersionSource::GoModDirective).range().unwrap();
+            assert!(go_mod.contains(Version::new(1, 22, 0)));
+            assert!(go_mod.contains(Version::new(1, 23, 4)));
+            assert!(!go_mod.contains(Version::new(1, 21, 9)));
+
+            let cargo = info("1.70", VersionSource::CargoTomlRustVersion)
+                .range()
+                .unwrap();
+            assert!(cargo.contains(Version::new(1, 78, 0)));
+
+            let go_version = info("1.22", VersionSource::GoVersionFile).range().unwrap();
+            assert!(!go_version.contains(Version::new(1, 23, 4)));
+        }
+    }
+
     mod file_parsers {
         use super::*;
         use std::fs;
@@ -723,7 +1342,7 @@ mod tests {
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, "go.mod", "module example.com\n\ngo 1.21\n");
 
-                let versions = parse_go_mod(&path);
+                let versions = parse_go_mod(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.21");
                 assert!(matches!(versions[0].source, VersionSource::GoModDirective));
@@ -737,7 +1356,7 @@ mod tests {
                 let path =
                     create_temp_file(&dir, "go.mod", "module example.com\n\n  go   1.20  \n");
 
-                let versions = parse_go_mod(&path);
+                let versions = parse_go_mod(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.20");
             }
@@ -756,7 +1375,7 @@ require (
 "#;
                 let path = create_temp_file(&dir, "go.mod", content);
 
-                let versions = parse_go_mod(&path);
+                let versions = parse_go_mod(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.21");
             }
@@ -766,14 +1385,139 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, "go.mod", "module example.com\n");
 
-                let versions = parse_go_mod(&path);
+                let versions = parse_go_mod(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
+            #[test]
+            fn test_go_mod_toolchain_directive() {
+                let dir = TempDir::new().unwrap();
+                let path = create_temp_file(
+                    &dir,
+                    "go.mod",
+                    "module example.com\n\ngo 1.21\n\ntoolchain go1.22.3 // pinned\n",
+                );
+
+                let versions = parse_go_mod(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 2);
+                assert_eq!(versions[1].raw, "1.22.3");
+                assert!(matches!(versions[1].source, VersionSource::GoModToolchain));
+                assert_eq!(versions[1].parsed.as_ref().unwrap().patch, Some(3));
+            }
+
+            #[test]
+            fn test_go_mod_toolchain_default_is_skipped() {
+                let dir = TempDir::new().unwrap();
+                let path = create_temp_file(
+                    &dir,
+                    "go.mod",
+                    "module example.com\n\ngo 1.21\ntoolchain default\n",
+                );
+
+                let versions = parse_go_mod(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 1);
+                assert!(matches!(versions[0].source, VersionSource::GoModDirective));
+            }
+
+            #[test]
+            fn test_go_work_directives() {
+                let dir = TempDir::new().unwrap();
+                let path = create_temp_file(
+                    &dir,
+                    "go.work",
+                    "go 1.22.0\n\ntoolchain go1.23.1\n\nuse (\n\t./api\n\t./web\n)\n",
+                );
+
+                let versions = parse_go_work(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 2);
+                assert!(matches!(versions[0].source, VersionSource::GoWorkDirective));
+                assert_eq!(versions[0].raw, "1.22.0");
+                assert!(matches!(versions[1].source, VersionSource::GoWorkToolchain));
+                assert_eq!(versions[1].raw, "1.23.1");
+            }
+
+            #[test]
+            fn test_effective_versions_prefer_toolchain_and_workspace() {
+                let info = |raw: &str, source, path: &str| VersionInfo {
+                    raw: raw.to_string(),
+                    parsed: parse_semantic_version(raw),
+                    source,
+                    path: PathBuf::from(path),
+                };
+                let mut detection = VersionDetection {
+                    language: Language::Go,
+                    versions: vec![
+                        info("1.21", VersionSource::GoModDirective, "./go.mod"),
+                        info("1.22.3", VersionSource::GoModToolchain, "./go.mod"),
+                        info("1.22.1", VersionSource::GoVersionFile, "./.go-version"),
+                    ],
+                    go_workspaces: vec![GoWorkspace {
+                        path: PathBuf::from("./go.work"),
+                        modules: vec![PathBuf::from(".")],
+                    }],
+                };
+
+                let effective: Vec<&str> = detection
+                    .effective_versions()
+                    .map(|v| v.raw.as_str())
+                    .collect();
+                assert_eq!(effective, ["1.22.3", "1.22.1"]);
+
+                detection
+                    .versions
+                    .push(info("1.23", VersionSource::GoWorkDirective, "./go.work"));
+                let effective: Vec<&str> = detection
+                    .effective_versions()
+                    .map(|v| v.raw.as_str())
+                    .collect();
+                assert_eq!(effective, ["1.22.1", "1.23"]);
+            }
+
+            #[test]
+            fn test_overrides_stay_within_a_module() {
+                let dir = TempDir::new().unwrap();
+                for module in ["a", "b", "c"] {
+                    fs::create_dir(dir.path().join(module)).unwrap();
+                }
+                let a = create_temp_file(
+                    &dir,
+                    "a/go.mod",
+                    "module a\n\ngo 1.24\n\ntoolchain go1.24.3\n",
+                );
+                let b = create_temp_file(&dir, "b/go.mod", "module b\n\ngo 1.25\n");
+                let c = create_temp_file(&dir, "c/go.mod", "module c\n\ngo 1.23\n");
+                let go_work = create_temp_file(&dir, "go.work", "go 1.25.1\n\nuse ./c\n");
+
+                let versions = [&a, &b, &c, &go_work]
+                    .into_iter()
+                    .flat_map(|path| {
+                        if path == &go_work {
+                            parse_go_work(&LocalFileSystem, path)
+                        } else {
+                            parse_go_mod(&LocalFileSystem, path)
+                        }
+                    })
+                    .collect();
+                let detection = VersionDetection::new(Language::Go, versions, &LocalFileSystem);
+
+                let effective: Vec<(&str, &Path)> = detection
+                    .effective_versions()
+                    .map(|v| (v.raw.as_str(), v.path.as_path()))
+                    .collect();
+                assert_eq!(
+                    effective,
+                    [
+                        ("1.24.3", a.as_path()),
+                        ("1.25", b.as_path()),
+                        ("1.25.1", go_work.as_path())
+                    ]
+                );
+            }
+
             #[test]
             fn test_go_mod_nonexistent_file() {
                 let path = PathBuf::from("/nonexistent/go.mod");
-                let versions = parse_go_mod(&path);
+                let versions = parse_go_mod(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
         }
@@ -786,7 +1530,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".go-version", "1.21.0\n");
 
-                let versions = parse_go_version_file(&path);
+                let versions = parse_go_version_file(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.21.0");
                 assert!(matches!(versions[0].source, VersionSource::GoVersionFile));
@@ -797,7 +1541,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".python-version", "3.11.0\n");
 
-                let versions = parse_python_version_file(&path);
+                let versions = parse_python_version_file(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "3.11.0");
                 assert!(matches!(
@@ -811,7 +1555,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".node-version", "18.0.0\n");
 
-                let versions = parse_node_version_file(&path);
+                let versions = parse_node_version_file(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "18.0.0");
                 assert!(matches!(versions[0].source, VersionSource::NodeVersionFile));
@@ -822,7 +1566,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".nvmrc", "v18.12.0\n");
 
-                let versions = parse_nvmrc(&path);
+                let versions = parse_nvmrc(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "v18.12.0");
                 assert!(matches!(versions[0].source, VersionSource::NvmrcFile));
@@ -833,7 +1577,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".bun-version", "1.0.0\n");
 
-                let versions = parse_bun_version_file(&path);
+                let versions = parse_bun_version_file(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.0.0");
                 assert!(matches!(versions[0].source, VersionSource::BunVersionFile));
@@ -844,7 +1588,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, "rust-toolchain", "1.70.0\n");
 
-                let versions = parse_rust_toolchain(&path);
+                let versions = parse_rust_toolchain(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.70.0");
                 assert!(matches!(
@@ -858,7 +1602,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".node-version", "");
 
-                let versions = parse_node_version_file(&path);
+                let versions = parse_node_version_file(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -867,7 +1611,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".node-version", "   \n  \n");
 
-                let versions = parse_node_version_file(&path);
+                let versions = parse_node_version_file(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -876,7 +1620,7 @@ require (
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, ".node-version", "  18.0.0  \n");
 
-                let versions = parse_node_version_file(&path);
+                let versions = parse_node_version_file(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "18.0.0");
             }
@@ -895,7 +1639,7 @@ rust-version = "1.70.0"
 "#;
                 let path = create_temp_file(&dir, "Cargo.toml", content);
 
-                let versions = parse_cargo_toml_rust_version(&path);
+                let versions = parse_cargo_toml_rust_version(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.70.0");
                 assert!(matches!(
@@ -913,7 +1657,7 @@ name = "example"
 "#;
                 let path = create_temp_file(&dir, "Cargo.toml", content);
 
-                let versions = parse_cargo_toml_rust_version(&path);
+                let versions = parse_cargo_toml_rust_version(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -926,7 +1670,7 @@ channel = "1.70.0"
 "#;
                 let path = create_temp_file(&dir, "rust-toolchain.toml", content);
 
-                let versions = parse_rust_toolchain_toml(&path);
+                let versions = parse_rust_toolchain_toml(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "1.70.0");
                 assert!(matches!(
@@ -943,7 +1687,7 @@ channel = "stable"
 "#;
                 let path = create_temp_file(&dir, "rust-toolchain.toml", content);
 
-                let versions = parse_rust_toolchain_toml(&path);
+                let versions = parse_rust_toolchain_toml(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "stable");
             }
@@ -957,7 +1701,7 @@ components = ["rustfmt"]
 "#;
                 let path = create_temp_file(&dir, "rust-toolchain.toml", content);
 
-                let versions = parse_rust_toolchain_toml(&path);
+                let versions = parse_rust_toolchain_toml(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -971,7 +1715,7 @@ requires-python = ">=3.8"
 "#;
                 let path = create_temp_file(&dir, "pyproject.toml", content);
 
-                let versions = parse_pyproject_toml(&path);
+                let versions = parse_pyproject_toml(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=3.8");
                 assert!(matches!(
@@ -989,7 +1733,7 @@ python_version = "3.9"
 "#;
                 let path = create_temp_file(&dir, "Pipfile", content);
 
-                let versions = parse_pipfile(&path);
+                let versions = parse_pipfile(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "3.9");
                 assert!(matches!(
@@ -1003,7 +1747,7 @@ python_version = "3.9"
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, "Cargo.toml", "invalid toml content {{");
 
-                let versions = parse_cargo_toml_rust_version(&path);
+                let versions = parse_cargo_toml_rust_version(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -1016,7 +1760,7 @@ rust-version = 123
 "#;
                 let path = create_temp_file(&dir, "Cargo.toml", content);
 
-                let versions = parse_cargo_toml_rust_version(&path);
+                let versions = parse_cargo_toml_rust_version(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
         }
@@ -1037,7 +1781,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "setup.py", content);
 
-                let versions = parse_setup_py(&path);
+                let versions = parse_setup_py(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=3.8");
                 assert!(matches!(
@@ -1056,7 +1800,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "setup.py", content);
 
-                let versions = parse_setup_py(&path);
+                let versions = parse_setup_py(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=3.9");
             }
@@ -1071,7 +1815,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "setup.py", content);
 
-                let versions = parse_setup_py(&path);
+                let versions = parse_setup_py(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=3.10");
             }
@@ -1086,7 +1830,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "setup.py", content);
 
-                let versions = parse_setup_py(&path);
+                let versions = parse_setup_py(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -1100,12 +1844,145 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "setup.py", content);
 
-                let versions = parse_setup_py(&path);
+                let versions = parse_setup_py(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=3.8,<4.0");
             }
         }
 
+        mod python_sources {
+            use super::*;
+
+            #[test]
+            fn test_setup_cfg_python_requires() {
+                let dir = TempDir::new().unwrap();
+                let content = "[metadata]\nname = example\n\n[options]\npackages = find:\npython_requires = >=3.9\n";
+                let path = create_temp_file(&dir, "setup.cfg", content);
+
+                let versions = parse_setup_cfg(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 1);
+                assert_eq!(versions[0].raw, ">=3.9");
+                assert_eq!(versions[0].source, VersionSource::SetupCfgPythonRequires);
+            }
+
+            #[test]
+            fn test_python_versions_file_lists_each_interpreter() {
+                let dir = TempDir::new().unwrap();
+                let path = create_temp_file(&dir, ".python-versions", "3.12\n3.11 # legacy\n\n");
+
+                let versions = parse_python_versions_file(&LocalFileSystem, &path);
+                let raws: Vec<&str> = versions.iter().map(|v| v.raw.as_str()).collect();
+                assert_eq!(raws, ["3.12", "3.11"]);
+            }
+
+            #[test]
+            fn test_runtime_txt() {
+                let dir = TempDir::new().unwrap();
+                let path = create_temp_file(&dir, "runtime.txt", "python-3.11.4\n");
+
+                let versions = parse_runtime_txt(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 1);
+                assert_eq!(versions[0].raw, "3.11.4");
+
+                let path = create_temp_file(&dir, "runtime.txt", "java-17\n");
+                assert!(parse_runtime_txt(&LocalFileSystem, &path).is_empty());
+            }
+
+            #[test]
+            fn test_environment_yml_python_dependency() {
+                let dir = TempDir::new().unwrap();
+                let content = "name: demo\nchannels:\n  - conda-forge\ndependencies:\n  - python-dateutil\n  - conda-forge::python=3.10.*\n  - numpy>=1.26\n  - pip:\n      - requests\n";
+                let path = create_temp_file(&dir, "environment.yml", content);
+
+                let versions = parse_environment_yml(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 1);
+                assert_eq!(versions[0].raw, "3.10");
+                assert_eq!(versions[0].source, VersionSource::CondaEnvironment);
+
+                let path = create_temp_file(
+                    &dir,
+                    "environment.yml",
+                    "dependencies:\n  - python >=3.9,<3.13\n",
+                );
+                let versions = parse_environment_yml(&LocalFileSystem, &path);
+                assert_eq!(versions[0].raw, ">=3.9,<3.13");
+            }
+
+            #[test]
+            fn test_noxfile_session_pythons() {
+                let dir = TempDir::new().unwrap();
+                let content = r#"
+import nox
+
+SUPPORTED = ["3.10", "3.11"]
+
+@nox.session(python=SUPPORTED)
+def tests(session):
+    session.run("pytest")
+
+@nox.session(reuse_venv=True, python="3.12")
+def lint(session):
+    session.run("ruff", "check")
+
+@nox.session
+def docs(session):
+    pass
+"#;
+                let path = create_temp_file(&dir, "noxfile.py", content);
+
+                let versions = parse_noxfile(&LocalFileSystem, &path);
+                let raws: Vec<&str> = versions.iter().map(|v| v.raw.as_str()).collect();
+                assert_eq!(raws, ["3.10", "3.11", "3.12"]);
+                assert!(
+                    versions
+                        .iter()
+                        .all(|v| v.source == VersionSource::NoxSession)
+                );
+            }
+
+            #[test]
+            fn test_required_versions_collect_matrix_sources() {
+                let dir = TempDir::new().unwrap();
+                let tox = create_temp_file(&dir, "tox.ini", "[tox]\nenvlist = py311, py312\n");
+                let pinned = create_temp_file(&dir, ".python-version", "3.12\n");
+                let uv = create_temp_file(&dir, ".python-versions", "3.12\n3.13\n");
+
+                let detection = VersionDetection {
+                    language: Language::Python,
+                    go_workspaces: vec![],
+                    versions: [
+                        parse_python_version_file(&LocalFileSystem, &pinned),
+                        parse_tox_envlist(&LocalFileSystem, &tox),
+                        parse_python_versions_file(&LocalFileSystem, &uv),
+                    ]
+                    .concat(),
+                };
+
+                let required: Vec<&str> = detection
+                    .required_versions()
+                    .iter()
+                    .map(|v| v.raw.as_str())
+                    .collect();
+                assert_eq!(required, ["3.11", "3.12", "3.13"]);
+            }
+
+            #[test]
+            fn test_tox_envlist_interpreters() {
+                let dir = TempDir::new().unwrap();
+                let content = "[tox]\nenvlist =\n    py39\n    py{310,311}-django{42,50}\n    py3.12, lint, pypy3\n\n[testenv]\ncommands = pytest\n";
+                let path = create_temp_file(&dir, "tox.ini", content);
+
+                let versions = parse_tox_envlist(&LocalFileSystem, &path);
+                let raws: Vec<&str> = versions.iter().map(|v| v.raw.as_str()).collect();
+                assert_eq!(raws, ["3.9", "3.10", "3.11", "3.12"]);
+                assert!(
+                    versions
+                        .iter()
+                        .all(|v| v.source == VersionSource::ToxEnvlist)
+                );
+            }
+        }
+
         mod package_json {
             use super::*;
 
@@ -1122,7 +1999,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=18.0.0");
                 assert!(matches!(
@@ -1131,6 +2008,19 @@ setup(
                 ));
             }
 
+            #[test]
+            fn test_package_json_volta_node() {
+                let dir = TempDir::new().unwrap();
+                let content =
+                    r#"{"name": "example", "volta": {"node": "20.11.1", "yarn": "1.22.19"}}"#;
+                let path = create_temp_file(&dir, "package.json", content);
+
+                let versions = parse_package_json(&LocalFileSystem, &path);
+                assert_eq!(versions.len(), 1);
+                assert_eq!(versions[0].raw, "20.11.1");
+                assert!(matches!(versions[0].source, VersionSource::VoltaNode));
+            }
+
             #[test]
             fn test_package_json_with_bun_engine() {
                 let dir = TempDir::new().unwrap();
@@ -1143,7 +2033,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, "^1.0.0");
                 assert!(matches!(
@@ -1165,7 +2055,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 2);
 
                 let node_version = versions
@@ -1193,7 +2083,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 1);
                 assert_eq!(versions[0].raw, ">=16.0.0 || >=18.0.0");
                 assert_eq!(versions[0].parsed.as_ref().unwrap().major, Some(18));
@@ -1209,7 +2099,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -1218,7 +2108,7 @@ setup(
                 let dir = TempDir::new().unwrap();
                 let path = create_temp_file(&dir, "package.json", "{ invalid json");
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -1232,7 +2122,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert!(versions.is_empty());
             }
 
@@ -1248,7 +2138,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert!(
                     versions
                         .iter()
@@ -1276,7 +2166,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 let ts_version = versions
                     .iter()
                     .find(|v| matches!(v.source, VersionSource::PackageJsonTypescript))
@@ -1302,7 +2192,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 let ts_versions: Vec<_> = versions
                     .iter()
                     .filter(|v| matches!(v.source, VersionSource::PackageJsonTypescript))
@@ -1325,7 +2215,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert_eq!(versions.len(), 2);
                 assert!(
                     versions
@@ -1351,7 +2241,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 assert!(
                     !versions
                         .iter()
@@ -1371,7 +2261,7 @@ setup(
 "#;
                 let path = create_temp_file(&dir, "package.json", content);
 
-                let versions = parse_package_json(&path);
+                let versions = parse_package_json(&LocalFileSystem, &path);
                 let ts_version = versions
                     .iter()
                     .find(|v| matches!(v.source, VersionSource::PackageJsonTypescript))
@@ -1558,19 +2448,19 @@ setup(
         #[test]
         fn test_nonexistent_file_parsing() {
             let path = PathBuf::from("/nonexistent/file.txt");
-            assert!(parse_go_mod(&path).is_empty());
-            assert!(parse_go_version_file(&path).is_empty());
-            assert!(parse_rust_toolchain(&path).is_empty());
-            assert!(parse_rust_toolchain_toml(&path).is_empty());
-            assert!(parse_cargo_toml_rust_version(&path).is_empty());
-            assert!(parse_pyproject_toml(&path).is_empty());
-            assert!(parse_python_version_file(&path).is_empty());
-            assert!(parse_pipfile(&path).is_empty());
-            assert!(parse_setup_py(&path).is_empty());
-            assert!(parse_package_json(&path).is_empty());
-            assert!(parse_nvmrc(&path).is_empty());
-            assert!(parse_node_version_file(&path).is_empty());
-            assert!(parse_bun_version_file(&path).is_empty());
+            assert!(parse_go_mod(&LocalFileSystem, &path).is_empty());
+            assert!(parse_go_version_file(&LocalFileSystem, &path).is_empty());
+            assert!(parse_rust_toolchain(&LocalFileSystem, &path).is_empty());
+            assert!(parse_rust_toolchain_toml(&LocalFileSystem, &path).is_empty());
+            assert!(parse_cargo_toml_rust_version(&LocalFileSystem, &path).is_empty());
+            assert!(parse_pyproject_toml(&LocalFileSystem, &path).is_empty());
+            assert!(parse_python_version_file(&LocalFileSystem, &path).is_empty());
+            assert!(parse_pipfile(&LocalFileSystem, &path).is_empty());
+            assert!(parse_setup_py(&LocalFileSystem, &path).is_empty());
+            assert!(parse_package_json(&LocalFileSystem, &path).is_empty());
+            assert!(parse_nvmrc(&LocalFileSystem, &path).is_empty());
+            assert!(parse_node_version_file(&LocalFileSystem, &path).is_empty());
+            assert!(parse_bun_version_file(&LocalFileSystem, &path).is_empty());
         }
 
         #[test]
@@ -1637,7 +2527,7 @@ channel = "1.70.0"
 "#;
             let path = create_temp_file(&dir, "rust-toolchain.toml", content);
 
-            let versions = parse_rust_toolchain_toml(&path);
+            let versions = parse_rust_toolchain_toml(&LocalFileSystem, &path);
             assert_eq!(versions.len(), 1);
             assert_eq!(versions[0].raw, "1.70.0");
         }
@@ -1647,7 +2537,7 @@ channel = "1.70.0"
             let dir = TempDir::new().unwrap();
             let path = create_temp_file(&dir, ".python-version", "3.11.0");
 
-            let versions = parse_python_version_file(&path);
+            let versions = parse_python_version_file(&LocalFileSystem, &path);
             assert_eq!(versions.len(), 1);
             assert_eq!(versions[0].raw, "3.11.0");
             assert!(versions[0].parsed.is_some());
@@ -0,0 +1,522 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::detection::{VersionInterval, VersionRange};
+
+const CATALOG_FORMAT: u32 = 1;
+const EMBEDDED_CATALOG: &str = include_str!("nixpkgs-catalog.json");
+
+/// A toolchain family whose nixpkgs attributes are versioned by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Toolchain {
+    Go,
+    Python,
+    Nodejs,
+}
+
+impl Toolchain {
+    pub const ALL: [Toolchain; 3] = [Toolchain::Go, Toolchain::Python, Toolchain::Nodejs];
+
+    /// The unversioned attribute used when no versioned one fits.
+    pub fn default_attr(self) -> &'static str {
+        match self {
+            Toolchain::Go => "go",
+            Toolchain::Python => "python3",
+            Toolchain::Nodejs => "nodejs",
+        }
+    }
+
+    /// How versioned attributes are spelled, for notices.
+    pub fn attr_pattern(self) -> &'static str {
+        match self {
+            Toolchain::Go => "go_X_Y",
+            Toolchain::Python => "pythonXY",
+            Toolchain::Nodejs => "nodejs_X",
+        }
+    }
+
+    /// The releases packaged by a versioned attribute such as `go_1_22`,
+    /// `python311` or `nodejs_20`.
+    pub fn attr_series(self, attr: &str) -> Option<VersionInterval> {
+        match self {
+            Toolchain::Go => {
+                let (major, minor) = attr.strip_prefix("go_")?.split_once('_')?;
+                Some(VersionInterval::minor_series(
+                    parse_number(major)?,
+                    parse_number(minor)?,
+                ))
+            }
+            Toolchain::Python => {
+                let rest = attr.strip_prefix("python")?;
+                let (major, minor) = rest.split_at_checked(1)?;
+                Some(VersionInterval::minor_series(
+                    parse_number(major)?,
+                    parse_number(minor)?,
+                ))
+            }
+            Toolchain::Nodejs => {
+                let major = attr.strip_prefix("nodejs_")?;
+                Some(VersionInterval::major_series(parse_number(major)?))
+            }
+        }
+    }
+}
+
+/// The versioned toolchain attributes available in one nixpkgs channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelCatalog {
+    pub nixpkgs_version: Option<String>,
+    pub revision: Option<String>,
+    pub attrs: BTreeMap<Toolchain, Vec<String>>,
+    /// Attributes packaging an alpha, beta or release candidate, which are
+    /// only picked when no stable attribute fits.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prerelease: BTreeMap<Toolchain, Vec<String>>,
+}
+
+impl ChannelCatalog {
+    /// Scans a local nixpkgs checkout for versioned toolchain attributes.
+    ///
+    /// Attributes are read from `pkgs/top-level/all-packages.nix` (both
+    /// definitions and `inherit`s) and from `pkgs/by-name`. Removed
+    /// attributes only survive as throws in `aliases.nix`, which is not read.
+    /// Pre-releases are recognised by the version their expression pins.
+    pub fn from_checkout(nixpkgs: &Path) -> io::Result<Self> {
+        let all_packages = fs::read_to_string(nixpkgs.join("pkgs/top-level/all-packages.nix"))?;
+
+        let mut names: BTreeSet<String> = nix_identifiers(&all_packages).collect();
+        names.extend(by_name_attrs(&nixpkgs.join("pkgs/by-name")));
+
+        let attrs: BTreeMap<Toolchain, Vec<String>> = Toolchain::ALL
+            .into_iter()
+            .map(|toolchain| {
+                let mut found: Vec<String> = names
+                    .iter()
+                    .filter(|name| toolchain.attr_series(name).is_some())
+                    .cloned()
+                    .collect();
+                found.sort_by_key(|attr| toolchain.attr_series(attr).map(|s| s.start));
+                (toolchain, found)
+            })
+            .collect();
+
+        let nixpkgs_version = fs::read_to_string(nixpkgs.join(".version"))
+            .ok()
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty());
+
+        let prerelease = prerelease_attrs(nixpkgs, &attrs);
+
+        Ok(Self {
+            nixpkgs_version,
+            revision: git_revision(nixpkgs),
+            attrs,
+            prerelease,
+        })
+    }
+
+    pub fn attrs(&self, toolchain: Toolchain) -> &[String] {
+        self.attrs.get(&toolchain).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn contains(&self, toolchain: Toolchain, attr: &str) -> bool {
+        self.attrs(toolchain).iter().any(|a| a == attr)
+    }
+
+    pub fn is_prerelease(&self, toolchain: Toolchain, attr: &str) -> bool {
+        self.prerelease
+            .get(&toolchain)
+            .is_some_and(|attrs| attrs.iter().any(|a| a == attr))
+    }
+
+    /// The newest attribute packaging a release inside `range`, preferring
+    /// stable releases so an open-ended range never lands on an alpha.
+    pub fn best_attr(&self, toolchain: Toolchain, range: &VersionRange) -> Option<&str> {
+        self.attrs(toolchain)
+            .iter()
+            .filter_map(|attr| Some((toolchain.attr_series(attr)?, attr)))
+            .filter(|(series, _)| range.overlaps(*series))
+            .max_by_key(|(series, attr)| (!self.is_prerelease(toolchain, attr), series.start))
+            .map(|(_, attr)| attr.as_str())
+    }
+}
+
+/// Toolchain attributes per nixpkgs channel, used to resolve version
+/// requirements to attributes that actually exist.
+///
+/// A catalog is compiled into autonix; `autonix refresh-catalog` writes
+/// channels scanned from a local checkout to [`NixpkgsCatalog::default_path`],
+/// which take precedence over the built-in ones. Passing
+/// `--output src/generation/nixpkgs-catalog.json` refreshes the built-in
+/// catalog itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NixpkgsCatalog {
+    format: u32,
+    channels: BTreeMap<String, ChannelCatalog>,
+}
+
+impl Default for NixpkgsCatalog {
+    fn default() -> Self {
+        Self::embedded()
+    }
+}
+
+impl NixpkgsCatalog {
+    /// The catalog shipped with this version of autonix.
+    pub fn embedded() -> Self {
+        serde_json::from_str(EMBEDDED_CATALOG).expect("embedded nixpkgs catalog is valid")
+    }
+
+    /// Catalog without any channels.
+    pub fn empty() -> Self {
+        Self {
+            format: CATALOG_FORMAT,
+            channels: BTreeMap::new(),
+        }
+    }
+
+    /// The built-in catalog overlaid with the channels stored at `path`;
+    /// a missing, unreadable or incompatible file is ignored.
+    pub fn load(path: &Path) -> Self {
+        let mut catalog = Self::embedded();
+        if let Some(stored) = Self::read(path) {
+            catalog.channels.extend(stored.channels);
+        }
+        catalog
+    }
+
+    /// Only the channels stored at `path`.
+    pub fn read(path: &Path) -> Option<Self> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|catalog| catalog.format == CATALOG_FORMAT)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        content.push('\n');
+        fs::write(path, content)
+    }
+
+    /// `$XDG_DATA_HOME/autonix/nixpkgs-catalog.json`, falling back to
+    /// `~/.local/share`.
+    pub fn default_path() -> Option<PathBuf> {
+        let data_home = env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+
+        Some(data_home.join("autonix").join("nixpkgs-catalog.json"))
+    }
+
+    pub fn channel(&self, name: &str) -> Option<&ChannelCatalog> {
+        self.channels.get(name)
+    }
+
+    pub fn channels(&self) -> impl Iterator<Item = (&str, &ChannelCatalog)> {
+        self.channels
+            .iter()
+            .map(|(name, channel)| (name.as_str(), channel))
+    }
+
+    pub fn set_channel(&mut self, name: impl Into<String>, channel: ChannelCatalog) -> &mut Self {
+        self.channels.insert(name.into(), channel);
+        self
+    }
+}
+
+fn parse_number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Every identifier in a Nix file, with comments skipped.
+fn nix_identifiers(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+}
+
+/// The scanned attributes whose expression pins a pre-release: CPython
+/// interpreters with a non-empty `suffix`, and Go toolchains whose
+/// `version` carries an `rc` or `beta` tag.
+fn prerelease_attrs(
+    nixpkgs: &Path,
+    attrs: &BTreeMap<Toolchain, Vec<String>>,
+) -> BTreeMap<Toolchain, Vec<String>> {
+    let mut prerelease: BTreeMap<Toolchain, Vec<String>> = BTreeMap::new();
+
+    let interpreters = nixpkgs.join("pkgs/development/interpreters/python/default.nix");
+    if let Ok(content) = fs::read_to_string(interpreters) {
+        let mut current = None;
+        for line in content.lines().map(str::trim) {
+            if let Some((name, _)) = line.split_once(" =")
+                && Toolchain::Python.attr_series(name).is_some()
+            {
+                current = Some(name);
+            } else if let Some(suffix) = nix_string_value(line, "suffix")
+                && !suffix.is_empty()
+                && let Some(name) = current
+            {
+                prerelease
+                    .entry(Toolchain::Python)
+                    .or_default()
+                    .push(name.to_string());
+            }
+        }
+    }
+
+    for attr in attrs.get(&Toolchain::Go).into_iter().flatten() {
+        let Some(file) = attr.strip_prefix("go_").map(|series| {
+            format!(
+                "pkgs/development/compilers/go/{}.nix",
+                series.replace('_', ".")
+            )
+        }) else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(nixpkgs.join(file)) else {
+            continue;
+        };
+        if content
+            .lines()
+            .filter_map(|line| nix_string_value(line.trim(), "version"))
+            .any(|version| version.bytes().any(|b| b.is_ascii_alphabetic()))
+        {
+            prerelease
+                .entry(Toolchain::Go)
+                .or_default()
+                .push(attr.clone());
+        }
+    }
+
+    for attrs in prerelease.values_mut() {
+        attrs.sort();
+        attrs.dedup();
+    }
+    prerelease
+}
+
+/// The value of a `key = "value";` binding on one line.
+fn nix_string_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+    rest.trim_start()
+        .strip_prefix('"')?
+        .split_once('"')
+        .map(|(value, _)| value)
+}
+
+fn by_name_attrs(by_name: &Path) -> Vec<String> {
+    let Ok(shards) = fs::read_dir(by_name) else {
+        return vec![];
+    };
+
+    shards
+        .flatten()
+        .filter_map(|shard| fs::read_dir(shard.path()).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
+
+fn git_revision(repo: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let revision = String::from_utf8(output.stdout).ok()?;
+    Some(revision.trim().to_string()).filter(|revision| !revision.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::constants;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_embedded_catalog_has_default_channel() {
+        let catalog = NixpkgsCatalog::embedded();
+        let channel = catalog.channel(constants::NIXPKGS_CHANNEL).unwrap();
+
+        for toolchain in Toolchain::ALL {
+            assert!(!channel.attrs(toolchain).is_empty());
+            assert!(
+                channel
+                    .attrs(toolchain)
+                    .iter()
+                    .all(|attr| toolchain.attr_series(attr).is_some())
+            );
+        }
+    }
+
+    #[test]
+    fn test_attr_series() {
+        assert_eq!(
+            Toolchain::Go.attr_series("go_1_22"),
+            Some(VersionInterval::minor_series(1, 22))
+        );
+        assert_eq!(
+            Toolchain::Python.attr_series("python311"),
+            Some(VersionInterval::minor_series(3, 11))
+        );
+        assert_eq!(
+            Toolchain::Nodejs.attr_series("nodejs_20"),
+            Some(VersionInterval::major_series(20))
+        );
+        assert_eq!(Toolchain::Python.attr_series("python3Minimal"), None);
+        assert_eq!(Toolchain::Python.attr_series("python3"), None);
+        assert_eq!(Toolchain::Nodejs.attr_series("nodejs_latest"), None);
+        assert_eq!(Toolchain::Go.attr_series("go"), None);
+    }
+
+    #[test]
+    fn test_best_attr_picks_newest_inside_range() {
+        let mut channel = ChannelCatalog::default();
+        channel.attrs.insert(
+            Toolchain::Python,
+            vec!["python310".into(), "python311".into(), "python312".into()],
+        );
+
+        let range = VersionRange::parse(">=3.9,<3.12").unwrap();
+        assert_eq!(
+            channel.best_attr(Toolchain::Python, &range),
+            Some("python311")
+        );
+
+        let range = VersionRange::parse(">=3.13").unwrap();
+        assert_eq!(channel.best_attr(Toolchain::Python, &range), None);
+    }
+
+    #[test]
+    fn test_best_attr_prefers_stable_releases() {
+        let mut channel = ChannelCatalog::default();
+        channel.attrs.insert(
+            Toolchain::Python,
+            vec!["python313".into(), "python314".into(), "python315".into()],
+        );
+        channel
+            .prerelease
+            .insert(Toolchain::Python, vec!["python315".into()]);
+
+        let range = VersionRange::parse(">=3.10").unwrap();
+        assert_eq!(
+            channel.best_attr(Toolchain::Python, &range),
+            Some("python314")
+        );
+
+        let range = VersionRange::parse(">=3.15").unwrap();
+        assert_eq!(
+            channel.best_attr(Toolchain::Python, &range),
+            Some("python315")
+        );
+    }
+
+    #[test]
+    fn test_embedded_catalog_resolves_open_ranges_to_stable() {
+        let catalog = NixpkgsCatalog::embedded();
+        let channel = catalog.channel(constants::NIXPKGS_CHANNEL).unwrap();
+
+        let range = VersionRange::parse(">=3.10").unwrap();
+        assert_eq!(
+            channel.best_attr(Toolchain::Python, &range),
+            Some("python314")
+        );
+    }
+
+    #[test]
+    fn test_from_checkout_scans_all_packages_and_by_name() {
+        let dir = TempDir::new().unwrap();
+        let top_level = dir.path().join("pkgs/top-level");
+        fs::create_dir_all(&top_level).unwrap();
+        fs::write(
+            top_level.join("all-packages.nix"),
+            "{\n  # go_1_19 was removed\n  go_1_22 = callPackage ../go/1.22.nix { };\n  go = go_1_22;\n  inherit (pythonInterpreters) python311 python312\n    python3Minimal;\n  nodejs_20 = callPackage ../nodejs/v20.nix { };\n}\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("pkgs/by-name/no/nodejs_22")).unwrap();
+        fs::write(dir.path().join(".version"), "25.11\n").unwrap();
+
+        let channel = ChannelCatalog::from_checkout(dir.path()).unwrap();
+        assert_eq!(channel.attrs(Toolchain::Go), ["go_1_22"]);
+        assert_eq!(channel.attrs(Toolchain::Python), ["python311", "python312"]);
+        assert_eq!(channel.attrs(Toolchain::Nodejs), ["nodejs_20", "nodejs_22"]);
+        assert_eq!(channel.nixpkgs_version.as_deref(), Some("25.11"));
+        assert!(channel.prerelease.is_empty());
+    }
+
+    #[test]
+    fn test_from_checkout_marks_prereleases() {
+        let dir = TempDir::new().unwrap();
+        let top_level = dir.path().join("pkgs/top-level");
+        fs::create_dir_all(&top_level).unwrap();
+        fs::write(
+            top_level.join("all-packages.nix"),
+            "{\n  go_1_25 = callPackage ../go/1.25.nix { };\n  go_1_26 = callPackage ../go/1.26.nix { };\n  inherit (pythonInterpreters) python314 python315;\n}\n",
+        )
+        .unwrap();
+        let go = dir.path().join("pkgs/development/compilers/go");
+        fs::create_dir_all(&go).unwrap();
+        fs::write(go.join("1.25.nix"), "{\n  version = \"1.25.3\";\n}\n").unwrap();
+        fs::write(go.join("1.26.nix"), "{\n  version = \"1.26rc1\";\n}\n").unwrap();
+        let python = dir.path().join("pkgs/development/interpreters/python");
+        fs::create_dir_all(&python).unwrap();
+        fs::write(
+            python.join("default.nix"),
+            "{\n  python314 = {\n    sourceVersion = {\n      minor = \"14\";\n      suffix = \"\";\n    };\n  };\n  python315 = callPackage ./cpython {\n    sourceVersion = {\n      minor = \"15\";\n      suffix = \"a1\";\n    };\n  };\n}\n",
+        )
+        .unwrap();
+
+        let channel = ChannelCatalog::from_checkout(dir.path()).unwrap();
+        assert!(channel.is_prerelease(Toolchain::Go, "go_1_26"));
+        assert!(!channel.is_prerelease(Toolchain::Go, "go_1_25"));
+        assert!(channel.is_prerelease(Toolchain::Python, "python315"));
+        assert!(!channel.is_prerelease(Toolchain::Python, "python314"));
+    }
+
+    #[test]
+    fn test_load_overlays_stored_channels() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("catalog.json");
+
+        let mut channel = ChannelCatalog::default();
+        channel
+            .attrs
+            .insert(Toolchain::Go, vec!["go_1_30".to_string()]);
+        let mut stored = NixpkgsCatalog::empty();
+        stored.set_channel(constants::NIXPKGS_CHANNEL, channel.clone());
+        stored.save(&path).unwrap();
+
+        let catalog = NixpkgsCatalog::load(&path);
+        assert_eq!(catalog.channel(constants::NIXPKGS_CHANNEL), Some(&channel));
+        assert!(catalog.channel("nixos-25.05").is_some());
+        assert_eq!(NixpkgsCatalog::read(&path), Some(stored));
+    }
+
+    #[test]
+    fn test_load_ignores_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let catalog = NixpkgsCatalog::load(&dir.path().join("missing.json"));
+        assert_eq!(catalog, NixpkgsCatalog::embedded());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::detection::VersionSource;
+use crate::detection::{ManagedTool, VersionSource};
 
 pub const NODE_TOOL_VITE: &str = "vite";
 pub const NODE_TOOL_WEBPACK: &str = "webpack";
@@ -19,24 +19,56 @@ pub const GO_TOOL_GOPLS: &str = "gopls";
 pub const NODE_PKG_TYPESCRIPT: &str = "typescript";
 pub const NODE_PKG_TYPESCRIPT_LS: &str = "typescript-language-server";
 
-pub const GO_VERSION_SOURCES: &[VersionSource] =
-    &[VersionSource::GoModDirective, VersionSource::GoVersionFile];
+pub const GO_VERSION_SOURCES: &[VersionSource] = &[
+    VersionSource::GoModDirective,
+    VersionSource::GoModToolchain,
+    VersionSource::GoWorkDirective,
+    VersionSource::GoWorkToolchain,
+    VersionSource::GoVersionFile,
+    VersionSource::ToolVersionsFile(ManagedTool::Go),
+    VersionSource::MiseToml(ManagedTool::Go),
+    VersionSource::GithubActions(ManagedTool::Go),
+    VersionSource::Dockerfile(ManagedTool::Go),
+];
 
 pub const PYTHON_VERSION_SOURCES: &[VersionSource] = &[
     VersionSource::PyprojectRequiresPython,
     VersionSource::PythonVersionFile,
     VersionSource::PipfilePythonVersion,
     VersionSource::SetupPyPythonRequires,
+    VersionSource::SetupCfgPythonRequires,
+    VersionSource::PythonVersionsFile,
+    VersionSource::RuntimeTxt,
+    VersionSource::CondaEnvironment,
+    VersionSource::ToolVersionsFile(ManagedTool::Python),
+    VersionSource::MiseToml(ManagedTool::Python),
+    VersionSource::ToxEnvlist,
+    VersionSource::NoxSession,
+    VersionSource::GithubActions(ManagedTool::Python),
+    VersionSource::Dockerfile(ManagedTool::Python),
 ];
 
 pub const NODE_VERSION_SOURCES: &[VersionSource] = &[
     VersionSource::PackageJsonEnginesNode,
     VersionSource::NvmrcFile,
     VersionSource::NodeVersionFile,
+    VersionSource::VoltaNode,
+    VersionSource::ToolVersionsFile(ManagedTool::Node),
+    VersionSource::MiseToml(ManagedTool::Node),
+    VersionSource::GithubActions(ManagedTool::Node),
+    VersionSource::Dockerfile(ManagedTool::Node),
 ];
 
 pub const RUST_VERSION_SOURCES: &[VersionSource] = &[
     VersionSource::RustToolchainFile,
     VersionSource::RustToolchainToml,
     VersionSource::CargoTomlRustVersion,
+    VersionSource::ToolVersionsFile(ManagedTool::Rust),
+    VersionSource::MiseToml(ManagedTool::Rust),
+    VersionSource::GithubActions(ManagedTool::Rust),
+    VersionSource::Dockerfile(ManagedTool::Rust),
 ];
+
+/// The nixpkgs channel generated flakes follow, and the catalog channel
+/// toolchain attributes are resolved against.
+pub const NIXPKGS_CHANNEL: &str = "nixos-unstable";
@@ -1,23 +1,30 @@
 use std::{
     collections::{BTreeSet, HashMap, HashSet},
     fmt::Write as _,
-    fs,
     path::{Path, PathBuf},
 };
 
 use serde_json::Value as JsonValue;
 
 use crate::detection::{
-    CommandExecutable, Language, PackageManager, ProjectMetadata, SemanticVersion, TaskCommand,
-    TaskRunner, VersionConstraint, VersionInfo, VersionSource,
+    CommandExecutable, FileSystem, Language, LocalFileSystem, PackageManager, PackageManagerInfo,
+    PackageManagerSource, ProjectMetadata, RustChannel, RustProfile, RustToolchainSpec,
+    SemanticVersion, TaskCommand, TaskRunner, VersionConstraint, VersionInfo, VersionRange,
+    VersionSource, YarnSetup,
 };
+use crate::generation::catalog::{NixpkgsCatalog, Toolchain};
 use crate::generation::constants;
+use crate::generation::generator::GeneratorRegistry;
 use crate::generation::nix_builder;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CheckCategory {
     Test,
     Build,
+    /// Linters and type checkers.
+    Lint,
+    /// Formatters in check mode.
+    Format,
 }
 
 #[derive(Debug, Clone)]
@@ -53,6 +60,9 @@ struct CheckSpec {
     required_exec: String,
     command: String,
     workdir: String,
+    /// A toolchain attribute put ahead of `devPackages`, for per-version
+    /// copies of a check.
+    toolchain_attr: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -64,6 +74,22 @@ struct CommandInfo {
 }
 
 pub fn generate_dev_flake(metadata: &ProjectMetadata, root: &Path) -> GeneratedFlake {
+    generate_dev_flake_with_registry(
+        metadata,
+        root,
+        &GeneratorRegistry::default(),
+        &LocalFileSystem,
+    )
+}
+
+/// Generates the flake for `metadata`, reading project files under `root`
+/// through `fs`, the same source detection read them from.
+pub fn generate_dev_flake_with_registry(
+    metadata: &ProjectMetadata,
+    root: &Path,
+    registry: &GeneratorRegistry,
+    fs: &dyn FileSystem,
+) -> GeneratedFlake {
     let detected_languages = detected_languages(metadata);
 
     let task_runners: HashSet<TaskRunner> = metadata
@@ -87,9 +113,16 @@ pub fn generate_dev_flake(metadata: &ProjectMetadata, root: &Path) -> GeneratedF
         });
 
     let mut need_python = detected_languages.contains(&Language::Python)
-        || task_runners
-            .iter()
-            .any(|tr| matches!(tr, TaskRunner::Tox | TaskRunner::Nox | TaskRunner::Invoke));
+        || task_runners.iter().any(|tr| {
+            matches!(
+                tr,
+                TaskRunner::Tox
+                    | TaskRunner::Nox
+                    | TaskRunner::Invoke
+                    | TaskRunner::Hatch
+                    | TaskRunner::Pixi
+            )
+        });
 
     let need_go =
         detected_languages.contains(&Language::Go) || task_runners.contains(&TaskRunner::GoTask);
@@ -109,31 +142,40 @@ pub fn generate_dev_flake(metadata: &ProjectMetadata, root: &Path) -> GeneratedF
     );
     let rust_version = best_version_info(metadata, Language::Rust, constants::RUST_VERSION_SOURCES);
 
-    let go_want_attr = go_version
-        .and_then(|v| v.parsed.as_ref())
-        .and_then(go_attr_from_version);
-    let python_want_attr = python_version
-        .and_then(|v| v.parsed.as_ref())
-        .and_then(python_attr_from_version);
-    let node_want_attr = node_version
-        .and_then(|v| v.parsed.as_ref())
-        .and_then(node_attr_from_version);
+    let catalog = registry.catalog();
+    let go_choice = resolve_attr(
+        catalog,
+        Toolchain::Go,
+        metadata,
+        Language::Go,
+        constants::GO_VERSION_SOURCES,
+        go_attr_from_version,
+    );
+    let python_choice = resolve_attr(
+        catalog,
+        Toolchain::Python,
+        metadata,
+        Language::Python,
+        constants::PYTHON_VERSION_SOURCES,
+        python_attr_from_version,
+    );
+    let node_choice = resolve_attr(
+        catalog,
+        Toolchain::Nodejs,
+        metadata,
+        Language::JavaScript,
+        constants::NODE_VERSION_SOURCES,
+        node_attr_from_version,
+    );
 
-    let rust_want_version = rust_version
-        .filter(|v| {
-            matches!(
-                v.parsed.as_ref().map(|p| p.constraint),
-                Some(VersionConstraint::Exact)
-            )
-        })
-        .and_then(|v| v.parsed.as_ref())
-        .and_then(rust_version_string_from_version);
+    let rust_plan = rust_toolchain_plan(metadata, root, rust_version, fs);
 
     let mut required_package_managers = detected_package_managers(metadata);
 
-    let checks_by_lang = collect_checks(
+    let mut checks_by_lang = collect_checks(
         metadata,
         root,
+        fs,
         &mut required_package_managers,
         &mut need_node,
         &mut need_python,
@@ -144,43 +186,99 @@ pub fn generate_dev_flake(metadata: &ProjectMetadata, root: &Path) -> GeneratedF
 
     let uses_rust_overlay = need_rust;
 
-    let go_notice = go_notice(go_version, go_want_attr.as_deref());
-    let python_notice = python_notice(python_version, python_want_attr.as_deref());
-    let node_notice = node_notice(node_version, node_want_attr.as_deref());
-    let rust_notice = rust_notice(need_rust, rust_version, rust_want_version.as_deref());
+    let go_notice = go_notice(go_version, &go_choice);
+    let python_notice = python_notice(python_version, &python_choice);
+    let node_notice = node_notice(node_version, &node_choice);
+    let rust_notice = rust_notice(need_rust, rust_version, &rust_plan);
+
+    let go_notices = language_notices(metadata, Language::Go, go_notice);
+    let mut python_notices = language_notices(metadata, Language::Python, python_notice);
+    let mut node_notices = language_notices(metadata, Language::JavaScript, node_notice);
+    let rust_notices = language_notices(metadata, Language::Rust, rust_notice);
 
     let mut language_packages = Vec::new();
 
     if need_go {
-        let want_go_attr = go_want_attr.as_deref().unwrap_or("go");
+        let want_go_attr = go_choice.want();
         language_packages.push(LanguagePackages {
             language: Language::Go,
-            content: generate_golang_packages_nix(want_go_attr, go_notice.as_deref()),
+            content: generate_golang_packages_nix(want_go_attr, &go_notices),
         });
     }
 
+    let want_python_attr = python_choice.want();
+    let want_node_attr = node_choice.want();
+
+    let (python_matrix, python_matrix_notices) = matrix_attrs(
+        metadata,
+        catalog,
+        Toolchain::Python,
+        Language::Python,
+        constants::PYTHON_VERSION_SOURCES,
+        want_python_attr,
+        python_attr_from_version,
+    );
+    python_notices.extend(python_matrix_notices);
+    let (node_matrix, node_matrix_notices) = matrix_attrs(
+        metadata,
+        catalog,
+        Toolchain::Nodejs,
+        Language::JavaScript,
+        constants::NODE_VERSION_SOURCES,
+        want_node_attr,
+        node_attr_from_version,
+    );
+    node_notices.extend(node_matrix_notices);
+
+    let package_manager_pins = package_manager_pins(metadata);
+    node_notices.extend(
+        package_manager_pins
+            .iter()
+            .filter(|pin| pin.package_manager == PackageManager::Npm)
+            .map(|pin| {
+                format!(
+                    "npm: {} pins {}; the shell uses the npm bundled with Node",
+                    pin.origin(),
+                    pin.version
+                )
+            }),
+    );
+
+    let yarn_setup = yarn_setup(metadata, fs);
+    if let Some(setup) = yarn_setup.as_ref().filter(|setup| setup.pnp) {
+        node_notices.push(format!(
+            "Yarn {}: Plug'n'Play installs; run `yarn dlx @yarnpkg/sdks` so editors resolve dependencies",
+            setup.version.as_deref().unwrap_or("berry")
+        ));
+    }
+
+    parameterize_checks(&mut checks_by_lang, Language::Python, &python_matrix);
+    parameterize_checks(&mut checks_by_lang, Language::JavaScript, &node_matrix);
+
     if need_python {
-        let want_python_attr = python_want_attr.as_deref().unwrap_or("python3");
         language_packages.push(LanguagePackages {
             language: Language::Python,
             content: generate_python_packages_nix(
                 want_python_attr,
-                python_notice.as_deref(),
+                &python_notices,
                 &required_package_managers,
                 &required_task_runner_tools,
+                &python_matrix,
             ),
         });
     }
 
     if need_node {
-        let want_node_attr = node_want_attr.as_deref().unwrap_or("nodejs");
         language_packages.push(LanguagePackages {
             language: Language::JavaScript,
             content: generate_nodejs_packages_nix(
                 want_node_attr,
-                node_notice.as_deref(),
+                &node_notices,
                 &required_package_managers,
                 &required_node_tools,
+                &node_matrix,
+                &package_manager_pins,
+                yarn_setup.as_ref(),
             ),
         });
     }
@@ -188,11 +286,22 @@ pub fn generate_dev_flake(metadata: &ProjectMetadata, root: &Path) -> GeneratedF
     if need_rust {
         language_packages.push(LanguagePackages {
             language: Language::Rust,
-            content: generate_rust_packages_nix(
-                rust_want_version.as_deref(),
-                rust_notice.as_deref(),
-            ),
+            content: generate_rust_packages_nix(&rust_plan, &rust_notices),
+        });
+    }
+
+    let mut custom_languages = Vec::new();
+    for generator in registry.language_generators() {
+        let language = generator.language();
+        if !detected_languages.contains(&language) || custom_languages.contains(&language) {
+            continue;
+        }
+
+        language_packages.push(LanguagePackages {
+            language: language.clone(),
+            content: generator.generate_packages_nix(metadata),
         });
+        custom_languages.push(language);
     }
 
     let rust_overlay = uses_rust_overlay.then(generate_rust_overlay_nix);
@@ -205,7 +314,7 @@ pub fn generate_dev_flake(metadata: &ProjectMetadata, root: &Path) -> GeneratedF
         need_python,
         need_node,
         need_rust,
-        uses_rust_overlay,
+        &custom_languages,
         &check_files,
         &required_task_runner_tools,
     );
@@ -249,8 +358,11 @@ fn required_node_tools(task_runners: &HashSet<TaskRunner>) -> BTreeSet<&'static
             | TaskRunner::Tox
             | TaskRunner::Nox
             | TaskRunner::Invoke
+            | TaskRunner::Hatch
+            | TaskRunner::Pixi
             | TaskRunner::Cargo
-            | TaskRunner::GoTask => {}
+            | TaskRunner::GoTask
+            | TaskRunner::Custom(_) => {}
         }
     }
 
@@ -280,13 +392,16 @@ fn required_task_runner_tools(metadata: &ProjectMetadata) -> BTreeSet<&'static s
             TaskRunner::Invoke => {
                 required.insert(constants::PYTHON_TOOL_INVOKE);
             }
+            // Provided by their package managers.
+            TaskRunner::Hatch | TaskRunner::Pixi => {}
             TaskRunner::GoTask | TaskRunner::Cargo | TaskRunner::NpmScripts => {}
             TaskRunner::Vite
             | TaskRunner::Webpack
             | TaskRunner::Rspack
             | TaskRunner::Rollup
             | TaskRunner::Turbo
-            | TaskRunner::Nx => {}
+            | TaskRunner::Nx
+            | TaskRunner::Custom(_) => {}
         }
     }
 
@@ -296,7 +411,7 @@ fn required_task_runner_tools(metadata: &ProjectMetadata) -> BTreeSet<&'static s
 fn generate_version_notice(
     language_name: &str,
     version_info: Option<&VersionInfo>,
-    selected_attr: Option<&str>,
+    choice: &AttrChoice,
     default_fallback: &str,
     note: Option<&str>,
 ) -> Option<String> {
@@ -304,7 +419,12 @@ fn generate_version_notice(
     let _parsed = version.parsed.as_ref()?;
 
     let requested = format!("{} (from {:?})", version.raw, version.source);
-    let selected = selected_attr.unwrap_or(default_fallback);
+    let selected = match (&choice.attr, &choice.reason) {
+        (Some(attr), Some(reason)) => format!("{attr} ({reason})"),
+        (Some(attr), None) => attr.clone(),
+        (None, Some(reason)) => format!("{} ({reason})", choice.default_attr),
+        (None, None) => default_fallback.to_string(),
+    };
     let note = note.unwrap_or("");
 
     Some(
@@ -314,7 +434,7 @@ fn generate_version_notice(
     )
 }
 
-fn go_notice(go_version: Option<&VersionInfo>, go_want_attr: Option<&str>) -> Option<String> {
+fn go_notice(go_version: Option<&VersionInfo>, choice: &AttrChoice) -> Option<String> {
     let patch_note = go_version
         .and_then(|v| v.parsed.as_ref())
         .filter(|p| p.patch.is_some())
@@ -323,16 +443,13 @@ fn go_notice(go_version: Option<&VersionInfo>, go_want_attr: Option<&str>) -> Op
     generate_version_notice(
         "Go",
         go_version,
-        go_want_attr,
+        choice,
         "go (unversioned; go_* not inferred)",
         patch_note,
     )
 }
 
-fn python_notice(
-    python_version: Option<&VersionInfo>,
-    python_want_attr: Option<&str>,
-) -> Option<String> {
+fn python_notice(python_version: Option<&VersionInfo>, choice: &AttrChoice) -> Option<String> {
     let patch_note = python_version
         .and_then(|v| v.parsed.as_ref())
         .filter(|p| p.patch.is_some() || !matches!(p.constraint, VersionConstraint::Exact))
@@ -341,13 +458,13 @@ fn python_notice(
     generate_version_notice(
         "Python",
         python_version,
-        python_want_attr,
+        choice,
         "python3 (unversioned; pythonXY not inferred)",
         patch_note,
     )
 }
 
-fn node_notice(node_version: Option<&VersionInfo>, node_want_attr: Option<&str>) -> Option<String> {
+fn node_notice(node_version: Option<&VersionInfo>, choice: &AttrChoice) -> Option<String> {
     let patch_note = node_version
         .and_then(|v| v.parsed.as_ref())
         .filter(|p| p.minor.is_some() || p.patch.is_some())
@@ -356,30 +473,74 @@ fn node_notice(node_version: Option<&VersionInfo>, node_want_attr: Option<&str>)
     generate_version_notice(
         "Node",
         node_version,
-        node_want_attr,
+        choice,
         "nodejs (unversioned; nodejs_* not inferred)",
         patch_note,
     )
 }
 
+/// The version notice for `language` followed by one notice per version
+/// conflict among its sources.
+fn language_notices(
+    metadata: &ProjectMetadata,
+    language: Language,
+    version_notice: Option<String>,
+) -> Vec<String> {
+    version_notice
+        .into_iter()
+        .chain(
+            metadata
+                .version_conflicts
+                .iter()
+                .filter(|conflict| conflict.language == language)
+                .map(|conflict| format!("version conflict: {}", conflict.summary())),
+        )
+        .collect()
+}
+
+fn notice_list(indent: &str, notices: &[String]) -> String {
+    let notices: Vec<&str> = notices.iter().map(String::as_str).collect();
+    nix_builder::NoticeListBuilder::new(indent).build_all(&notices)
+}
+
 fn rust_notice(
     need_rust: bool,
     rust_version: Option<&VersionInfo>,
-    rust_want_version: Option<&str>,
+    plan: &RustToolchainPlan,
 ) -> Option<String> {
     if !need_rust {
         return None;
     }
 
-    if let Some(v) = rust_want_version {
+    let requested = plan.requested.or(rust_version);
+
+    if let Some(v) = &plan.want_version {
+        let attr = if plan.want_release {
+            format!("newest rust-bin.stable.{v}.x")
+        } else {
+            format!("rust-bin.stable.{v}")
+        };
+        let fallback = if plan.pinned {
+            "fallback latest, with a warning"
+        } else {
+            "fallback latest"
+        };
         return Some(format!(
-            "Rust: requested {} -> try rust-bin.stable.{v} (fallback latest)",
-            rust_version
+            "Rust: requested {} -> try {attr} ({fallback})",
+            requested
                 .map(|vi| format!("{} (from {:?})", vi.raw, vi.source))
                 .unwrap_or_else(|| "(unknown)".to_string())
         ));
     }
 
+    if let Some(vi) = plan.requested {
+        let base = plan.base.trim_start_matches("pkgs.");
+        return Some(format!(
+            "Rust: requested {} (from {:?}) -> using {base}",
+            vi.raw, vi.source
+        ));
+    }
+
     if let Some(vi) = rust_version {
         return Some(format!(
             "Rust: detected {} (from {:?}) -> using rust-bin.stable.latest (not exact pin)",
@@ -398,7 +559,7 @@ fn generate_main_flake_header() -> String {
     out.push_str("# This flake uses a multi-file structure:\n");
     out.push_str("#   devShell.nix              - Development shell\n");
     out.push_str("#   {language}/packages.nix   - Language toolchains and tools\n");
-    out.push_str("#   {language}/*-checks.nix   - Build and test checks\n");
+    out.push_str("#   {language}/*-checks.nix   - Test, build, lint and format checks\n");
     out.push_str("#\n");
 
     out
@@ -412,7 +573,11 @@ fn generate_flake_inputs(uses_rust_overlay: bool) -> String {
     let mut out = String::new();
 
     out.push_str("  inputs = {\n");
-    out.push_str("    nixpkgs.url = \"github:NixOS/nixpkgs/nixos-unstable\";\n");
+    let _ = writeln!(
+        out,
+        "    nixpkgs.url = \"github:NixOS/nixpkgs/{}\";",
+        constants::NIXPKGS_CHANNEL
+    );
     out.push_str("    flake-utils.url = \"github:numtide/flake-utils\";\n");
 
     if uses_rust_overlay {
@@ -433,6 +598,7 @@ fn generate_flake_let_bindings(
     need_python: bool,
     need_node: bool,
     need_rust: bool,
+    custom_languages: &[Language],
     required_task_runner_tools: &BTreeSet<&'static str>,
 ) -> String {
     let mut out = String::new();
@@ -467,7 +633,15 @@ fn generate_flake_let_bindings(
             "        rustPackages = import ./rust/packages.nix { inherit pkgs lib; };\n",
         );
     }
-    if need_go || need_python || need_node || need_rust {
+    for language in custom_languages {
+        let dir = language.dir_name();
+        writeln!(
+            out,
+            "        {dir}Packages = import ./{dir}/packages.nix {{ inherit pkgs lib; }};"
+        )
+        .unwrap();
+    }
+    if need_go || need_python || need_node || need_rust || !custom_languages.is_empty() {
         out.push('\n');
     }
 
@@ -482,21 +656,24 @@ fn generate_flake_let_bindings(
         out.push_str("        ];\n\n");
     }
 
-    let mut dev_sources: Vec<&str> = Vec::new();
+    let mut dev_sources: Vec<String> = Vec::new();
     if include_generic_packages {
-        dev_sources.push("genericPackages");
+        dev_sources.push("genericPackages".to_string());
     }
     if need_go {
-        dev_sources.push("golangPackages.packages");
+        dev_sources.push("golangPackages.packages".to_string());
     }
     if need_python {
-        dev_sources.push("pythonPackages.packages");
+        dev_sources.push("pythonPackages.packages".to_string());
     }
     if need_node {
-        dev_sources.push("nodejsPackages.packages");
+        dev_sources.push("nodejsPackages.packages".to_string());
     }
     if need_rust {
-        dev_sources.push("rustPackages.packages");
+        dev_sources.push("rustPackages.packages".to_string());
+    }
+    for language in custom_languages {
+        dev_sources.push(format!("{}Packages.packages", language.dir_name()));
     }
 
     out.push_str("        devPackages = []");
@@ -505,18 +682,21 @@ fn generate_flake_let_bindings(
     }
     out.push_str(";\n\n");
 
-    let mut notice_sources: Vec<&str> = Vec::new();
+    let mut notice_sources: Vec<String> = Vec::new();
     if need_go {
-        notice_sources.push("golangPackages.notices");
+        notice_sources.push("golangPackages.notices".to_string());
     }
     if need_python {
-        notice_sources.push("pythonPackages.notices");
+        notice_sources.push("pythonPackages.notices".to_string());
     }
     if need_node {
-        notice_sources.push("nodejsPackages.notices");
+        notice_sources.push("nodejsPackages.notices".to_string());
     }
     if need_rust {
-        notice_sources.push("rustPackages.notices");
+        notice_sources.push("rustPackages.notices".to_string());
+    }
+    for language in custom_languages {
+        notice_sources.push(format!("{}Packages.notices", language.dir_name()));
     }
 
     out.push_str("        notices = []");
@@ -589,11 +769,12 @@ fn generate_main_flake(
     need_python: bool,
     need_node: bool,
     need_rust: bool,
-    uses_rust_overlay: bool,
+    custom_languages: &[Language],
     check_files: &[CheckFile],
     required_task_runner_tools: &BTreeSet<&'static str>,
 ) -> String {
     let mut out = String::new();
+    let uses_rust_overlay = need_rust;
 
     out.push_str(&generate_main_flake_header());
 
@@ -615,6 +796,7 @@ fn generate_main_flake(
         need_python,
         need_node,
         need_rust,
+        custom_languages,
         required_task_runner_tools,
     ));
 
@@ -703,7 +885,7 @@ fn generate_rust_overlay_nix() -> String {
     out
 }
 
-fn generate_golang_packages_nix(want_go_attr: &str, notice: Option<&str>) -> String {
+fn generate_golang_packages_nix(want_go_attr: &str, notices: &[String]) -> String {
     let mut out = String::new();
 
     out.push_str(&generate_file_header("Go toolchain and development tools"));
@@ -714,7 +896,7 @@ fn generate_golang_packages_nix(want_go_attr: &str, notice: Option<&str>) -> Str
     nix_builder::write_attr_with_fallback(&mut out, "  ", "goAttr", "wantGoAttr", "pkgs", "go");
     out.push_str("  go = pkgs.${goAttr};\n\n");
 
-    out.push_str(&nix_builder::NoticeListBuilder::new("  ").build(notice));
+    out.push_str(&notice_list("  ", notices));
 
     out.push_str("in\n{\n");
     out.push_str("  inherit go goAttr wantGoAttr notices;\n\n");
@@ -729,9 +911,10 @@ fn generate_golang_packages_nix(want_go_attr: &str, notice: Option<&str>) -> Str
 
 fn generate_python_packages_nix(
     want_python_attr: &str,
-    notice: Option<&str>,
+    notices: &[String],
     required_package_managers: &HashSet<PackageManager>,
     required_task_runner_tools: &BTreeSet<&'static str>,
+    python_matrix: &[String],
 ) -> String {
     let include_tox = required_task_runner_tools.contains(constants::PYTHON_TOOL_TOX);
     let include_nox = required_task_runner_tools.contains(constants::PYTHON_TOOL_NOX);
@@ -741,6 +924,10 @@ fn generate_python_packages_nix(
     let include_uv = required_package_managers.contains(&PackageManager::Uv);
     let include_pdm = required_package_managers.contains(&PackageManager::Pdm);
     let include_pipenv = required_package_managers.contains(&PackageManager::Pipenv);
+    let include_hatch = required_package_managers.contains(&PackageManager::Hatch);
+    let include_pixi = required_package_managers.contains(&PackageManager::Pixi);
+    let include_pip_tools = required_package_managers.contains(&PackageManager::PipTools);
+    let include_conda = required_package_managers.contains(&PackageManager::Conda);
 
     let mut out = String::new();
 
@@ -771,6 +958,7 @@ fn generate_python_packages_nix(
             tool = constants::PYTHON_TOOL_TOX,
         ));
     }
+
     if include_nox {
         out.push_str(&format!(
             "  {tool} = if builtins.hasAttr \"{tool}\" pythonPackages then pythonPackages.{tool} else null;\n",
@@ -800,18 +988,51 @@ fn generate_python_packages_nix(
             "  pipenv = if builtins.hasAttr \"pipenv\" pkgs then pkgs.pipenv else null;\n",
         );
     }
+    if include_hatch {
+        out.push_str("  hatch = if builtins.hasAttr \"hatch\" pkgs then pkgs.hatch else null;\n");
+    }
+    if include_pixi {
+        out.push_str("  pixi = if builtins.hasAttr \"pixi\" pkgs then pkgs.pixi else null;\n");
+    }
+    if include_pip_tools {
+        out.push_str(
+            "  pip-tools = if builtins.hasAttr \"pip-tools\" pythonPackages then pythonPackages.pip-tools else null;\n",
+        );
+    }
+    if include_conda {
+        // micromamba solves conda environment files without a base install.
+        out.push_str(
+            "  micromamba = if builtins.hasAttr \"micromamba\" pkgs then pkgs.micromamba else null;\n",
+        );
+    }
+
+    if !python_matrix.is_empty() {
+        writeln!(
+            out,
+            "  pythonMatrixAttrs = {};",
+            nix_string_list(python_matrix)
+        )
+        .unwrap();
+        out.push_str(
+            "  pythonMatrix = map (attr: pkgs.${attr}) (builtins.filter (attr: builtins.hasAttr attr pkgs) pythonMatrixAttrs);\n",
+        );
+    }
 
     out.push_str(
         "  pyright = if builtins.hasAttr \"pyright\" pkgs then pkgs.pyright\n    else if builtins.hasAttr \"pyright\" pkgs.nodePackages then pkgs.nodePackages.pyright\n    else null;\n\n",
     );
 
-    out.push_str(&nix_builder::NoticeListBuilder::new("  ").build(notice));
+    out.push_str(&notice_list("  ", notices));
 
     out.push_str("in\n{\n");
     out.push_str("  inherit python pythonPackages pythonAttr wantPythonAttr notices;\n\n");
 
     out.push_str("  packages = [ python ]");
 
+    if !python_matrix.is_empty() {
+        out.push_str("\n    ++ pythonMatrix");
+    }
+
     if include_tox {
         out.push_str("\n    ++ lib.optional (tox != null) tox");
     }
@@ -834,6 +1055,18 @@ fn generate_python_packages_nix(
     if include_pipenv {
         out.push_str("\n    ++ lib.optional (pipenv != null) pipenv");
     }
+    if include_hatch {
+        out.push_str("\n    ++ lib.optional (hatch != null) hatch");
+    }
+    if include_pixi {
+        out.push_str("\n    ++ lib.optional (pixi != null) pixi");
+    }
+    if include_pip_tools {
+        out.push_str("\n    ++ lib.optional (pip-tools != null) pip-tools");
+    }
+    if include_conda {
+        out.push_str("\n    ++ lib.optional (micromamba != null) micromamba");
+    }
 
     out.push_str("\n    ++ lib.optional (pyright != null) pyright");
 
@@ -845,9 +1078,12 @@ fn generate_python_packages_nix(
 
 fn generate_nodejs_packages_nix(
     want_node_attr: &str,
-    notice: Option<&str>,
+    notices: &[String],
     required_package_managers: &HashSet<PackageManager>,
     required_node_tools: &BTreeSet<&'static str>,
+    node_matrix: &[String],
+    pins: &[PackageManagerPin],
+    yarn_setup: Option<&YarnSetup>,
 ) -> String {
     let include_pnpm = required_package_managers.contains(&PackageManager::Pnpm);
     let include_yarn = required_package_managers.contains(&PackageManager::Yarn);
@@ -873,13 +1109,39 @@ fn generate_nodejs_packages_nix(
     );
     out.push_str("  node = pkgs.${nodeAttr};\n\n");
 
-    if include_pnpm {
+    let pin_for = |pm: PackageManager| pins.iter().find(|pin| pin.package_manager == pm);
+    let pnpm_pin = pin_for(PackageManager::Pnpm).filter(|_| include_pnpm);
+    let yarn_pin = pin_for(PackageManager::Yarn).filter(|_| include_yarn);
+    let pinned: Vec<(&str, &PackageManagerPin)> = [("pnpm", pnpm_pin), ("yarn", yarn_pin)]
+        .into_iter()
+        .filter_map(|(name, pin)| Some((name, pin?)))
+        .collect();
+    let uses_corepack = pinned.iter().any(|(_, pin)| pin.via_corepack);
+
+    if uses_corepack {
         out.push_str(
-            "  pnpm = if builtins.hasAttr \"pnpm\" pkgs.nodePackages then pkgs.nodePackages.pnpm else null;\n",
+            "  corepack = if builtins.hasAttr \"corepack\" pkgs then pkgs.corepack else null;\n",
         );
     }
+    if include_pnpm {
+        let fallback =
+            "if builtins.hasAttr \"pnpm\" pkgs.nodePackages then pkgs.nodePackages.pnpm else null";
+        match pnpm_pin {
+            Some(pin) => write_pinned_package_manager(&mut out, "pnpm", fallback, pin),
+            None => writeln!(out, "  pnpm = {fallback};").unwrap(),
+        }
+    }
     if include_yarn {
-        out.push_str("  yarn = if builtins.hasAttr \"yarn\" pkgs then pkgs.yarn else null;\n");
+        // Berry projects must not fall back to the 1.x `pkgs.yarn`.
+        let fallback = if yarn_setup.is_some_and(|setup| setup.berry) {
+            "if builtins.hasAttr \"yarn-berry\" pkgs then pkgs.yarn-berry else null"
+        } else {
+            "if builtins.hasAttr \"yarn\" pkgs then pkgs.yarn else null"
+        };
+        match yarn_pin {
+            Some(pin) => write_pinned_package_manager(&mut out, "yarn", fallback, pin),
+            None => writeln!(out, "  yarn = {fallback};").unwrap(),
+        }
     }
     if include_bun {
         out.push_str("  bun = if builtins.hasAttr \"bun\" pkgs then pkgs.bun else null;\n");
@@ -894,11 +1156,43 @@ fn generate_nodejs_packages_nix(
         ));
     }
 
+    // Every Node binary is called `node`, so the extra versions are
+    // reachable as `node20`, `node22`, ... instead.
+    if !node_matrix.is_empty() {
+        writeln!(out, "  nodeMatrixAttrs = {};", nix_string_list(node_matrix)).unwrap();
+        out.push_str(
+            "  nodeMatrix = map (attr: pkgs.writeShellScriptBin \"node${lib.removePrefix \"nodejs_\" attr}\" ''\n    exec ${pkgs.${attr}}/bin/node \"$@\"\n  '') (builtins.filter (attr: builtins.hasAttr attr pkgs) nodeMatrixAttrs);\n",
+        );
+    }
+
+    if !pinned.is_empty() {
+        out.push_str("  packageManagerNotices = []");
+        for (name, pin) in &pinned {
+            let outcome = if pin.via_corepack {
+                "using corepack"
+            } else {
+                "using nixpkgs"
+            };
+            write!(
+                out,
+                "\n    ++ lib.optional (!{name}Exact) \"{name}: {} pins ${{{name}Pinned}} but nixpkgs has ${{if {name}Nix != null then lib.getVersion {name}Nix else \"none\"}}; {outcome}\"",
+                pin.origin()
+            )
+            .unwrap();
+        }
+        out.push_str(";\n");
+    }
+
     out.push('\n');
-    out.push_str(&nix_builder::NoticeListBuilder::new("  ").build(notice));
+    out.push_str(&notice_list("  ", notices));
 
     out.push_str("in\n{\n");
-    out.push_str("  inherit node nodeAttr wantNodeAttr notices;\n\n");
+    if pinned.is_empty() {
+        out.push_str("  inherit node nodeAttr wantNodeAttr notices;\n\n");
+    } else {
+        out.push_str("  inherit node nodeAttr wantNodeAttr;\n");
+        out.push_str("  notices = notices ++ packageManagerNotices;\n\n");
+    }
 
     out.push_str("  packages =\n");
     out.push_str("    [\n");
@@ -913,6 +1207,18 @@ fn generate_nodejs_packages_nix(
     ));
     out.push_str("    ]");
 
+    if !node_matrix.is_empty() {
+        out.push_str("\n    ++ nodeMatrix");
+    }
+    for (name, pin) in &pinned {
+        if pin.via_corepack {
+            write!(
+                out,
+                "\n    ++ lib.optional (corepack != null && !{name}Exact) corepack"
+            )
+            .unwrap();
+        }
+    }
     if include_pnpm {
         out.push_str("\n    ++ lib.optional (pnpm != null) pnpm");
     }
@@ -936,7 +1242,128 @@ fn generate_nodejs_packages_nix(
     out
 }
 
-fn generate_rust_packages_nix(want_rust_version: Option<&str>, notice: Option<&str>) -> String {
+/// A JavaScript package manager version pinned by `package.json`.
+#[derive(Debug, Clone)]
+struct PackageManagerPin {
+    package_manager: PackageManager,
+    version: String,
+    /// Pinned by the `packageManager` field, which corepack honours; otherwise
+    /// by the `volta` block.
+    via_corepack: bool,
+}
+
+impl PackageManagerPin {
+    fn origin(&self) -> &'static str {
+        if self.via_corepack {
+            "packageManager"
+        } else {
+            "volta"
+        }
+    }
+
+    /// The nixpkgs attribute packaging the pinned major version.
+    fn attr(&self) -> Option<String> {
+        let major: u32 = self.version.split('.').next()?.parse().ok()?;
+        match self.package_manager {
+            PackageManager::Pnpm => Some(format!("pnpm_{major}")),
+            PackageManager::Yarn if major == 1 => Some("yarn".to_string()),
+            PackageManager::Yarn => Some(format!("yarn-berry_{major}")),
+            _ => None,
+        }
+    }
+}
+
+/// The root-most pin of each package manager, preferring `packageManager`
+/// over `volta` since that is what corepack and the package manager itself
+/// enforce. Pins that disagree are reported by
+/// [`VersionConflict::from_package_managers`](crate::detection::VersionConflict::from_package_managers).
+fn package_manager_pins(metadata: &ProjectMetadata) -> Vec<PackageManagerPin> {
+    let mut candidates: Vec<&PackageManagerInfo> = metadata
+        .package_managers
+        .iter()
+        .flat_map(|pm| pm.package_managers.iter())
+        .filter(|info| info.version.is_some())
+        .filter(|info| {
+            matches!(
+                info.source,
+                PackageManagerSource::PackageJson | PackageManagerSource::Volta
+            )
+        })
+        .collect();
+    candidates.sort_by_key(|info| {
+        (
+            info.path.components().count(),
+            !matches!(info.source, PackageManagerSource::PackageJson),
+        )
+    });
+
+    let mut pins: Vec<PackageManagerPin> = Vec::new();
+    for info in candidates {
+        if pins
+            .iter()
+            .any(|pin| pin.package_manager == info.package_manager)
+        {
+            continue;
+        }
+        let Some(version) = info.version.as_deref() else {
+            continue;
+        };
+        pins.push(PackageManagerPin {
+            package_manager: info.package_manager,
+            // corepack pins may carry a hash: `pnpm@9.0.0+sha512.abc`.
+            version: version.split('+').next().unwrap_or(version).to_string(),
+            via_corepack: matches!(info.source, PackageManagerSource::PackageJson),
+        });
+    }
+    pins
+}
+
+/// The setup of the root-most Yarn project.
+fn yarn_setup(metadata: &ProjectMetadata, fs: &dyn FileSystem) -> Option<YarnSetup> {
+    let lockfile = metadata
+        .package_managers
+        .iter()
+        .flat_map(|pm| pm.package_managers.iter())
+        .filter(|info| matches!(info.source, PackageManagerSource::YarnLock))
+        .min_by_key(|info| info.path.components().count())?;
+    Some(YarnSetup::detect(fs, lockfile.path.parent()?))
+}
+
+fn write_pinned_package_manager(
+    out: &mut String,
+    name: &str,
+    fallback: &str,
+    pin: &PackageManagerPin,
+) {
+    let candidate = match pin.attr() {
+        Some(attr) => {
+            let attr = nix_builder::escape_nix_string(&attr);
+            format!("if builtins.hasAttr \"{attr}\" pkgs then pkgs.\"{attr}\" else {fallback}")
+        }
+        None => fallback.to_string(),
+    };
+
+    nix_builder::write_nix_string_binding(out, "  ", &format!("{name}Pinned"), &pin.version);
+    writeln!(out, "  {name}Nix = {candidate};").unwrap();
+    writeln!(
+        out,
+        "  {name}Exact = {name}Nix != null && lib.getVersion {name}Nix == {name}Pinned;"
+    )
+    .unwrap();
+    if pin.via_corepack {
+        // nixpkgs' corepack ships enabled `pnpm`/`yarn` shims that fetch the
+        // exact `packageManager` version when nixpkgs does not package it.
+        writeln!(
+            out,
+            "  {name} = if {name}Exact || corepack == null then {name}Nix else null;"
+        )
+        .unwrap();
+    } else {
+        writeln!(out, "  {name} = {name}Nix;").unwrap();
+    }
+}
+
+fn generate_rust_packages_nix(plan: &RustToolchainPlan, notices: &[String]) -> String {
     let mut out = String::new();
 
     out.push_str(&generate_file_header(
@@ -945,21 +1372,49 @@ fn generate_rust_packages_nix(want_rust_version: Option<&str>, notice: Option<&s
     out.push_str("{ pkgs, lib }:\n\n");
 
     out.push_str("let\n");
-    if let Some(want) = want_rust_version {
+    if let Some(want) = &plan.want_version {
+        let profile = plan.profile;
+        let fallback = if plan.pinned {
+            format!(
+                "lib.warn \"rust-toolchain pins Rust ${{wantRustVersion}}, which rust-overlay does not provide; using the latest stable\" {}",
+                plan.base
+            )
+        } else {
+            plan.base.clone()
+        };
         nix_builder::write_nix_string_binding(&mut out, "  ", "wantRustVersion", want);
-        out.push_str(
-            "  rustToolchainBase = if builtins.hasAttr wantRustVersion pkgs.rust-bin.stable\n    then pkgs.rust-bin.stable.${wantRustVersion}.default\n    else pkgs.rust-bin.stable.latest.default;\n",
-        );
+        if plan.want_release {
+            out.push_str("  rustReleasePatches = lib.filter (v: lib.hasPrefix \"${wantRustVersion}.\" v)\n    (builtins.attrNames pkgs.rust-bin.stable);\n");
+            writeln!(
+                out,
+                "  rustToolchainBase = if rustReleasePatches != [ ]\n    then pkgs.rust-bin.stable.${{lib.last (lib.sort lib.versionOlder rustReleasePatches)}}.{profile}\n    else {fallback};"
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                out,
+                "  rustToolchainBase = if builtins.hasAttr wantRustVersion pkgs.rust-bin.stable\n    then pkgs.rust-bin.stable.${{wantRustVersion}}.{profile}\n    else {fallback};"
+            )
+            .unwrap();
+        }
     } else {
-        out.push_str("  rustToolchainBase = pkgs.rust-bin.stable.latest.default;\n");
+        writeln!(out, "  rustToolchainBase = {};", plan.base).unwrap();
     }
 
     out.push('\n');
     out.push_str("  rustToolchain = rustToolchainBase.override {\n");
-    out.push_str("    extensions = [ \"rust-src\" \"rust-analyzer\" ];\n");
+    writeln!(
+        out,
+        "    extensions = {};",
+        nix_string_list(&plan.extensions)
+    )
+    .unwrap();
+    if !plan.targets.is_empty() {
+        writeln!(out, "    targets = {};", nix_string_list(&plan.targets)).unwrap();
+    }
     out.push_str("  };\n\n");
 
-    out.push_str(&nix_builder::NoticeListBuilder::new("  ").build(notice));
+    out.push_str(&notice_list("  ", notices));
 
     out.push_str("in\n{\n");
     out.push_str("  inherit rustToolchain notices;\n\n");
@@ -969,6 +1424,151 @@ fn generate_rust_packages_nix(want_rust_version: Option<&str>, notice: Option<&s
     out
 }
 
+fn nix_string_list(values: &[String]) -> String {
+    let items: Vec<String> = values
+        .iter()
+        .map(|v| format!("\"{}\"", nix_builder::escape_nix_string(v)))
+        .collect();
+    format!("[ {} ]", items.join(" "))
+}
+
+/// How `rust/packages.nix` selects its toolchain from rust-overlay.
+struct RustToolchainPlan<'a> {
+    /// The version source the selection follows, when it is a toolchain file.
+    requested: Option<&'a VersionInfo>,
+    /// A stable release tried first, falling back to `base`.
+    want_version: Option<String>,
+    /// Whether `want_version` is a `major.minor` release, which resolves to
+    /// its newest patch.
+    want_release: bool,
+    /// Whether a toolchain file pins `want_version`, so falling back to
+    /// `base` deserves a warning.
+    pinned: bool,
+    base: String,
+    profile: &'static str,
+    extensions: Vec<String>,
+    targets: Vec<String>,
+}
+
+/// Follows the root-most `rust-toolchain(.toml)` the way rustup would, and
+/// otherwise pins stable to an exact version from any other source.
+fn rust_toolchain_plan<'a>(
+    metadata: &'a ProjectMetadata,
+    root: &Path,
+    rust_version: Option<&VersionInfo>,
+    fs: &dyn FileSystem,
+) -> RustToolchainPlan<'a> {
+    let toolchain_file = metadata
+        .versions
+        .iter()
+        .find(|vd| vd.language == Language::Rust)
+        .into_iter()
+        .flat_map(|vd| vd.effective_versions())
+        .filter(|v| {
+            matches!(
+                v.source,
+                VersionSource::RustToolchainFile | VersionSource::RustToolchainToml
+            )
+        })
+        .min_by_key(|v| v.path.components().count());
+    let spec = toolchain_file.and_then(|v| RustToolchainSpec::from_path(fs, &v.path));
+
+    let profile = match spec.as_ref().map(|s| s.profile) {
+        Some(RustProfile::Minimal) => "minimal",
+        _ => "default",
+    };
+    let latest_stable = format!("pkgs.rust-bin.stable.latest.{profile}");
+
+    let mut extensions = vec!["rust-src".to_string(), "rust-analyzer".to_string()];
+    let mut targets = Vec::new();
+    if let Some(spec) = &spec {
+        for component in &spec.components {
+            if !extensions.contains(component) {
+                extensions.push(component.clone());
+            }
+        }
+        targets.clone_from(&spec.targets);
+    }
+
+    let exact_stable = |version: &SemanticVersion| {
+        matches!(version.constraint, VersionConstraint::Exact)
+            .then(|| rust_version_string_from_version(version))
+            .flatten()
+    };
+
+    // `channel = "1.78"` means the newest 1.78.x, as rustup reads it.
+    let stable_release = |version: &SemanticVersion| match version {
+        SemanticVersion {
+            major: Some(major),
+            minor: Some(minor),
+            patch: None,
+            constraint: VersionConstraint::Exact,
+            ..
+        } => Some(format!("{major}.{minor}")),
+        _ => None,
+    };
+
+    let channel = spec.as_ref().and_then(|s| s.rust_channel());
+    let pinned = matches!(channel, Some(RustChannel::Version(_)));
+    let pinned_version = toolchain_file
+        .filter(|_| pinned)
+        .and_then(|v| v.parsed.as_ref());
+    let want_release = pinned_version.is_some_and(|v| stable_release(v).is_some());
+
+    let (want_version, base) = match channel {
+        None => (
+            rust_version
+                .and_then(|v| v.parsed.as_ref())
+                .and_then(exact_stable),
+            latest_stable,
+        ),
+        Some(RustChannel::Stable) => (None, latest_stable),
+        Some(RustChannel::Version(_)) => (
+            pinned_version.and_then(|v| exact_stable(v).or_else(|| stable_release(v))),
+            latest_stable,
+        ),
+        Some(RustChannel::Beta(None)) => (None, format!("pkgs.rust-bin.beta.latest.{profile}")),
+        Some(RustChannel::Beta(Some(date))) => {
+            (None, format!("pkgs.rust-bin.beta.\"{date}\".{profile}"))
+        }
+        Some(RustChannel::Nightly(None)) => (
+            None,
+            format!("pkgs.rust-bin.selectLatestNightlyWith (toolchain: toolchain.{profile})"),
+        ),
+        Some(RustChannel::Nightly(Some(date))) => {
+            (None, format!("pkgs.rust-bin.nightly.\"{date}\".{profile}"))
+        }
+        Some(RustChannel::Other(_)) => {
+            let relative = spec
+                .as_ref()
+                .and_then(|s| relativize_path(root, &s.path))
+                .map(|p| p.to_string_lossy().into_owned());
+            match relative {
+                // `rust/packages.nix` sits two levels below the project root.
+                Some(relative) => (
+                    None,
+                    format!(
+                        "pkgs.rust-bin.fromRustupToolchainFile (../.. + \"/{}\")",
+                        nix_builder::escape_nix_string(&relative)
+                    ),
+                ),
+                None => (None, latest_stable),
+            }
+        }
+    };
+
+    RustToolchainPlan {
+        requested: toolchain_file.filter(|_| spec.as_ref().is_some_and(|s| s.channel.is_some())),
+        want_version,
+        want_release,
+        pinned,
+        base,
+        profile,
+        extensions,
+        targets,
+    }
+}
+
 fn detected_languages(metadata: &ProjectMetadata) -> HashSet<Language> {
     metadata
         .languages
@@ -1001,16 +1601,17 @@ fn best_version_info<'a>(
         .versions
         .iter()
         .find(|vd| vd.language == language)?
-        .versions
-        .iter()
+        .effective_versions()
         .filter(|v| allowed_sources.contains(&v.source))
         .filter(|v| v.parsed.is_some());
 
+    // A single-version pin outranks any entry of a matrix.
     versions.max_by_key(|v| {
         let Some(parsed) = &v.parsed else {
-            return (0_u32, 0_u32, 0_u32);
+            return (false, 0_u32, 0_u32, 0_u32);
         };
         (
+            !v.source.is_matrix(),
             parsed.major.unwrap_or(0),
             parsed.minor.unwrap_or(0),
             parsed.patch.unwrap_or(0),
@@ -1018,32 +1619,228 @@ fn best_version_info<'a>(
     })
 }
 
-fn go_attr_from_version(version: &SemanticVersion) -> Option<String> {
-    let major = version.major?;
-    let minor = version.minor?;
-    Some(format!("go_{major}_{minor}"))
+/// Intersection of every parseable, effective requirement on `language`
+/// from `allowed_sources`.
+fn version_range(
+    metadata: &ProjectMetadata,
+    language: Language,
+    allowed_sources: &[VersionSource],
+) -> Option<VersionRange> {
+    metadata
+        .versions
+        .iter()
+        .find(|vd| vd.language == language)?
+        .effective_versions()
+        .filter(|v| allowed_sources.contains(&v.source))
+        // CI matrices and tox envs list alternatives, not requirements, and
+        // a matrix's entries are each required alongside the primary.
+        .filter(|v| !v.source.is_fallback() && !v.source.is_matrix())
+        .filter_map(VersionInfo::range)
+        .reduce(|acc, range| acc.intersect(&range))
 }
 
-fn python_attr_from_version(version: &SemanticVersion) -> Option<String> {
-    let major = version.major?;
-    let minor = version.minor?;
-    Some(format!("python{major}{minor}"))
+/// The toolchain attribute chosen for a language, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AttrChoice {
+    attr: Option<String>,
+    reason: Option<String>,
+    /// The toolchain's unversioned attribute, used when `attr` is `None`.
+    default_attr: &'static str,
 }
 
-fn node_attr_from_version(version: &SemanticVersion) -> Option<String> {
-    let major = version.major?;
-    Some(format!("nodejs_{major}"))
+impl AttrChoice {
+    fn want(&self) -> &str {
+        self.attr.as_deref().unwrap_or(self.default_attr)
+    }
 }
 
-fn rust_version_string_from_version(version: &SemanticVersion) -> Option<String> {
-    let major = version.major?;
-    let minor = version.minor?;
+/// Resolves the requirements on `language` against the catalog channel the
+/// generated flake follows.
+///
+/// The newest attribute inside the intersected range wins. When sources
+/// disagree, or their requirements cannot be parsed as a range, the highest
+/// requested version is used if the channel packages it. Without a fitting
+/// attribute the unversioned default is kept, and the reason says so.
+fn resolve_attr(
+    catalog: &NixpkgsCatalog,
+    toolchain: Toolchain,
+    metadata: &ProjectMetadata,
+    language: Language,
+    allowed_sources: &[VersionSource],
+    attr_from_version: fn(&SemanticVersion) -> Option<String>,
+) -> AttrChoice {
+    let channel_name = constants::NIXPKGS_CHANNEL;
+    let range = version_range(metadata, language.clone(), allowed_sources);
+    let highest_attr = best_version_info(metadata, language, allowed_sources)
+        .and_then(|v| v.parsed.as_ref())
+        .and_then(attr_from_version);
+
+    let Some(channel) = catalog.channel(channel_name) else {
+        return AttrChoice {
+            attr: highest_attr,
+            reason: None,
+            default_attr: toolchain.default_attr(),
+        };
+    };
+
+    if let Some(range) = range.as_ref().filter(|range| !range.is_empty()) {
+        return match channel.best_attr(toolchain, range) {
+            Some(attr) => AttrChoice {
+                attr: Some(attr.to_string()),
+                reason: Some(format!(
+                    "newest {} in {channel_name} within the requested range",
+                    toolchain.attr_pattern()
+                )),
+                default_attr: toolchain.default_attr(),
+            },
+            None => AttrChoice {
+                attr: None,
+                reason: Some(format!(
+                    "no {} in {channel_name} satisfies the request; available: {}",
+                    toolchain.attr_pattern(),
+                    channel.attrs(toolchain).join(", ")
+                )),
+                default_attr: toolchain.default_attr(),
+            },
+        };
+    }
+
+    let conflict = range.is_some();
+    match highest_attr {
+        Some(attr) if channel.contains(toolchain, &attr) => AttrChoice {
+            attr: Some(attr),
+            reason: conflict.then(|| {
+                "requirements from different sources do not overlap; using the highest".to_string()
+            }),
+            default_attr: toolchain.default_attr(),
+        },
+        Some(attr) => AttrChoice {
+            attr: None,
+            reason: Some(format!("{attr} is not packaged in {channel_name}")),
+            default_attr: toolchain.default_attr(),
+        },
+        None => AttrChoice {
+            attr: None,
+            reason: None,
+            default_attr: toolchain.default_attr(),
+        },
+    }
+}
+
+/// The attributes for the [required versions](crate::detection::VersionDetection::required_versions)
+/// of `language` besides `primary_attr`, and a notice for each one the
+/// catalog channel does not package.
+fn matrix_attrs(
+    metadata: &ProjectMetadata,
+    catalog: &NixpkgsCatalog,
+    toolchain: Toolchain,
+    language: Language,
+    allowed_sources: &[VersionSource],
+    primary_attr: &str,
+    attr_from_version: fn(&SemanticVersion) -> Option<String>,
+) -> (Vec<String>, Vec<String>) {
+    let channel_name = constants::NIXPKGS_CHANNEL;
+    let channel = catalog.channel(channel_name);
+    let mut attrs: Vec<String> = Vec::new();
+    let mut notices = Vec::new();
+
+    let Some(detection) = metadata.versions.iter().find(|vd| vd.language == language) else {
+        return (attrs, notices);
+    };
+    let required = detection
+        .required_versions()
+        .into_iter()
+        .filter(|v| allowed_sources.contains(&v.source));
+
+    for version in required {
+        let Some(attr) = version.parsed.as_ref().and_then(attr_from_version) else {
+            continue;
+        };
+        if attr == primary_attr || attrs.contains(&attr) {
+            continue;
+        }
+        if channel.is_some_and(|c| !c.contains(toolchain, &attr)) {
+            notices.push(format!(
+                "{}: {} (from {:?}) skipped ({attr} is not packaged in {channel_name})",
+                language_display_name(Some(language.clone())),
+                version.raw,
+                version.source
+            ));
+            continue;
+        }
+        attrs.push(attr);
+    }
+
+    (attrs, notices)
+}
+
+/// Adds a copy of each `language` test check per matrix attribute, with that
+/// toolchain first on `PATH`. tox and nox pick their interpreters themselves.
+fn parameterize_checks(
+    checks_by_lang: &mut HashMap<Option<Language>, HashMap<CheckCategory, Vec<CheckSpec>>>,
+    language: Language,
+    matrix: &[String],
+) {
+    if matrix.is_empty() {
+        return;
+    }
+    let Some(checks) = checks_by_lang
+        .get_mut(&Some(language))
+        .and_then(|by_cat| by_cat.get_mut(&CheckCategory::Test))
+    else {
+        return;
+    };
+
+    let variants: Vec<CheckSpec> = checks
+        .iter()
+        .filter(|check| {
+            check.required_exec != constants::PYTHON_TOOL_TOX
+                && check.required_exec != constants::PYTHON_TOOL_NOX
+        })
+        .flat_map(|check| {
+            matrix.iter().map(move |attr| {
+                let slug = slugify_identifier(attr);
+                CheckSpec {
+                    key: format!("{}-{slug}", check.key),
+                    derivation_name: format!("{}-{slug}", check.derivation_name),
+                    display: format!("{} ({attr})", check.display),
+                    toolchain_attr: Some(attr.clone()),
+                    ..check.clone()
+                }
+            })
+        })
+        .collect();
+
+    checks.extend(variants);
+    checks.sort_by(|a, b| a.key.cmp(&b.key));
+}
+
+fn go_attr_from_version(version: &SemanticVersion) -> Option<String> {
+    let major = version.major?;
+    let minor = version.minor?;
+    Some(format!("go_{major}_{minor}"))
+}
+
+fn python_attr_from_version(version: &SemanticVersion) -> Option<String> {
+    let major = version.major?;
+    let minor = version.minor?;
+    Some(format!("python{major}{minor}"))
+}
+
+fn node_attr_from_version(version: &SemanticVersion) -> Option<String> {
+    let major = version.major?;
+    Some(format!("nodejs_{major}"))
+}
+
+fn rust_version_string_from_version(version: &SemanticVersion) -> Option<String> {
+    let major = version.major?;
+    let minor = version.minor?;
     let patch = version.patch?;
     Some(format!("{major}.{minor}.{patch}"))
 }
 
-fn read_package_json_manager(path: &Path) -> Option<PackageManager> {
-    let content = fs::read_to_string(path).ok()?;
+fn read_package_json_manager(path: &Path, fs: &dyn FileSystem) -> Option<PackageManager> {
+    let content = fs.read_to_string(path).ok()?;
     let parsed: JsonValue = serde_json::from_str(&content).ok()?;
     let package_manager_str = parsed.get("packageManager")?.as_str()?;
 
@@ -1061,25 +1858,25 @@ fn read_package_json_manager(path: &Path) -> Option<PackageManager> {
     }
 }
 
-fn detect_lockfile_manager(dir: &Path) -> Option<PackageManager> {
-    if dir.join("bun.lockb").exists() || dir.join("bun.lock").exists() {
+fn detect_lockfile_manager(dir: &Path, fs: &dyn FileSystem) -> Option<PackageManager> {
+    if fs.exists(&dir.join("bun.lockb")) || fs.exists(&dir.join("bun.lock")) {
         return Some(PackageManager::Bun);
     }
-    if dir.join("pnpm-lock.yaml").exists() {
+    if fs.exists(&dir.join("pnpm-lock.yaml")) {
         return Some(PackageManager::Pnpm);
     }
-    if dir.join("yarn.lock").exists() {
+    if fs.exists(&dir.join("yarn.lock")) {
         return Some(PackageManager::Yarn);
     }
-    if dir.join("package-lock.json").exists() {
+    if fs.exists(&dir.join("package-lock.json")) {
         return Some(PackageManager::Npm);
     }
 
     None
 }
 
-fn resolve_js_package_manager(package_json_path: &Path) -> PackageManager {
-    if let Some(pm) = read_package_json_manager(package_json_path) {
+fn resolve_js_package_manager(package_json_path: &Path, fs: &dyn FileSystem) -> PackageManager {
+    if let Some(pm) = read_package_json_manager(package_json_path, fs) {
         return pm;
     }
 
@@ -1087,16 +1884,37 @@ fn resolve_js_package_manager(package_json_path: &Path) -> PackageManager {
         return PackageManager::Npm;
     };
 
-    if let Some(pm) = detect_lockfile_manager(parent) {
+    if let Some(pm) = detect_lockfile_manager(parent, fs) {
         return pm;
     }
 
     PackageManager::Npm
 }
 
+/// `node <yarnPath>` for a package under a Yarn project that checks in its
+/// release, with the path relative to the package directory.
+fn checked_in_yarn(package_dir: &Path, root: &Path, fs: &dyn FileSystem) -> Option<String> {
+    let project_dir = package_dir
+        .ancestors()
+        .take_while(|dir| dir.starts_with(root))
+        .find(|dir| fs.exists(&dir.join("yarn.lock")) || fs.exists(&dir.join(".yarnrc.yml")))?;
+    let yarn_path = YarnSetup::detect(fs, project_dir).yarn_path?;
+
+    let depth = package_dir
+        .strip_prefix(project_dir)
+        .ok()?
+        .components()
+        .count();
+    let relative: PathBuf = std::iter::repeat_n(Path::new(".."), depth)
+        .collect::<PathBuf>()
+        .join(yarn_path);
+    Some(format!("node {}", relative.display()))
+}
+
 fn collect_checks(
     metadata: &ProjectMetadata,
     root: &Path,
+    fs: &dyn FileSystem,
     required_package_managers: &mut HashSet<PackageManager>,
     need_node: &mut bool,
     need_python: &mut bool,
@@ -1110,13 +1928,18 @@ fn collect_checks(
     for tr in &metadata.task_runners {
         let runner_path = relativize_path(root, &tr.path).unwrap_or_else(|| tr.path.clone());
         let runner_slug = slugify_path(&runner_path);
-
+        // Checks never install dependencies, so the lifecycle scripts in
+        // `tr.commands.setup` would run without them; they're left out.
         for (category, cmds) in [
             (CheckCategory::Test, &tr.commands.test),
             (CheckCategory::Build, &tr.commands.build),
+            (CheckCategory::Lint, &tr.commands.lint),
+            (CheckCategory::Lint, &tr.commands.typecheck),
+            (CheckCategory::Format, &tr.commands.format),
         ] {
-            for cmd in cmds {
-                let (cmd_info, pm_used) = resolve_task_command(cmd, root);
+            // A check can't supply arguments the command needs.
+            for cmd in cmds.iter().filter(|cmd| cmd.required_args.is_empty()) {
+                let (cmd_info, pm_used) = resolve_task_command(cmd, root, &runner_path, fs);
 
                 if let Some(pm) = pm_used {
                     required_package_managers.insert(pm);
@@ -1182,8 +2005,12 @@ fn infer_check_language(
         | TaskRunner::Rollup
         | TaskRunner::Turbo
         | TaskRunner::Nx => return Some(Language::JavaScript),
-        TaskRunner::Tox | TaskRunner::Nox | TaskRunner::Invoke => return Some(Language::Python),
-        TaskRunner::Make | TaskRunner::Just | TaskRunner::Task => {}
+        TaskRunner::Tox
+        | TaskRunner::Nox
+        | TaskRunner::Invoke
+        | TaskRunner::Hatch
+        | TaskRunner::Pixi => return Some(Language::Python),
+        TaskRunner::Make | TaskRunner::Just | TaskRunner::Task | TaskRunner::Custom(_) => {}
     }
 
     match required_exec {
@@ -1194,7 +2021,9 @@ fn infer_check_language(
         | "python3"
         | constants::PYTHON_TOOL_TOX
         | constants::PYTHON_TOOL_NOX
-        | constants::PYTHON_TOOL_INVOKE => Some(Language::Python),
+        | constants::PYTHON_TOOL_INVOKE
+        | "hatch"
+        | "pixi" => Some(Language::Python),
         "make" | "just" | "task" => primary_language,
         _ => primary_language,
     }
@@ -1210,6 +2039,7 @@ fn language_display_name(lang: Option<Language>) -> &'static str {
         Some(Language::Python) => "Python",
         Some(Language::JavaScript) => "Node.js",
         Some(Language::Rust) => "Rust",
+        Some(Language::Custom(name)) => name,
         None => "Generic",
     }
 }
@@ -1218,6 +2048,8 @@ fn category_name(category: CheckCategory) -> &'static str {
     match category {
         CheckCategory::Test => "test",
         CheckCategory::Build => "build",
+        CheckCategory::Lint => "lint",
+        CheckCategory::Format => "format",
     }
 }
 
@@ -1225,17 +2057,31 @@ fn category_file_name(category: CheckCategory) -> &'static str {
     match category {
         CheckCategory::Test => "test-checks.nix",
         CheckCategory::Build => "build-checks.nix",
+        CheckCategory::Lint => "lint-checks.nix",
+        CheckCategory::Format => "format-checks.nix",
     }
 }
 
-fn resolve_task_command(cmd: &TaskCommand, root: &Path) -> (CommandInfo, Option<PackageManager>) {
+/// `runner_path` is the task runner file relative to `root`; direct commands
+/// run from its directory.
+fn resolve_task_command(
+    cmd: &TaskCommand,
+    root: &Path,
+    runner_path: &Path,
+    fs: &dyn FileSystem,
+) -> (CommandInfo, Option<PackageManager>) {
     match &cmd.executable {
         CommandExecutable::Direct { command } => {
             let required_exec = command_first_word(command).unwrap_or("").to_string();
+            let workdir = runner_path
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| ".".to_string());
             let info = CommandInfo {
                 required_exec,
                 command: command.clone(),
-                workdir: ".".to_string(),
+                workdir,
                 display: command.clone(),
             };
             (info, None)
@@ -1244,16 +2090,37 @@ fn resolve_task_command(cmd: &TaskCommand, root: &Path) -> (CommandInfo, Option<
             script_name,
             script_body,
             package_json_path,
+            workspace,
         } => {
-            let pm = resolve_js_package_manager(package_json_path);
-            let pm_cmd = pm.command_name().unwrap_or("");
-
-            let run_command = pm
-                .run_script(script_name)
-                .unwrap_or_else(|| format!("npm run {script_name}"));
+            // Workspace members run from the root, with its package manager
+            // and lockfile.
+            let (pm, run_dir, mut run_command) = match workspace {
+                Some(member) => {
+                    let pm = resolve_js_package_manager(&member.root.join("package.json"), fs);
+                    let run_command = pm
+                        .run_workspace_script(&member.name, script_name)
+                        .unwrap_or_else(|| format!("npm -w {} run {script_name}", member.name));
+                    (pm, Some(member.root.as_path()), run_command)
+                }
+                None => {
+                    let pm = resolve_js_package_manager(package_json_path, fs);
+                    let run_command = pm
+                        .run_script(script_name)
+                        .unwrap_or_else(|| format!("npm run {script_name}"));
+                    (pm, package_json_path.parent(), run_command)
+                }
+            };
+            let mut pm_cmd = pm.command_name().unwrap_or("");
+
+            if pm == PackageManager::Yarn
+                && let Some(yarn) = run_dir.and_then(|dir| checked_in_yarn(dir, root, fs))
+                && let Some(args) = run_command.strip_prefix("yarn ")
+            {
+                run_command = format!("{yarn} {args}");
+                pm_cmd = "node";
+            }
 
-            let workdir = package_json_path
-                .parent()
+            let workdir = run_dir
                 .and_then(|p| relativize_path(root, p))
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_else(|| ".".to_string());
@@ -1307,6 +2174,7 @@ fn build_check_spec(
         required_exec: cmd_info.required_exec,
         command: cmd_info.command,
         workdir: cmd_info.workdir,
+        toolchain_attr: None,
     }
 }
 
@@ -1315,20 +2183,34 @@ fn generate_check_files(
 ) -> Vec<CheckFile> {
     let mut out = Vec::new();
 
+    let mut custom_languages: Vec<Option<Language>> = checks_by_lang
+        .keys()
+        .filter(|language| matches!(language, Some(Language::Custom(_))))
+        .cloned()
+        .collect();
+    custom_languages.sort_by_key(|language| language_dir_name(language.as_ref()));
+
     let language_order = [
         None,
         Some(Language::Go),
         Some(Language::Python),
         Some(Language::JavaScript),
         Some(Language::Rust),
-    ];
+    ]
+    .into_iter()
+    .chain(custom_languages);
 
     for language in language_order {
         let Some(by_cat) = checks_by_lang.get(&language) else {
             continue;
         };
 
-        for category in [CheckCategory::Test, CheckCategory::Build] {
+        for category in [
+            CheckCategory::Test,
+            CheckCategory::Build,
+            CheckCategory::Lint,
+            CheckCategory::Format,
+        ] {
             let Some(checks) = by_cat.get(&category) else {
                 continue;
             };
@@ -1377,7 +2259,8 @@ fn generate_check_file_content(description: &str, checks: &[CheckSpec]) -> Strin
             check.required_exec.clone(),
             check.command.clone(),
             check.workdir.clone(),
-        );
+        )
+        .with_toolchain_attr(check.toolchain_attr.clone());
         out.push_str(&builder.build());
     }
 
@@ -1401,8 +2284,11 @@ fn task_runner_name(task_runner: TaskRunner) -> &'static str {
         TaskRunner::Tox => "Tox",
         TaskRunner::Nox => "Nox",
         TaskRunner::Invoke => "Invoke",
+        TaskRunner::Hatch => "Hatch",
+        TaskRunner::Pixi => "Pixi",
         TaskRunner::Cargo => "Cargo",
         TaskRunner::GoTask => "GoTask",
+        TaskRunner::Custom(name) => name,
     }
 }
 
@@ -1447,10 +2333,34 @@ fn command_first_word(command: &str) -> Option<&str> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::detection::DetectionEngine;
+    use crate::detection::{Confidence, DetectionEngine, DetectionScope, TarFileSystem};
+    use std::fs;
     use std::io::Write;
     use tempfile::TempDir;
 
+    fn tar_file_system(entries: &[(&str, &str)]) -> TarFileSystem {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        TarFileSystem::from_bytes(&builder.into_inner().unwrap()).unwrap()
+    }
+
+    /// Detects and generates the flake of an archive, as `--from-tar` does.
+    fn generate_from_tar(entries: &[(&str, &str)]) -> GeneratedFlake {
+        let tar_fs = tar_file_system(entries);
+        let root = Path::new(".");
+        let metadata =
+            DetectionEngine::default().detect_with_fs(&tar_fs, root, DetectionScope::All);
+        generate_dev_flake_with_registry(&metadata, root, &GeneratorRegistry::default(), &tar_fs)
+    }
+
     fn create_temp_file(dir: &TempDir, name: &str, content: &str) -> PathBuf {
         let path = dir.path().join(name);
         if let Some(parent) = path.parent() {
@@ -1478,6 +2388,92 @@ mod tests {
             .map(|p| p.content.as_str())
     }
 
+    #[test]
+    fn generates_custom_language_packages_from_registry() {
+        use crate::detection::{
+            LanguageDetection, LanguageDetectionSignal, LanguageDetectionSource,
+            TaskRunnerCommands, TaskRunnerDetection, TaskRunnerSource,
+        };
+        use crate::generation::LanguageGenerator;
+
+        struct ZigGenerator;
+
+        impl LanguageGenerator for ZigGenerator {
+            fn language(&self) -> Language {
+                Language::Custom("zig")
+            }
+
+            fn generate_packages_nix(&self, _metadata: &ProjectMetadata) -> String {
+                "{ pkgs, lib }:\n{ packages = [ pkgs.zig ]; notices = []; }\n".to_string()
+            }
+        }
+
+        let dir = TempDir::new().unwrap();
+        let metadata = ProjectMetadata {
+            languages: vec![LanguageDetection::new(
+                Language::Custom("zig"),
+                vec![LanguageDetectionSignal::Strong {
+                    path: dir.path().join("build.zig"),
+                    source: LanguageDetectionSource::Custom {
+                        language: "zig",
+                        name: "BuildZig",
+                    },
+                }],
+            )],
+            versions: vec![],
+            package_managers: vec![],
+            version_conflicts: vec![],
+            task_runners: vec![TaskRunnerDetection {
+                task_runner: TaskRunner::Custom("ZigBuild"),
+                source: TaskRunnerSource::Custom("BuildZig"),
+                path: dir.path().join("build.zig"),
+                commands: TaskRunnerCommands {
+                    test: vec![TaskCommand {
+                        name: "test".to_string(),
+                        executable: CommandExecutable::Direct {
+                            command: "zig build test".to_string(),
+                        },
+                        description: None,
+                        depends_on: vec![],
+                        required_args: vec![],
+                        workdir: None,
+                        confidence: Confidence::High,
+                    }],
+                    ..Default::default()
+                },
+                task_graph: None,
+            }],
+        };
+
+        let mut registry = GeneratorRegistry::default();
+        registry.register_language_generator(ZigGenerator);
+        let flake =
+            generate_dev_flake_with_registry(&metadata, dir.path(), &registry, &LocalFileSystem);
+
+        assert!(
+            language_packages_content(&flake, Language::Custom("zig"))
+                .unwrap()
+                .contains("pkgs.zig")
+        );
+        assert!(
+            flake
+                .main_flake
+                .contains("zigPackages = import ./zig/packages.nix { inherit pkgs lib; };")
+        );
+        assert!(flake.main_flake.contains("++ zigPackages.packages"));
+        assert!(flake.main_flake.contains("++ zigPackages.notices"));
+        assert!(
+            flake
+                .check_files
+                .iter()
+                .any(|f| f.relative_path == Path::new("zig/test-checks.nix"))
+        );
+
+        let flake = generate_dev_flake(&metadata, dir.path());
+        assert!(flake.language_packages.is_empty());
+        assert!(!flake.main_flake.contains("zigPackages"));
+    }
+
     #[test]
     fn generates_rust_overlay_when_rust_detected() {
         let dir = TempDir::new().unwrap();
@@ -1488,7 +2484,7 @@ mod tests {
         );
         create_temp_file(&dir, "src/main.rs", "fn main() {}\n");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1502,6 +2498,132 @@ mod tests {
         assert!(rust_pkgs.contains("rust-src"));
     }
 
+    #[test]
+    fn follows_dated_nightly_toolchain_file() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nrust-version = \"1.70.0\"\n",
+        );
+        create_temp_file(
+            &dir,
+            "rust-toolchain.toml",
+            "[toolchain]\nchannel = \"nightly-2024-05-01\"\ncomponents = [\"clippy\", \"miri\", \"rust-src\"]\ntargets = [\"wasm32-unknown-unknown\"]\nprofile = \"minimal\"\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let rust_pkgs = language_packages_content(&flake, Language::Rust).unwrap();
+        assert!(
+            rust_pkgs.contains("rustToolchainBase = pkgs.rust-bin.nightly.\"2024-05-01\".minimal;")
+        );
+        assert!(
+            rust_pkgs
+                .contains("extensions = [ \"rust-src\" \"rust-analyzer\" \"clippy\" \"miri\" ];")
+        );
+        assert!(rust_pkgs.contains("targets = [ \"wasm32-unknown-unknown\" ];"));
+        assert!(!rust_pkgs.contains("wantRustVersion"));
+        assert!(rust_pkgs.contains(
+            "Rust: requested nightly-2024-05-01 (from RustToolchainToml) -> using rust-bin.nightly.\\\"2024-05-01\\\".minimal"
+        ));
+    }
+
+    #[test]
+    fn reads_toolchain_file_from_detection_source() {
+        let flake = generate_from_tar(&[
+            (
+                "repo/Cargo.toml",
+                "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+            ),
+            (
+                "repo/rust-toolchain.toml",
+                "[toolchain]\nchannel = \"1.78.0\"\ncomponents = [\"clippy\"]\ntargets = [\"wasm32-unknown-unknown\"]\nprofile = \"minimal\"\n",
+            ),
+        ]);
+
+        let rust_pkgs = language_packages_content(&flake, Language::Rust).unwrap();
+        assert!(rust_pkgs.contains("extensions = [ \"rust-src\" \"rust-analyzer\" \"clippy\" ];"));
+        assert!(rust_pkgs.contains("targets = [ \"wasm32-unknown-unknown\" ];"));
+        assert!(rust_pkgs.contains(".minimal"));
+    }
+
+    #[test]
+    fn resolves_two_component_channel_to_newest_patch() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        );
+        create_temp_file(
+            &dir,
+            "rust-toolchain.toml",
+            "[toolchain]\nchannel = \"1.78\"\n",
+        );
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let rust_pkgs = language_packages_content(&flake, Language::Rust).unwrap();
+        assert!(rust_pkgs.contains("wantRustVersion = \"1.78\";"));
+        assert!(rust_pkgs.contains("lib.hasPrefix \"${wantRustVersion}.\" v"));
+        assert!(rust_pkgs.contains(
+            "else lib.warn \"rust-toolchain pins Rust ${wantRustVersion}, which rust-overlay does not provide; using the latest stable\" pkgs.rust-bin.stable.latest.default;"
+        ));
+        assert!(
+            rust_pkgs
+                .contains("try newest rust-bin.stable.1.78.x (fallback latest, with a warning)")
+        );
+    }
+
+    #[test]
+    fn rust_toolchain_channel_selection() {
+        let cases = [
+            (
+                "stable",
+                "rustToolchainBase = pkgs.rust-bin.stable.latest.default;",
+            ),
+            (
+                "beta",
+                "rustToolchainBase = pkgs.rust-bin.beta.latest.default;",
+            ),
+            (
+                "nightly",
+                "rustToolchainBase = pkgs.rust-bin.selectLatestNightlyWith (toolchain: toolchain.default);",
+            ),
+            ("1.78.0", "wantRustVersion = \"1.78.0\";"),
+            (
+                "1.78",
+                "then pkgs.rust-bin.stable.${lib.last (lib.sort lib.versionOlder rustReleasePatches)}.default",
+            ),
+            (
+                "1.78.0-x86_64-unknown-linux-gnu",
+                "rustToolchainBase = pkgs.rust-bin.fromRustupToolchainFile (../.. + \"/rust-toolchain\");",
+            ),
+        ];
+
+        for (channel, expected) in cases {
+            let dir = TempDir::new().unwrap();
+            create_temp_file(
+                &dir,
+                "Cargo.toml",
+                "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+            );
+            create_temp_file(&dir, "rust-toolchain", &format!("{channel}\n"));
+
+            let engine = DetectionEngine::default();
+            let metadata = engine.detect(dir.path());
+            let flake = generate_dev_flake(&metadata, dir.path());
+
+            let rust_pkgs = language_packages_content(&flake, Language::Rust).unwrap();
+            assert!(rust_pkgs.contains(expected), "{channel}: {rust_pkgs}");
+            assert!(!rust_pkgs.contains("targets ="));
+        }
+    }
+
     #[test]
     fn resolves_package_manager_from_package_manager_field() {
         let dir = TempDir::new().unwrap();
@@ -1515,7 +2637,7 @@ mod tests {
  }"#,
         );
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1537,7 +2659,7 @@ mod tests {
         );
         create_temp_file(&dir, "pnpm-lock.yaml", "");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1557,7 +2679,7 @@ mod tests {
  }"#,
         );
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1568,15 +2690,16 @@ mod tests {
     #[test]
     fn includes_versioned_go_when_available() {
         let dir = TempDir::new().unwrap();
-        create_temp_file(&dir, "go.mod", "module example.com\n\ngo 1.21\n");
+        create_temp_file(&dir, "go.mod", "module example.com\n\ngo 1.25\n");
         create_temp_file(&dir, "main.go", "package main\nfunc main(){}\n");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
+        // The `go` directive is a minimum, so any newer Go satisfies it.
         let go_pkgs = language_packages_content(&flake, Language::Go).unwrap();
-        assert!(go_pkgs.contains("go_1_21"));
+        assert!(go_pkgs.contains("wantGoAttr = \"go_1_26\""));
     }
 
     #[test]
@@ -1602,7 +2725,7 @@ mod tests {
         );
         create_temp_file(&dir, "src/main.rs", "fn main() {}\n");
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1647,7 +2770,7 @@ mod tests {
             r#"{"scripts": {"test:unit": "echo one", "test_unit": "echo two"}}"#,
         );
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1666,7 +2789,7 @@ mod tests {
             r#"{"scripts": {"test": "vitest"}}"#,
         );
 
-        let engine = DetectionEngine;
+        let engine = DetectionEngine::default();
         let metadata = engine.detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
@@ -1675,84 +2798,692 @@ mod tests {
     }
 
     #[test]
-    fn test_version_fallback_when_unavailable() {
+    fn test_workspace_members_run_from_root() {
         let dir = TempDir::new().unwrap();
         create_temp_file(
             &dir,
-            "pyproject.toml",
-            "[project]\nrequires-python = \">=3.99\"\n",
+            "package.json",
+            r#"{"name": "root", "private": true, "scripts": {"test": "pnpm -r test"}}"#,
+        );
+        create_temp_file(&dir, "pnpm-workspace.yaml", "packages:\n  - packages/*\n");
+        create_temp_file(&dir, "pnpm-lock.yaml", "lockfileVersion: '9.0'\n");
+        create_temp_file(
+            &dir,
+            "packages/web/package.json",
+            r#"{"name": "@acme/web", "scripts": {"test": "vitest"}}"#,
         );
 
-        let engine = DetectionEngine;
-        let metadata = engine.detect(dir.path());
+        let metadata = DetectionEngine::default().detect(dir.path());
         let flake = generate_dev_flake(&metadata, dir.path());
 
-        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
-        assert!(python_pkgs.contains("wantPythonAttr = \"python399\""));
-        assert!(python_pkgs.contains("else \"python3\""));
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("pnpm --filter @acme/web run test"));
+        assert!(!checks.contains("workdir = \"packages/web\";"));
     }
 
     #[test]
-    fn test_nix_escape_quotes() {
-        assert_eq!(
-            nix_builder::escape_nix_string(r#"hello "world""#),
-            r#"hello \"world\""#
+    fn test_npm_workspace_members_use_root_lockfile() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "root", "workspaces": ["services/*"]}"#,
+        );
+        create_temp_file(&dir, "package-lock.json", "{}");
+        create_temp_file(
+            &dir,
+            "services/api/package.json",
+            r#"{"name": "api", "scripts": {"build": "tsc"}}"#,
         );
-    }
 
-    #[test]
-    fn test_nix_escape_dollar_brace() {
-        assert_eq!(nix_builder::escape_nix_string("${foo}"), r"\${foo}");
-        assert_eq!(nix_builder::escape_nix_string("$foo"), "$foo");
-    }
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
 
-    #[test]
-    fn test_nix_escape_backslash() {
-        assert_eq!(nix_builder::escape_nix_string(r"foo\bar"), r"foo\\bar");
+        assert!(all_check_contents(&flake).contains("npm -w api run build"));
     }
 
     #[test]
-    fn test_nix_escape_newlines_tabs() {
-        assert_eq!(
-            nix_builder::escape_nix_string("foo\nbar\ttab"),
-            "foo\\nbar\\ttab"
+    fn test_nested_cargo_workspace_runs_in_its_directory() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "engine/Cargo.toml",
+            "[workspace]\nmembers = [\"core\"]\n",
+        );
+        create_temp_file(
+            &dir,
+            "engine/core/Cargo.toml",
+            "[package]\nname = \"core\"\n",
         );
-    }
 
-    #[test]
-    fn test_nix_escape_combined() {
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let checks = all_check_contents(&flake);
         assert_eq!(
-            nix_builder::escape_nix_string(r#"echo "${VAR}" > file\nend"#),
-            r#"echo \"\${VAR}\" > file\\nend"#
+            checks
+                .matches("cmd = \"cargo test -p core --all-targets\";")
+                .count(),
+            1
         );
+        assert!(checks.contains("workdir = \"engine\";"));
+        assert!(!checks.contains("workdir = \"engine/core\";"));
     }
 
     #[test]
-    fn test_best_version_info_prefers_highest_version() {
+    fn test_lint_and_format_check_files() {
         let dir = TempDir::new().unwrap();
-        create_temp_file(&dir, "go.mod", "module example.com\n\ngo 1.21\n");
-        create_temp_file(&dir, ".go-version", "1.19\n");
-        create_temp_file(&dir, "main.go", "package main\nfunc main(){}\n");
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"scripts": {"lint": "eslint .", "typecheck": "tsc --noEmit", "dev": "vite"}}"#,
+        );
+        create_temp_file(&dir, "Cargo.toml", "[package]\nname = \"demo\"\n");
 
-        let engine = DetectionEngine;
-        let metadata = engine.detect(dir.path());
-        let version = best_version_info(&metadata, Language::Go, constants::GO_VERSION_SOURCES);
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+        let check_file = |path: &str| {
+            flake
+                .check_files
+                .iter()
+                .find(|f| f.relative_path == Path::new(path))
+                .map(|f| f.content.as_str())
+        };
 
-        let parsed = version.unwrap().parsed.as_ref().unwrap();
-        assert_eq!(parsed.major, Some(1));
-        assert_eq!(parsed.minor, Some(21));
+        let node_lint = check_file("nodejs/lint-checks.nix").unwrap();
+        assert!(node_lint.contains("eslint ."));
+        assert!(node_lint.contains("tsc --noEmit"));
+        assert!(!all_check_contents(&flake).contains("vite"));
+
+        assert!(
+            check_file("rust/lint-checks.nix")
+                .unwrap()
+                .contains("cargo clippy --all-targets")
+        );
+        assert!(
+            check_file("rust/format-checks.nix")
+                .unwrap()
+                .contains("cargo fmt --check")
+        );
     }
 
     #[test]
-    fn test_best_version_info_filters_by_allowed_sources() {
+    fn test_justfile_recipes_needing_arguments_are_not_checks() {
         let dir = TempDir::new().unwrap();
-        create_temp_file(&dir, ".node-version", "18.0.0\n");
-        create_temp_file(&dir, ".bun-version", "999.0.0\n");
+        create_temp_file(
+            &dir,
+            "justfile",
+            "test:\n    cargo test\n\ntest-one name:\n    cargo test {{name}}\n",
+        );
 
-        let engine = DetectionEngine;
-        let metadata = engine.detect(dir.path());
-        let version = best_version_info(
-            &metadata,
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("cmd = \"just test\";"));
+        assert!(!checks.contains("just test-one"));
+    }
+
+    #[test]
+    fn test_package_manager_read_from_detection_source() {
+        let flake = generate_from_tar(&[
+            (
+                "repo/package.json",
+                r#"{"name": "app", "scripts": {"test": "vitest run"}}"#,
+            ),
+            ("repo/pnpm-lock.yaml", "lockfileVersion: '9.0'\n"),
+        ]);
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("cmd = \"pnpm run test\";"));
+        assert!(!checks.contains("cmd = \"npm run test\";"));
+    }
+
+    #[test]
+    fn test_npm_install_lifecycle_scripts_are_not_checks() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"scripts": {"prepare": "tsc -p .", "pretest": "eslint .", "test": "jest"}}"#,
+        );
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("cmd = \"npm run test\";"));
+        assert!(!checks.contains("npm run prepare"));
+        assert!(!checks.contains("cmd = \"npm run prepare\";"));
+        assert!(!checks.contains("cmd = \"npm run pretest\";"));
+    }
+
+    #[test]
+    fn test_turbo_workspace_checks_per_package() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "root", "workspaces": ["apps/*"]}"#,
+        );
+        create_temp_file(
+            &dir,
+            "apps/web/package.json",
+            r#"{"name": "web", "scripts": {"test": "jest", "dev": "next dev"}}"#,
+        );
+        create_temp_file(
+            &dir,
+            "turbo.json",
+            r#"{"tasks": {"test": {}, "dev": {"persistent": true}}}"#,
+        );
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("cmd = \"turbo run test --filter=web\";"));
+        assert!(!checks.contains("turbo run dev"));
+    }
+
+    #[test]
+    fn test_nx_workspace_checks_per_project() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "nx.json", r#"{"plugins": ["@nx/jest/plugin"]}"#);
+        create_temp_file(
+            &dir,
+            "libs/ui/project.json",
+            r#"{"name": "ui", "targets": {"serve": {"executor": "@nx/vite:dev-server"}}}"#,
+        );
+        create_temp_file(&dir, "libs/ui/jest.config.ts", "export default {};");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("cmd = \"nx run ui:test\";"));
+        assert!(!checks.contains("nx run ui:serve"));
+    }
+
+    #[test]
+    fn test_version_fallback_when_unavailable() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "pyproject.toml",
+            "[project]\nrequires-python = \">=3.99\"\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python3\""));
+        assert!(python_pkgs.contains("no pythonXY in nixos-unstable satisfies the request"));
+    }
+
+    #[test]
+    fn test_python_attr_is_highest_inside_requires_python_range() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "pyproject.toml",
+            "[project]\nrequires-python = \">=3.9,<3.12\"\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python311\""));
+    }
+
+    #[test]
+    fn test_python_attr_intersects_all_sources() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "pyproject.toml",
+            "[project]\nrequires-python = \"~=3.9\"\n",
+        );
+        create_temp_file(&dir, ".python-version", "3.10\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python310\""));
+    }
+
+    #[test]
+    fn test_single_pin_outranks_matrix_for_primary_python() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, ".python-versions", "3.11\n3.12\n");
+        create_temp_file(&dir, "runtime.txt", "python-3.11.4\n");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        assert!(metadata.version_conflicts.is_empty());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python311\""));
+    }
+
+    #[test]
+    fn test_node_attr_respects_upper_bound() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "package.json", r#"{"engines": {"node": "<22"}}"#);
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("wantNodeAttr = \"nodejs_20\""));
+    }
+
+    #[test]
+    fn test_conflicting_sources_fall_back_to_highest_version() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "go.mod", "module test\n\ngo 1.25\n");
+        create_temp_file(&dir, ".go-version", "1.24.3\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let go_pkgs = language_packages_content(&flake, Language::Go).unwrap();
+        assert!(go_pkgs.contains("wantGoAttr = \"go_1_25\""));
+        assert!(go_pkgs.contains("do not overlap"));
+    }
+
+    #[test]
+    fn test_version_conflicts_become_notices() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, ".nvmrc", "18\n");
+        create_temp_file(&dir, ".node-version", "22\n");
+        create_temp_file(&dir, "package.json", r#"{"engines": {"node": ">=20"}}"#);
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("wantNodeAttr = \"nodejs_22\""));
+        assert!(node_pkgs.contains("\"version conflict: node: "));
+        assert!(node_pkgs.contains("-> using 22 (from NodeVersionFile)"));
+    }
+
+    #[test]
+    fn test_mise_toml_pins_toolchains() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "mise.toml",
+            "[tools]\nnode = \"20.11\"\npython = \"3.11\"\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("wantNodeAttr = \"nodejs_20\""));
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python311\""));
+    }
+
+    #[test]
+    fn test_go_toolchain_directive_drives_attr() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "go.mod",
+            "module example.com\n\ngo 1.24\n\ntoolchain go1.25.2\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        assert!(metadata.version_conflicts.is_empty());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let go_pkgs = language_packages_content(&flake, Language::Go).unwrap();
+        assert!(go_pkgs.contains("wantGoAttr = \"go_1_25\""));
+    }
+
+    #[test]
+    fn test_go_workspace_version_and_modules() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "go.work", "go 1.25.0\n\nuse (\n\t./api\n\t./cli\n)\n");
+        create_temp_file(&dir, "api/go.mod", "module example.com/api\n\ngo 1.24\n");
+        create_temp_file(&dir, "cli/go.mod", "module example.com/cli\n\ngo 1.24\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let go_pkgs = language_packages_content(&flake, Language::Go).unwrap();
+        assert!(go_pkgs.contains("wantGoAttr = \"go_1_26\""));
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("go test ./api/..."));
+        assert!(checks.contains("go test ./cli/..."));
+        assert!(checks.contains("go build ./cli/..."));
+        assert!(!checks.contains("go test ./...\""));
+    }
+
+    #[test]
+    fn test_ci_matrix_is_used_when_nothing_else_pins_node() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "package.json", r#"{"name": "demo"}"#);
+        create_temp_file(
+            &dir,
+            ".github/workflows/test.yml",
+            "jobs:\n  test:\n    strategy:\n      matrix:\n        node-version: [20.x, 22.x]\n    steps:\n      - uses: actions/setup-node@v4\n        with:\n          node-version: ${{ matrix.node-version }}\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("wantNodeAttr = \"nodejs_22\""));
+    }
+
+    #[test]
+    fn test_tox_envlist_adds_interpreters() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, ".python-version", "3.12\n");
+        create_temp_file(
+            &dir,
+            "tox.ini",
+            "[tox]\nenvlist = py39, py{311,312}\n\n[testenv]\ncommands = pytest\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        assert!(metadata.version_conflicts.is_empty());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python312\""));
+        assert!(python_pkgs.contains("pythonMatrixAttrs = [ \"python311\" ];"));
+        assert!(python_pkgs.contains("++ pythonMatrix"));
+        assert!(python_pkgs.contains(
+            "Python: 3.9 (from ToxEnvlist) skipped (python39 is not packaged in nixos-unstable)"
+        ));
+
+        // tox runs each env with its own interpreter already.
+        let checks = all_check_contents(&flake);
+        assert!(!checks.contains("pkgs.\"python311\""));
+    }
+
+    #[test]
+    fn test_nox_sessions_parameterize_python_checks() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, ".python-version", "3.13\n");
+        create_temp_file(
+            &dir,
+            "noxfile.py",
+            "import nox\n\nPYTHONS = [\"3.11\", \"3.12\", \"3.13\"]\n\n@nox.session(python=PYTHONS)\ndef tests(session):\n    session.run(\"pytest\")\n",
+        );
+        create_temp_file(&dir, "Makefile", "test:\n\tpython -m pytest\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("pythonMatrixAttrs = [ \"python311\" \"python312\" ];"));
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains(
+            "nativeBuildInputs = lib.optional (builtins.hasAttr \"python311\" pkgs) pkgs.\"python311\" ++ devPackages;"
+        ));
+        assert!(checks.contains("(python312)"));
+    }
+
+    #[test]
+    fn test_ci_node_matrix_adds_versioned_node_commands() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "demo", "scripts": {"test": "node --test"}}"#,
+        );
+        create_temp_file(&dir, ".nvmrc", "22\n");
+        create_temp_file(
+            &dir,
+            ".github/workflows/ci.yml",
+            "jobs:\n  test:\n    strategy:\n      matrix:\n        node: [20, 22, 24]\n    steps:\n      - uses: actions/setup-node@v4\n        with:\n          node-version: ${{ matrix.node }}\n",
+        );
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("wantNodeAttr = \"nodejs_22\""));
+        assert!(node_pkgs.contains("nodeMatrixAttrs = [ \"nodejs_20\" \"nodejs_24\" ];"));
+        assert!(node_pkgs.contains("++ nodeMatrix"));
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("pkgs.\"nodejs_20\" ++ devPackages"));
+        assert!(checks.contains("pkgs.\"nodejs_24\" ++ devPackages"));
+    }
+
+    #[test]
+    fn test_corepack_pin_falls_back_to_corepack() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "demo", "packageManager": "pnpm@9.1.0+sha512.abc", "scripts": {"test": "vitest"}}"#,
+        );
+        create_temp_file(&dir, "pnpm-lock.yaml", "lockfileVersion: '9.0'\n");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("pnpmPinned = \"9.1.0\";"));
+        assert!(node_pkgs.contains("builtins.hasAttr \"pnpm_9\" pkgs"));
+        assert!(node_pkgs.contains("corepack = if builtins.hasAttr \"corepack\" pkgs"));
+        assert!(node_pkgs.contains("lib.optional (corepack != null && !pnpmExact) corepack"));
+        assert!(node_pkgs.contains("notices = notices ++ packageManagerNotices;"));
+    }
+
+    #[test]
+    fn test_volta_pins_node_and_yarn() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "demo", "volta": {"node": "20.11.1", "yarn": "4.1.0", "npm": "10.2.0"}}"#,
+        );
+        create_temp_file(&dir, "yarn.lock", "");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("wantNodeAttr = \"nodejs_20\""));
+        assert!(node_pkgs.contains("builtins.hasAttr \"yarn-berry_4\" pkgs"));
+        assert!(node_pkgs.contains("yarn = yarnNix;"));
+        assert!(!node_pkgs.contains("corepack"));
+        assert!(node_pkgs.contains("the npm bundled with Node"));
+    }
+
+    #[test]
+    fn test_yarn_berry_runs_checked_in_release() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "demo", "scripts": {"test": "jest"}}"#,
+        );
+        create_temp_file(&dir, "yarn.lock", "__metadata:\n  version: 8\n");
+        create_temp_file(
+            &dir,
+            ".yarnrc.yml",
+            "yarnPath: .yarn/releases/yarn-4.1.0.cjs\n",
+        );
+        create_temp_file(&dir, ".yarn/releases/yarn-4.1.0.cjs", "");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("pkgs.yarn-berry"));
+        assert!(!node_pkgs.contains("pkgs.yarn else"));
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("node .yarn/releases/yarn-4.1.0.cjs run test"));
+    }
+
+    #[test]
+    fn test_yarn_release_read_from_detection_source() {
+        let flake = generate_from_tar(&[
+            (
+                "repo/package.json",
+                r#"{"name": "demo", "scripts": {"test": "jest"}}"#,
+            ),
+            ("repo/yarn.lock", "__metadata:\n  version: 8\n"),
+            (
+                "repo/.yarnrc.yml",
+                "yarnPath: .yarn/releases/yarn-4.1.0.cjs\n",
+            ),
+            ("repo/.yarn/releases/yarn-4.1.0.cjs", ""),
+        ]);
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("pkgs.yarn-berry"));
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("node .yarn/releases/yarn-4.1.0.cjs run test"));
+    }
+
+    #[test]
+    fn test_yarn_classic_keeps_yarn_1() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "package.json",
+            r#"{"name": "demo", "scripts": {"test": "jest"}}"#,
+        );
+        create_temp_file(&dir, "yarn.lock", "# yarn lockfile v1\n");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let node_pkgs = language_packages_content(&flake, Language::JavaScript).unwrap();
+        assert!(node_pkgs.contains("then pkgs.yarn else null"));
+        assert!(!node_pkgs.contains("yarn-berry"));
+        assert!(all_check_contents(&flake).contains("yarn run test"));
+    }
+
+    #[test]
+    fn test_hatch_and_pixi_tasks_become_checks() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(
+            &dir,
+            "pyproject.toml",
+            "[project]\nname = \"demo\"\n\n[tool.hatch.envs.default.scripts]\ntest = \"pytest\"\n",
+        );
+        create_temp_file(&dir, "tools/pixi.toml", "[tasks]\ntest = \"pytest\"\n");
+        create_temp_file(&dir, "requirements.in", "pytest\n");
+
+        let metadata = DetectionEngine::default().detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("++ lib.optional (hatch != null) hatch"));
+        assert!(python_pkgs.contains("++ lib.optional (pixi != null) pixi"));
+        assert!(python_pkgs.contains("pythonPackages.pip-tools"));
+
+        let checks = all_check_contents(&flake);
+        assert!(checks.contains("hatch run test"));
+        assert!(checks.contains("pixi run test"));
+    }
+
+    #[test]
+    fn test_runtime_txt_pins_python() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "requirements.txt", "flask\n");
+        create_temp_file(&dir, "runtime.txt", "python-3.11.4\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let flake = generate_dev_flake(&metadata, dir.path());
+
+        let python_pkgs = language_packages_content(&flake, Language::Python).unwrap();
+        assert!(python_pkgs.contains("wantPythonAttr = \"python311\""));
+        assert!(!python_pkgs.contains("pythonMatrix"));
+    }
+
+    #[test]
+    fn test_nix_escape_quotes() {
+        assert_eq!(
+            nix_builder::escape_nix_string(r#"hello "world""#),
+            r#"hello \"world\""#
+        );
+    }
+
+    #[test]
+    fn test_nix_escape_dollar_brace() {
+        assert_eq!(nix_builder::escape_nix_string("${foo}"), r"\${foo}");
+        assert_eq!(nix_builder::escape_nix_string("$foo"), "$foo");
+    }
+
+    #[test]
+    fn test_nix_escape_backslash() {
+        assert_eq!(nix_builder::escape_nix_string(r"foo\bar"), r"foo\\bar");
+    }
+
+    #[test]
+    fn test_nix_escape_newlines_tabs() {
+        assert_eq!(
+            nix_builder::escape_nix_string("foo\nbar\ttab"),
+            "foo\\nbar\\ttab"
+        );
+    }
+
+    #[test]
+    fn test_nix_escape_combined() {
+        assert_eq!(
+            nix_builder::escape_nix_string(r#"echo "${VAR}" > file\nend"#),
+            r#"echo \"\${VAR}\" > file\\nend"#
+        );
+    }
+
+    #[test]
+    fn test_best_version_info_prefers_highest_version() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, "go.mod", "module example.com\n\ngo 1.21\n");
+        create_temp_file(&dir, ".go-version", "1.19\n");
+        create_temp_file(&dir, "main.go", "package main\nfunc main(){}\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let version = best_version_info(&metadata, Language::Go, constants::GO_VERSION_SOURCES);
+
+        let parsed = version.unwrap().parsed.as_ref().unwrap();
+        assert_eq!(parsed.major, Some(1));
+        assert_eq!(parsed.minor, Some(21));
+    }
+
+    #[test]
+    fn test_best_version_info_filters_by_allowed_sources() {
+        let dir = TempDir::new().unwrap();
+        create_temp_file(&dir, ".node-version", "18.0.0\n");
+        create_temp_file(&dir, ".bun-version", "999.0.0\n");
+
+        let engine = DetectionEngine::default();
+        let metadata = engine.detect(dir.path());
+        let version = best_version_info(
+            &metadata,
             Language::JavaScript,
             constants::NODE_VERSION_SOURCES,
         )
@@ -1855,9 +3586,14 @@ mod tests {
                 command: "cargo test".to_string(),
             },
             description: None,
+            depends_on: vec![],
+            required_args: vec![],
+            workdir: None,
+            confidence: Confidence::High,
         };
 
-        let (info, pm) = resolve_task_command(&cmd, dir.path());
+        let (info, pm) =
+            resolve_task_command(&cmd, dir.path(), Path::new("Cargo.toml"), &LocalFileSystem);
         assert_eq!(info.required_exec, "cargo");
         assert_eq!(info.command, "cargo test");
         assert_eq!(info.workdir, ".");
@@ -1874,6 +3610,10 @@ mod tests {
                 command: "npm test".to_string(),
             },
             description: None,
+            depends_on: vec![],
+            required_args: vec![],
+            workdir: None,
+            confidence: Confidence::High,
         };
         let info = CommandInfo {
             required_exec: "npm".to_string(),
@@ -1910,7 +3650,10 @@ mod tests {
     fn test_resolve_js_pm_from_package_json_field() {
         let dir = TempDir::new().unwrap();
         let path = create_temp_file(&dir, "package.json", r#"{"packageManager": "pnpm@9.0.0"}"#);
-        assert_eq!(resolve_js_package_manager(&path), PackageManager::Pnpm);
+        assert_eq!(
+            resolve_js_package_manager(&path, &LocalFileSystem),
+            PackageManager::Pnpm
+        );
     }
 
     #[test]
@@ -1919,7 +3662,7 @@ mod tests {
         create_temp_file(&dir, "package.json", r#"{"name": "test"}"#);
         create_temp_file(&dir, "bun.lockb", "");
 
-        let pm = resolve_js_package_manager(&dir.path().join("package.json"));
+        let pm = resolve_js_package_manager(&dir.path().join("package.json"), &LocalFileSystem);
         assert_eq!(pm, PackageManager::Bun);
     }
 
@@ -1929,7 +3672,7 @@ mod tests {
         create_temp_file(&dir, "package.json", r#"{"name": "test"}"#);
         create_temp_file(&dir, "pnpm-lock.yaml", "");
 
-        let pm = resolve_js_package_manager(&dir.path().join("package.json"));
+        let pm = resolve_js_package_manager(&dir.path().join("package.json"), &LocalFileSystem);
         assert_eq!(pm, PackageManager::Pnpm);
     }
 
@@ -1938,7 +3681,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         create_temp_file(&dir, "package.json", r#"{"name": "test"}"#);
 
-        let pm = resolve_js_package_manager(&dir.path().join("package.json"));
+        let pm = resolve_js_package_manager(&dir.path().join("package.json"), &LocalFileSystem);
         assert_eq!(pm, PackageManager::Npm);
     }
 
@@ -1946,7 +3689,7 @@ mod tests {
     fn test_read_package_json_manager_missing_field() {
         let dir = TempDir::new().unwrap();
         let path = create_temp_file(&dir, "package.json", r#"{"name": "test"}"#);
-        assert_eq!(read_package_json_manager(&path), None);
+        assert_eq!(read_package_json_manager(&path, &LocalFileSystem), None);
     }
 
     #[test]
@@ -1956,7 +3699,7 @@ mod tests {
         create_temp_file(&dir, "package-lock.json", "");
 
         assert_eq!(
-            detect_lockfile_manager(dir.path()),
+            detect_lockfile_manager(dir.path(), &LocalFileSystem),
             Some(PackageManager::Bun)
         );
     }
@@ -1964,7 +3707,7 @@ mod tests {
     #[test]
     fn test_detect_lockfile_manager_no_lockfile() {
         let dir = TempDir::new().unwrap();
-        assert_eq!(detect_lockfile_manager(dir.path()), None);
+        assert_eq!(detect_lockfile_manager(dir.path(), &LocalFileSystem), None);
     }
 
     #[test]
@@ -2001,7 +3744,12 @@ mod tests {
             path: PathBuf::from("go.mod"),
         };
 
-        let notice = go_notice(Some(&version_info), Some("go_1_21")).unwrap();
+        let choice = AttrChoice {
+            attr: Some("go_1_21".to_string()),
+            reason: None,
+            default_attr: "go",
+        };
+        let notice = go_notice(Some(&version_info), &choice).unwrap();
         assert!(notice.contains("1.21.3"));
         assert!(notice.contains("go_1_21"));
         assert!(notice.contains("patch may differ"));
@@ -2023,7 +3771,12 @@ mod tests {
             path: PathBuf::from("pyproject.toml"),
         };
 
-        let notice = python_notice(Some(&version_info), Some("python310")).unwrap();
+        let choice = AttrChoice {
+            attr: Some("python310".to_string()),
+            reason: None,
+            default_attr: "python3",
+        };
+        let notice = python_notice(Some(&version_info), &choice).unwrap();
         assert!(notice.contains(">=3.10"));
         assert!(notice.contains("patch may differ"));
     }
@@ -2044,20 +3797,46 @@ mod tests {
             path: PathBuf::from("go.mod"),
         };
 
-        let notice = generate_version_notice(
-            "Go",
-            Some(&version_info),
-            Some("go_1_21"),
-            "go",
-            Some("note: test"),
-        )
-        .unwrap();
+        let choice = AttrChoice {
+            attr: Some("go_1_21".to_string()),
+            reason: Some("newest go_X_Y".to_string()),
+            default_attr: "go",
+        };
+        let notice =
+            generate_version_notice("Go", Some(&version_info), &choice, "go", Some("note: test"))
+                .unwrap();
 
         assert!(notice.contains("Go: requested 1.21.3"));
-        assert!(notice.contains("go_1_21"));
+        assert!(notice.contains("want go_1_21 (newest go_X_Y)"));
         assert!(notice.contains("note: test"));
     }
 
+    #[test]
+    fn test_version_notice_names_default_attr_with_reason() {
+        let version_info = VersionInfo {
+            raw: "3.99".to_string(),
+            source: VersionSource::PythonVersionFile,
+            parsed: Some(SemanticVersion {
+                major: Some(3),
+                minor: Some(99),
+                patch: None,
+                pre_release: None,
+                build: None,
+                constraint: VersionConstraint::Exact,
+            }),
+            path: PathBuf::from(".python-version"),
+        };
+
+        let choice = AttrChoice {
+            attr: None,
+            reason: Some("python399 is not packaged".to_string()),
+            default_attr: "python3",
+        };
+        assert_eq!(choice.want(), "python3");
+        let notice = python_notice(Some(&version_info), &choice).unwrap();
+        assert!(notice.contains("want python3 (python399 is not packaged)"));
+    }
+
     #[test]
     fn test_generate_flake_inputs_without_rust_overlay() {
         let result = generate_flake_inputs(false);
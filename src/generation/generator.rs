@@ -0,0 +1,51 @@
+use crate::detection::{Language, ProjectMetadata};
+use crate::generation::catalog::NixpkgsCatalog;
+
+/// Writes `<language>/packages.nix` for a language contributed by a custom
+/// detector.
+///
+/// The generated file is imported with `{ pkgs, lib }` and must evaluate to an
+/// attribute set with `packages` and `notices` lists, like the built-in
+/// language files. It is bound in `flake.nix` as `<dir_name>Packages`, so the
+/// language's `dir_name` has to be a valid Nix identifier.
+pub trait LanguageGenerator {
+    fn language(&self) -> Language;
+
+    fn generate_packages_nix(&self, metadata: &ProjectMetadata) -> String;
+}
+
+/// Generators for languages that are not built into autonix, and the nixpkgs
+/// catalog toolchain versions are resolved against.
+///
+/// Generators registered for built-in languages are ignored.
+#[derive(Default)]
+pub struct GeneratorRegistry {
+    language_generators: Vec<Box<dyn LanguageGenerator>>,
+    catalog: NixpkgsCatalog,
+}
+
+impl GeneratorRegistry {
+    pub fn set_catalog(&mut self, catalog: NixpkgsCatalog) -> &mut Self {
+        self.catalog = catalog;
+        self
+    }
+
+    pub fn catalog(&self) -> &NixpkgsCatalog {
+        &self.catalog
+    }
+
+    pub fn register_language_generator(
+        &mut self,
+        generator: impl LanguageGenerator + 'static,
+    ) -> &mut Self {
+        self.language_generators.push(Box::new(generator));
+        self
+    }
+
+    pub fn language_generators(&self) -> impl Iterator<Item = &dyn LanguageGenerator> {
+        self.language_generators
+            .iter()
+            .map(|generator| generator.as_ref())
+            .filter(|generator| matches!(generator.language(), Language::Custom(_)))
+    }
+}
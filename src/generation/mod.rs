@@ -1,5 +1,7 @@
+pub mod catalog;
 pub mod constants;
 pub mod dev_flake;
+pub mod generator;
 pub mod nix_builder;
 
 use std::{
@@ -7,14 +9,28 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::detection::ProjectMetadata;
+use crate::detection::{LocalFileSystem, ProjectMetadata};
 
-pub use dev_flake::generate_dev_flake;
+pub use catalog::{ChannelCatalog, NixpkgsCatalog, Toolchain};
 pub use dev_flake::{CheckCategory, CheckFile, GeneratedFlake, LanguagePackages};
+pub use dev_flake::{generate_dev_flake, generate_dev_flake_with_registry};
+pub use generator::{GeneratorRegistry, LanguageGenerator};
 
 pub fn write_dev_flake(metadata: &ProjectMetadata, root: &Path) -> Result<(), std::io::Error> {
-    let flake = generate_dev_flake(metadata, root);
+    write_dev_flake_with_registry(metadata, root, &GeneratorRegistry::default())
+}
+
+pub fn write_dev_flake_with_registry(
+    metadata: &ProjectMetadata,
+    root: &Path,
+    registry: &GeneratorRegistry,
+) -> Result<(), std::io::Error> {
+    let flake = generate_dev_flake_with_registry(metadata, root, registry, &LocalFileSystem);
+    write_flake_files(flake, root)
+}
 
+/// Writes a generated flake into `root/.autonix`.
+pub fn write_flake_files(flake: GeneratedFlake, root: &Path) -> Result<(), std::io::Error> {
     let autonix_dir = root.join(".autonix");
     fs::create_dir_all(&autonix_dir)?;
 
@@ -60,17 +60,25 @@ impl NoticeListBuilder {
     }
 
     pub fn build(&self, notice: Option<&str>) -> String {
+        let notices: Vec<&str> = notice.into_iter().collect();
+        self.build_all(&notices)
+    }
+
+    pub fn build_all(&self, notices: &[&str]) -> String {
         let mut buf = String::new();
         let child_indent = format!("{}  ", self.indent);
 
-        if let Some(msg) = notice {
+        if notices.is_empty() {
+            writeln!(buf, "{}notices = [];", self.indent).unwrap();
+            return buf;
+        }
+
+        writeln!(buf, "{}notices = [", self.indent).unwrap();
+        for msg in notices {
             let escaped = escape_nix_string(msg);
-            writeln!(buf, "{}notices = [", self.indent).unwrap();
             writeln!(buf, "{child_indent}\"{escaped}\"").unwrap();
-            writeln!(buf, "{}];", self.indent).unwrap();
-        } else {
-            writeln!(buf, "{}notices = [];", self.indent).unwrap();
         }
+        writeln!(buf, "{}];", self.indent).unwrap();
 
         buf
     }
@@ -83,6 +91,7 @@ pub struct CheckDerivationBuilder {
     required_exec: String,
     command: String,
     workdir: String,
+    toolchain_attr: Option<String>,
 }
 
 impl CheckDerivationBuilder {
@@ -101,9 +110,16 @@ impl CheckDerivationBuilder {
             required_exec,
             command,
             workdir,
+            toolchain_attr: None,
         }
     }
 
+    /// Puts `pkgs.<attr>` ahead of `devPackages`, if the channel has it.
+    pub fn with_toolchain_attr(mut self, attr: Option<String>) -> Self {
+        self.toolchain_attr = attr;
+        self
+    }
+
     pub fn build(&self) -> String {
         let mut out = String::new();
 
@@ -120,7 +136,17 @@ impl CheckDerivationBuilder {
         writeln!(out, "    workdir = \"{workdir_escaped}\";").unwrap();
         writeln!(out, "    display = \"{display_escaped}\";").unwrap();
         writeln!(out, "  in pkgs.runCommand \"{drv_escaped}\" {{").unwrap();
-        out.push_str("    nativeBuildInputs = devPackages;\n");
+        match &self.toolchain_attr {
+            Some(attr) => {
+                let attr = escape_nix_string(attr);
+                writeln!(
+                    out,
+                    "    nativeBuildInputs = lib.optional (builtins.hasAttr \"{attr}\" pkgs) pkgs.\"{attr}\" ++ devPackages;"
+                )
+                .unwrap();
+            }
+            None => out.push_str("    nativeBuildInputs = devPackages;\n"),
+        }
         out.push_str("  } ''\n");
         out.push_str("    set -euo pipefail\n");
         out.push_str("    export HOME=\"$TMPDIR/home\"\n");
@@ -198,6 +224,16 @@ mod tests {
         assert!(result.contains("\"Test notice\""));
     }
 
+    #[test]
+    fn test_notice_list_builder_with_several_notices() {
+        let builder = NoticeListBuilder::new("  ");
+        let result = builder.build_all(&["first", "second"]);
+        assert_eq!(
+            result,
+            "  notices = [\n    \"first\"\n    \"second\"\n  ];\n"
+        );
+    }
+
     #[test]
     fn test_notice_list_builder_empty() {
         let builder = NoticeListBuilder::new("  ");
@@ -221,5 +257,24 @@ mod tests {
         assert!(result.contains("cmd = \"npm run test\";"));
         assert!(result.contains("requiredExec = \"npm\";"));
         assert!(result.contains("pkgs.runCommand \"check-test-key\""));
+        assert!(result.contains("nativeBuildInputs = devPackages;"));
+    }
+
+    #[test]
+    fn test_check_derivation_builder_with_toolchain_attr() {
+        let builder = CheckDerivationBuilder::new(
+            "test-key-nodejs_20".to_string(),
+            "check-test-key-nodejs_20".to_string(),
+            "npm run test (nodejs_20)".to_string(),
+            "npm".to_string(),
+            "npm run test".to_string(),
+            ".".to_string(),
+        )
+        .with_toolchain_attr(Some("nodejs_20".to_string()));
+        let result = builder.build();
+
+        assert!(result.contains(
+            "nativeBuildInputs = lib.optional (builtins.hasAttr \"nodejs_20\" pkgs) pkgs.\"nodejs_20\" ++ devPackages;"
+        ));
     }
 }
@@ -1,6 +1,6 @@
 use autonix::*;
 use clap::Parser;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(name = "autonix")]
@@ -15,16 +15,49 @@ struct Args {
     #[arg(long, value_enum, default_value = "all", global = true)]
     detect_scope: DetectScope,
 
+    #[command(flatten)]
+    source: SourceArgs,
+
     #[arg(default_value = ".")]
     path: PathBuf,
 }
 
+/// Where the project's files are read from.
+#[derive(clap::Args, Debug)]
+struct SourceArgs {
+    #[arg(long, value_name = "REV", conflicts_with = "from_tar", global = true)]
+    from_git: Option<String>,
+
+    #[arg(long, value_name = "FILE", global = true)]
+    from_tar: Option<PathBuf>,
+
+    /// Re-parse every file instead of reusing cached detection results
+    #[arg(long, global = true)]
+    no_cache: bool,
+}
+
 #[derive(Parser, Debug)]
 enum Command {
     Generate {
         #[arg(default_value = ".")]
         path: PathBuf,
     },
+    /// Report version sources that disagree; exits nonzero if any do
+    CheckVersions {
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Record the toolchain attributes of a local nixpkgs checkout
+    RefreshCatalog {
+        nixpkgs: PathBuf,
+
+        #[arg(long, default_value = constants::NIXPKGS_CHANNEL)]
+        channel: String,
+
+        /// Catalog file to update instead of the user catalog
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -50,20 +83,90 @@ impl From<DetectScope> for DetectionScope {
 
 fn main() {
     let args = Args::parse();
-    let engine = DetectionEngine;
+    let engine = DetectionEngine::default();
     let detect_scope: DetectionScope = args.detect_scope.into();
 
     match args.command {
         Some(Command::Generate { path }) => {
-            let metadata = engine.detect_with_scope(&path, detect_scope);
-            if let Err(e) = write_dev_flake(&metadata, &path) {
+            let project = open_source(&path, &args.source);
+            let metadata = detect(&engine, &project, detect_scope, args.source.no_cache);
+            let mut registry = GeneratorRegistry::default();
+            if let Some(catalog_path) = NixpkgsCatalog::default_path() {
+                registry.set_catalog(NixpkgsCatalog::load(&catalog_path));
+            }
+            let flake = generate_dev_flake_with_registry(
+                &metadata,
+                &project.root,
+                &registry,
+                project.fs.as_ref(),
+            );
+            if let Err(e) = write_flake_files(flake, &path) {
                 eprintln!("Failed to write flake files: {e}");
                 std::process::exit(1);
             }
             println!("Generated flake structure in {}", path.display());
         }
+        Some(Command::CheckVersions { path }) => {
+            let project = open_source(&path, &args.source);
+            let metadata = detect(&engine, &project, detect_scope, args.source.no_cache);
+            if metadata.version_conflicts.is_empty() {
+                println!("No version conflicts in {}", path.display());
+                return;
+            }
+
+            for conflict in &metadata.version_conflicts {
+                println!("{}", conflict.summary());
+                for disagreement in &conflict.disagreements {
+                    println!(
+                        "  {} ({}) vs {} ({})",
+                        disagreement.first.raw,
+                        disagreement.first.path.display(),
+                        disagreement.second.raw,
+                        disagreement.second.path.display()
+                    );
+                }
+            }
+            std::process::exit(1);
+        }
+        Some(Command::RefreshCatalog {
+            nixpkgs,
+            channel,
+            output,
+        }) => {
+            let Some(output) = output.or_else(NixpkgsCatalog::default_path) else {
+                eprintln!("Cannot locate the user catalog; pass --output");
+                std::process::exit(1);
+            };
+            let scanned = ChannelCatalog::from_checkout(&nixpkgs).unwrap_or_else(|e| {
+                eprintln!("Failed to read nixpkgs checkout: {e}");
+                std::process::exit(1);
+            });
+
+            for toolchain in Toolchain::ALL {
+                let attrs: Vec<String> = scanned
+                    .attrs(toolchain)
+                    .iter()
+                    .map(|attr| {
+                        if scanned.is_prerelease(toolchain, attr) {
+                            format!("{attr} (pre-release)")
+                        } else {
+                            attr.clone()
+                        }
+                    })
+                    .collect();
+                println!("{toolchain:?}: {}", attrs.join(", "));
+            }
+            let mut catalog = NixpkgsCatalog::read(&output).unwrap_or_else(NixpkgsCatalog::empty);
+            catalog.set_channel(channel.as_str(), scanned);
+            if let Err(e) = catalog.save(&output) {
+                eprintln!("Failed to write catalog: {e}");
+                std::process::exit(1);
+            }
+            println!("Updated {channel} in {}", output.display());
+        }
         None => {
-            let metadata = engine.detect_with_scope(&args.path, detect_scope);
+            let project = open_source(&args.path, &args.source);
+            let metadata = detect(&engine, &project, detect_scope, args.source.no_cache);
             match args.format {
                 OutputFormat::Debug => {
                     println!("{:#?}", metadata);
@@ -79,3 +182,70 @@ fn main() {
         }
     }
 }
+
+/// The files of a project and the directory within them it sits at.
+struct ProjectFiles {
+    fs: Box<dyn FileSystem>,
+    root: PathBuf,
+    /// Whether `fs` is the working tree, which detection may cache.
+    local: bool,
+}
+
+/// The working tree at `path`, or the revision or archive `source` selects.
+fn open_source(path: &Path, source: &SourceArgs) -> ProjectFiles {
+    let fs: std::io::Result<Box<dyn FileSystem>> = match (&source.from_git, &source.from_tar) {
+        (Some(rev), _) => GitTreeFileSystem::new(path, rev).map(|fs| Box::new(fs) as _),
+        (None, Some(archive)) => TarFileSystem::open(archive).map(|fs| Box::new(fs) as _),
+        (None, None) => {
+            return ProjectFiles {
+                fs: Box::new(LocalFileSystem),
+                root: path.to_path_buf(),
+                local: true,
+            };
+        }
+    };
+    match fs {
+        Ok(fs) => ProjectFiles {
+            fs,
+            root: PathBuf::from("."),
+            local: false,
+        },
+        Err(e) => {
+            eprintln!("Failed to read project source: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn detect(
+    engine: &DetectionEngine,
+    project: &ProjectFiles,
+    scope: DetectionScope,
+    no_cache: bool,
+) -> ProjectMetadata {
+    if project.local {
+        detect_local(engine, &project.root, scope, no_cache)
+    } else {
+        engine.detect_with_fs(project.fs.as_ref(), &project.root, scope)
+    }
+}
+
+fn detect_local(
+    engine: &DetectionEngine,
+    path: &Path,
+    scope: DetectionScope,
+    no_cache: bool,
+) -> ProjectMetadata {
+    let cache_path = (!no_cache)
+        .then(|| DetectionCache::default_path(path))
+        .flatten();
+    let Some(cache_path) = cache_path else {
+        return engine.detect_with_scope(path, scope);
+    };
+
+    let mut cache = DetectionCache::load(&cache_path);
+    let metadata = engine.detect_with_cache(&LocalFileSystem, path, scope, &mut cache);
+    // An unwritable cache (read-only home, sandboxed builds) only costs speed.
+    let _ = cache.save(&cache_path);
+    metadata
+}